@@ -10,128 +10,11 @@ using System.Text;
 using System.Text.Json;
 using System.Threading;
 using System.Threading.Tasks;
+using TerminalRacer.Core.Constants;
+using TerminalRacer.Rendering.FFI;
 
 namespace TerminalRacer
 {
-    // Enhanced FFI structures
-    [StructLayout(LayoutKind.Sequential)]
-    public struct GameState
-    {
-        // Player 1
-        public int PlayerPosition;
-        public float PlayerSpeed;
-        public float PlayerDistance;
-        public int PlayerHealth;
-        public int PlayerScore;
-        public int PlayerCarType;
-        
-        // Player 2
-        [MarshalAs(UnmanagedType.I1)] public bool Player2Active;
-        public int Player2Position;
-        public float Player2Speed;
-        public float Player2Distance;
-        public int Player2Health;
-        public int Player2Score;
-        public int Player2CarType;
-        
-        // Game state
-        public float LapTime;
-        public int GameMode;
-        public int TrackType;
-        public int Level;
-        public float CareerProgress;
-        
-        // Powerups
-        [MarshalAs(UnmanagedType.I1)] public bool BoostActive;
-        public float BoostRemaining;
-        [MarshalAs(UnmanagedType.I1)] public bool ShieldActive;
-        public float ShieldRemaining;
-        [MarshalAs(UnmanagedType.I1)] public bool InvincibilityActive;
-        public float InvincibilityRemaining;
-        [MarshalAs(UnmanagedType.I1)] public bool MagnetActive;
-        public float MagnetRemaining;
-        [MarshalAs(UnmanagedType.I1)] public bool SlowmoActive;
-        public float SlowmoRemaining;
-        
-        // Objects
-        public int CarCount;
-        public IntPtr AiPositions;
-        public IntPtr AiDistances;
-        public IntPtr AiTypes;
-        public IntPtr AiIsBoss;
-        
-        public int ObstacleCount;
-        public IntPtr ObstaclePositions;
-        public IntPtr ObstacleDistances;
-        public IntPtr ObstacleTypes;
-        
-        public int BuildingCount;
-        public IntPtr BuildingPositions;
-        public IntPtr BuildingDistances;
-        public IntPtr BuildingHeights;
-        public IntPtr BuildingTypes;
-        
-        // Environment
-        public int Weather;
-        public float CurveOffset;
-        public float Elevation;
-        public float TunnelDarkness;
-        
-        // Meta
-        public int Combo;
-        [MarshalAs(UnmanagedType.I1)] public bool ReplayMode;
-        public int GhostPosition;
-        public float GhostDistance;
-    }
-
-    [StructLayout(LayoutKind.Sequential)]
-    public struct InputState
-    {
-        [MarshalAs(UnmanagedType.I1)] public bool P1Left;
-        [MarshalAs(UnmanagedType.I1)] public bool P1Right;
-        [MarshalAs(UnmanagedType.I1)] public bool P1Accel;
-        [MarshalAs(UnmanagedType.I1)] public bool P1Brake;
-        [MarshalAs(UnmanagedType.I1)] public bool P1Boost;
-        
-        [MarshalAs(UnmanagedType.I1)] public bool P2Left;
-        [MarshalAs(UnmanagedType.I1)] public bool P2Right;
-        [MarshalAs(UnmanagedType.I1)] public bool P2Accel;
-        [MarshalAs(UnmanagedType.I1)] public bool P2Brake;
-        [MarshalAs(UnmanagedType.I1)] public bool P2Boost;
-        
-        [MarshalAs(UnmanagedType.I1)] public bool Quit;
-        [MarshalAs(UnmanagedType.I1)] public bool Pause;
-        [MarshalAs(UnmanagedType.I1)] public bool Menu;
-    }
-
-    public static class RatatuiFFI
-    {
-        private const string LibName = "rust_renderer";
-        
-        [DllImport(LibName, CallingConvention = CallingConvention.Cdecl)]
-        [return: MarshalAs(UnmanagedType.I1)]
-        public static extern bool ratatui_init();
-        
-        [DllImport(LibName, CallingConvention = CallingConvention.Cdecl)]
-        public static extern void ratatui_cleanup();
-        
-        [DllImport(LibName, CallingConvention = CallingConvention.Cdecl)]
-        [return: MarshalAs(UnmanagedType.I1)]
-        public static extern bool ratatui_poll_input(ref InputState input);
-        
-        [DllImport(LibName, CallingConvention = CallingConvention.Cdecl)]
-        [return: MarshalAs(UnmanagedType.I1)]
-        public static extern bool ratatui_render(ref GameState state);
-        
-        [DllImport(LibName, CallingConvention = CallingConvention.Cdecl)]
-        [return: MarshalAs(UnmanagedType.I1)]
-        public static extern bool ratatui_render_menu(
-            [MarshalAs(UnmanagedType.LPStr)] string title,
-            [MarshalAs(UnmanagedType.LPArray)] string[] options,
-            int optionCount,
-            int selected);
-    }
-
     public enum GameMode { Single = 0, Splitscreen = 1, Career = 2, Replay = 3 }
     public enum CarType { Sports = 0, Police = 1, Racer = 2, Truck = 3, Taxi = 4, Van = 5, Muscle = 6, Convertible = 7, Limo = 8 }
     public enum ObstacleType { Cone = 0, Oil = 1, Boost = 2, Star = 3, Magnet = 4, Clock = 5 }
@@ -598,7 +481,11 @@ namespace TerminalRacer
         public async Task<bool> Initialize()
         {
             if (!RatatuiFFI.ratatui_init())
-                throw new Exception("Failed to initialize terminal");
+                throw new Exception($"Failed to initialize terminal: {NativeError.Last()}");
+            
+            // The renderer paces frames and sheds effects on slow terminals
+            RatatuiFFI.ratatui_set_target_fps((uint)GameConstants.TargetFPS);
+            RatatuiFFI.ratatui_set_quality(RatatuiFFI.QualityLevels.High, true);
             
             // Initialize players
             player1Car = new Car(1, 0, 0, true, CarType.Sports);
@@ -1117,6 +1004,7 @@ namespace TerminalRacer
                 LapTime = (float)gameTimer.Elapsed.TotalSeconds,
                 GameMode = (int)currentMode,
                 TrackType = (int)currentTrack,
+                LaneCount = NumLanes,
                 Level = currentLevel,
                 CareerProgress = careerProgress,
                 
@@ -1226,11 +1114,11 @@ namespace TerminalRacer
                 
                 if (!Render())
                 {
-                    Console.Error.WriteLine("Render failed!");
+                    Console.Error.WriteLine($"Render failed: {NativeError.Last()}");
                     break;
                 }
                 
-                Thread.Sleep(16); // ~60 FPS
+                RatatuiFFI.ratatui_wait_frame();
             }
             
             GameOver();
@@ -1248,10 +1136,12 @@ namespace TerminalRacer
                 var input = new InputState();
                 RatatuiFFI.ratatui_poll_input(ref input);
                 
-                if (input.P1Accel) selected = Math.Max(0, selected - 1);
-                if (input.P1Brake) selected = Math.Min(options.Length - 1, selected + 1);
+                if (input.MenuUp) selected = Math.Max(0, selected - 1);
+                if (input.MenuDown) selected = Math.Min(options.Length - 1, selected + 1);
+                if (input.MenuPageUp) selected = 0;
+                if (input.MenuPageDown) selected = options.Length - 1;
                 
-                if (input.P1Boost) // Enter
+                if (input.MenuConfirm)
                 {
                     switch (selected)
                     {
@@ -1263,7 +1153,7 @@ namespace TerminalRacer
                     }
                 }
                 
-                if (input.Quit || input.Pause) return;
+                if (input.MenuBack || input.Quit || input.Pause) return;
                 
                 Thread.Sleep(50);
             }
@@ -41,7 +41,6 @@ public static class GameConstants
     // Timing
     public const float ComboDuration = 3.0f;
     public const int TargetFPS = 60;
-    public const int FrameDelayMs = 16; // ~60 FPS
     
     // Files
     public const string ReplayFolder = "replays";
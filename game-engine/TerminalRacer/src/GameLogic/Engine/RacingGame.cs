@@ -91,7 +91,7 @@ public class RacingGame
     public async Task<bool> Initialize()
     {
         if (!_renderer.Initialize())
-            throw new Exception("Failed to initialize renderer");
+            throw new Exception($"Failed to initialize renderer: {_renderer.LastError}");
         
         // Initialize players
         _player1Car = new Car(1, 0, 0, true, CarType.Sports);
@@ -206,6 +206,12 @@ public class RacingGame
             
             if (input.Quit) break;
             
+            // Pause while the player is in another window
+            while (_renderer.NextEvent(out var ev))
+            {
+                if (ev.Kind == RatatuiFFI.EventKinds.FocusLost) paused = true;
+            }
+            
             if (input.Pause)
             {
                 paused = !paused;
@@ -244,7 +250,7 @@ public class RacingGame
                 break;
             }
             
-            Thread.Sleep(GameConstants.FrameDelayMs);
+            _renderer.WaitFrame();
         }
         
         GameOver();
@@ -255,6 +261,19 @@ public class RacingGame
         var options = new[] { "Resume", "Restart", "Change Track", "Save Replay", "Quit" };
         int selected = 0;
         
+        _renderer.SetMouseCapture(true);
+        try
+        {
+            RunInGameMenu(options, ref selected);
+        }
+        finally
+        {
+            _renderer.SetMouseCapture(false);
+        }
+    }
+    
+    private void RunInGameMenu(string[] options, ref int selected)
+    {
         while (true)
         {
             _renderer.RenderMenu("PAUSE MENU", options, selected);
@@ -262,10 +281,32 @@ public class RacingGame
             var input = new InputState();
             _renderer.PollInput(ref input);
             
-            if (input.P1Accel) selected = Math.Max(0, selected - 1);
-            if (input.P1Brake) selected = Math.Min(options.Length - 1, selected + 1);
+            // A click selects and activates an option; the wheel moves the selection
+            bool clicked = false;
+            while (_renderer.PollMouse(out var mouse))
+            {
+                if (mouse.Target == RatatuiFFI.HitTargets.MenuItem && mouse.Kind == RatatuiFFI.MouseKinds.Down
+                    && mouse.Button == RatatuiFFI.MouseButtons.Left)
+                {
+                    selected = mouse.Item;
+                    clicked = true;
+                }
+                else if (mouse.Kind == RatatuiFFI.MouseKinds.ScrollUp)
+                {
+                    selected = Math.Max(0, selected - 1);
+                }
+                else if (mouse.Kind == RatatuiFFI.MouseKinds.ScrollDown)
+                {
+                    selected = Math.Min(options.Length - 1, selected + 1);
+                }
+            }
+            
+            if (input.MenuUp) selected = Math.Max(0, selected - 1);
+            if (input.MenuDown) selected = Math.Min(options.Length - 1, selected + 1);
+            if (input.MenuPageUp) selected = 0;
+            if (input.MenuPageDown) selected = options.Length - 1;
             
-            if (input.P1Boost)
+            if (input.MenuConfirm || clicked)
             {
                 switch (selected)
                 {
@@ -277,7 +318,7 @@ public class RacingGame
                 }
             }
             
-            if (input.Quit || input.Pause) return;
+            if (input.MenuBack || input.Quit || input.Pause) return;
             
             Thread.Sleep(50);
         }
@@ -1,5 +1,6 @@
 using System.Diagnostics;
 using System.Runtime.InteropServices;
+using TerminalRacer.Core.Constants;
 using TerminalRacer.Core.Enums;
 using TerminalRacer.Core.Models;
 using TerminalRacer.GameLogic.Interfaces;
@@ -102,6 +103,7 @@ public class GameStateManager
             LapTime = (float)gameTimer.Elapsed.TotalSeconds,
             GameMode = (int)_gameMode,
             TrackType = (int)_trackType,
+            LaneCount = GameConstants.NumLanes,
             Level = currentLevel,
             CareerProgress = careerProgress,
             
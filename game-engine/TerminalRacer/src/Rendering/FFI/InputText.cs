@@ -0,0 +1,27 @@
+using System.Text;
+
+namespace TerminalRacer.Rendering.FFI;
+
+/// <summary>
+/// Reads the characters typed during a poll, e.g. for name entry.
+/// </summary>
+public static class InputText
+{
+    /// <summary>
+    /// Returns the text typed since the previous poll, or an empty string.
+    /// </summary>
+    public static string Typed(this in InputState input)
+    {
+        if (input.Text == null || input.TextLen == 0)
+        {
+            return string.Empty;
+        }
+        
+        var text = new StringBuilder();
+        for (int i = 0; i < Math.Min((int)input.TextLen, input.Text.Length); i++)
+        {
+            text.Append(char.ConvertFromUtf32((int)input.Text[i]));
+        }
+        return text.ToString();
+    }
+}
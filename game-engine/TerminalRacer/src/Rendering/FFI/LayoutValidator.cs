@@ -0,0 +1,41 @@
+using System.Reflection;
+using System.Runtime.InteropServices;
+
+namespace TerminalRacer.Rendering.FFI;
+
+/// <summary>
+/// Checks that the marshalled FFI structs match the Rust renderer's layout.
+/// </summary>
+public static class LayoutValidator
+{
+    /// <summary>
+    /// Verifies the ABI version and reports struct sizes and field offsets to
+    /// the renderer. Returns an error message, or null when everything matches.
+    /// </summary>
+    public static string? Validate()
+    {
+        uint nativeVersion = RatatuiFFI.ratatui_abi_version();
+        if (nativeVersion != RatatuiFFI.AbiVersion)
+        {
+            return $"Renderer ABI version {nativeVersion} does not match bindings version {RatatuiFFI.AbiVersion}";
+        }
+        
+        var gameOffsets = FieldOffsets<GameState>();
+        var inputOffsets = FieldOffsets<InputState>();
+        
+        bool ok = RatatuiFFI.ratatui_validate_layout(
+            (UIntPtr)Marshal.SizeOf<GameState>(), gameOffsets, (UIntPtr)gameOffsets.Length,
+            (UIntPtr)Marshal.SizeOf<InputState>(), inputOffsets, (UIntPtr)inputOffsets.Length);
+        
+        return ok ? null : NativeError.Last() ?? "GameState/InputState layout does not match the renderer";
+    }
+    
+    private static UIntPtr[] FieldOffsets<T>() where T : struct
+    {
+        return typeof(T)
+            .GetFields(BindingFlags.Public | BindingFlags.Instance)
+            .OrderBy(f => f.MetadataToken) // declaration order
+            .Select(f => (UIntPtr)Marshal.OffsetOf<T>(f.Name).ToInt64())
+            .ToArray();
+    }
+}
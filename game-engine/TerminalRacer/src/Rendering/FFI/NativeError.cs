@@ -0,0 +1,27 @@
+using System.Text;
+
+namespace TerminalRacer.Rendering.FFI;
+
+/// <summary>
+/// Reads the reason for the last failed renderer call on the calling thread.
+/// </summary>
+public static class NativeError
+{
+    /// <summary>
+    /// Describes the last failure as "Code: message", or returns null when the
+    /// renderer has not reported one.
+    /// </summary>
+    public static string? Last()
+    {
+        ErrorCode code = RatatuiFFI.ratatui_last_error_code();
+        if (code == ErrorCode.Ok)
+        {
+            return null;
+        }
+        
+        int len = RatatuiFFI.ratatui_last_error(null, UIntPtr.Zero);
+        var buf = new byte[Math.Max(len, 0) + 1];
+        RatatuiFFI.ratatui_last_error(buf, (UIntPtr)buf.Length);
+        return $"{code}: {Encoding.UTF8.GetString(buf, 0, Math.Max(len, 0))}";
+    }
+}
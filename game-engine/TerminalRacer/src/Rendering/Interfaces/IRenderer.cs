@@ -9,4 +9,28 @@ public interface IRenderer
     bool PollInput(ref InputState input);
     bool Render(ref GameState state);
     bool RenderMenu(string title, string[] options, int selected);
+    
+    /// <summary>Switches to one of <see cref="RatatuiFFI.AssetStyles"/>; takes effect from the next frame.</summary>
+    bool SetAssetStyle(int style);
+    
+    /// <summary>Turns mouse reporting on or off; off by default.</summary>
+    bool SetMouseCapture(bool enabled);
+    
+    /// <summary>Takes the next mouse event read by <see cref="PollInput"/>, hit-tested against the last frame.</summary>
+    bool PollMouse(out MouseEvent mouse);
+    
+    /// <summary>Takes the next key, resize, focus, mouse or paste event; false once none is pending.</summary>
+    bool NextEvent(out Event ev);
+    
+    /// <summary>Text of the last paste event taken with <see cref="NextEvent"/>.</summary>
+    string EventText();
+    
+    /// <summary>Waits until the next frame is due at the target rate, reading input meanwhile.</summary>
+    bool WaitFrame();
+    
+    /// <summary>Frame counter, measured frame cost, target rate and current quality.</summary>
+    FrameStats FrameStats { get; }
+    
+    /// <summary>Reason for the last failed call, or null if none was reported.</summary>
+    string? LastError { get; }
 }
\ No newline at end of file
@@ -1,3 +1,5 @@
+using System.Text;
+using TerminalRacer.Core.Constants;
 using TerminalRacer.Rendering.FFI;
 using TerminalRacer.Rendering.Interfaces;
 
@@ -5,7 +7,55 @@ namespace TerminalRacer.Rendering;
 
 public class RatatuiRenderer : IRenderer
 {
-    public bool Initialize() => RatatuiFFI.ratatui_init();
+    /// <summary>Optional key binding overrides, read from the working directory</summary>
+    public const string BindingsFile = "keybindings.toml";
+    
+    /// <summary>Optional asset pack with extra or replacement art, read from the working directory</summary>
+    public const string AssetPackFile = "assets.toml";
+    
+    public bool Initialize()
+    {
+        string? layoutError = LayoutValidator.Validate();
+        if (layoutError != null)
+        {
+            Console.Error.WriteLine(layoutError);
+            return false;
+        }
+        
+        if (!RatatuiFFI.ratatui_init())
+        {
+            Console.Error.WriteLine($"Renderer initialization failed: {LastError}");
+            return false;
+        }
+        
+        // Frames are paced by the renderer, which also drops rain, particles
+        // and building windows while frames take longer than the budget
+        RatatuiFFI.ratatui_set_target_fps((uint)GameConstants.TargetFPS);
+        RatatuiFFI.ratatui_set_quality(RatatuiFFI.QualityLevels.High, true);
+        
+        // Bad binding files are reported but do not stop the game; the
+        // default keys stay active
+        if (File.Exists(BindingsFile) && !RatatuiFFI.ratatui_load_bindings(BindingsFile))
+        {
+            Console.Error.WriteLine($"Ignoring {BindingsFile}: {LastError}");
+        }
+        
+        // Likewise a bad asset pack keeps the built-in art. Debug builds
+        // reload the pack on every save and show load errors on screen.
+#if DEBUG
+        if (File.Exists(AssetPackFile))
+        {
+            RatatuiFFI.ratatui_watch_asset_pack(AssetPackFile);
+        }
+#else
+        if (File.Exists(AssetPackFile) && !RatatuiFFI.ratatui_load_asset_pack(AssetPackFile))
+        {
+            Console.Error.WriteLine($"Ignoring {AssetPackFile}: {LastError}");
+        }
+#endif
+        
+        return true;
+    }
     
     public void Cleanup() => RatatuiFFI.ratatui_cleanup();
     
@@ -15,4 +65,42 @@ public class RatatuiRenderer : IRenderer
     
     public bool RenderMenu(string title, string[] options, int selected) =>
         RatatuiFFI.ratatui_render_menu(title, options, options.Length, selected);
+    
+    public bool SetAssetStyle(int style) => RatatuiFFI.ratatui_set_asset_style(style);
+    
+    public bool SetMouseCapture(bool enabled) => RatatuiFFI.ratatui_set_mouse_capture(enabled);
+    
+    public bool PollMouse(out MouseEvent mouse)
+    {
+        mouse = default;
+        return RatatuiFFI.ratatui_poll_mouse(ref mouse);
+    }
+    
+    public bool NextEvent(out Event ev)
+    {
+        ev = default;
+        return RatatuiFFI.ratatui_next_event(ref ev);
+    }
+    
+    public string EventText()
+    {
+        int len = RatatuiFFI.ratatui_event_text(null, UIntPtr.Zero);
+        var buf = new byte[Math.Max(len, 0) + 1];
+        RatatuiFFI.ratatui_event_text(buf, (UIntPtr)buf.Length);
+        return Encoding.UTF8.GetString(buf, 0, Math.Max(len, 0));
+    }
+    
+    public bool WaitFrame() => RatatuiFFI.ratatui_wait_frame();
+    
+    public FrameStats FrameStats
+    {
+        get
+        {
+            var stats = default(FrameStats);
+            RatatuiFFI.ratatui_frame_stats(ref stats);
+            return stats;
+        }
+    }
+    
+    public string? LastError => NativeError.Last();
 }
@@ -0,0 +1,70 @@
+//! Full-frame render cost for every game mode, track type and weather
+//! Each frame is a busy race (see `Objects::crowded`) with particles in
+//! flight, drawn into an off-screen buffer at three terminal sizes. At 60 FPS
+//! the whole frame, host included, has 16.7 ms.
+//!
+//! `cargo bench --bench frame`, or `cargo bench --bench frame -- 240x72/split`
+//! for one size and mode.
+
+#[path = "../tests/common/mod.rs"]
+mod common;
+
+use std::hint::black_box;
+use std::time::Duration;
+use common::*;
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use ratatui::{backend::TestBackend, Terminal};
+use rust_renderer::core::types::GameState;
+use rust_renderer::render_game_with;
+use rust_renderer::rendering::Scene;
+
+/// Terminal sizes (columns, rows); the smallest fits every mode's minimum
+const SIZES: [(u16, u16); 3] = [(100, 30), (160, 48), (240, 72)];
+
+/// Half a second of frames at 30 fps, enough to fill the particle emitters
+fn warm_scene(state: &GameState) -> Scene {
+    let mut scene = Scene::default();
+    for _ in 0..15 {
+        scene.step(state, 1.0 / 30.0);
+    }
+    scene
+}
+
+fn frames(c: &mut Criterion) {
+    let objects = Objects::crowded();
+
+    for (width, height) in SIZES {
+        let mut group = c.benchmark_group(format!("{}x{}", width, height));
+        let mut terminal = Terminal::new(TestBackend::new(width, height)).unwrap();
+
+        for (game_mode, mode) in GAME_MODES {
+            for (track_type, track) in TRACK_TYPES {
+                for (weather_type, weather) in WEATHER {
+                    let mut state = base_state(game_mode, track_type, weather_type);
+                    state.boost_active = true;
+                    objects.attach(&mut state);
+                    let scene = warm_scene(&state);
+
+                    let id = BenchmarkId::new(mode, format!("{}/{}", track, weather));
+                    group.bench_function(id, |b| {
+                        b.iter(|| {
+                            terminal.draw(|f| render_game_with(f, black_box(&state), &scene)).unwrap();
+                        });
+                    });
+                }
+            }
+        }
+        group.finish();
+    }
+}
+
+criterion_group! {
+    name = benches;
+    // 240 frames to time; keep a full run to a few minutes
+    config = Criterion::default()
+        .sample_size(20)
+        .warm_up_time(Duration::from_millis(300))
+        .measurement_time(Duration::from_secs(1));
+    targets = frames
+}
+criterion_main!(benches);
@@ -0,0 +1,104 @@
+//! Per-frame render cost on a 200x60 terminal
+//! Compares one `Paragraph` per glyph, the way the track renderers used to
+//! draw, with the direct buffer writes of `rendering::draw`, then times whole
+//! frames. Run with `cargo bench --bench render`
+
+#[path = "../tests/common/mod.rs"]
+mod common;
+
+use std::hint::black_box;
+use common::*;
+use criterion::{criterion_group, criterion_main, Criterion};
+use ratatui::{
+    backend::TestBackend,
+    layout::Rect,
+    style::{Color, Style},
+    widgets::Paragraph,
+    Terminal,
+};
+use rust_renderer::core::types::{game_modes, weather};
+use rust_renderer::rendering::draw;
+
+const WIDTH: u16 = 200;
+const HEIGHT: u16 = 60;
+
+fn glyphs(c: &mut Criterion) {
+    let mut terminal = Terminal::new(TestBackend::new(WIDTH, HEIGHT)).unwrap();
+    let style = Style::default().fg(Color::DarkGray);
+
+    // Every cell of the frame, one glyph at a time
+    let mut group = c.benchmark_group("glyphs");
+    group.bench_function("paragraph", |b| {
+        b.iter(|| {
+            terminal.draw(|f| {
+                let area = f.area();
+                for y in area.top()..area.bottom() {
+                    for x in area.left()..area.right() {
+                        f.render_widget(Paragraph::new("▒").style(style), Rect::new(x, y, 1, 1));
+                    }
+                }
+            }).unwrap();
+        });
+    });
+    group.bench_function("put", |b| {
+        b.iter(|| {
+            terminal.draw(|f| {
+                let area = f.area();
+                let buf = f.buffer_mut();
+                for y in area.top()..area.bottom() {
+                    for x in area.left()..area.right() {
+                        draw::put(buf, area, x, y, "▒", style);
+                    }
+                }
+            }).unwrap();
+        });
+    });
+    group.finish();
+
+    // Every row as one run, like the road surface
+    let mut group = c.benchmark_group("rows");
+    group.bench_function("paragraph", |b| {
+        b.iter(|| {
+            terminal.draw(|f| {
+                let area = f.area();
+                for y in area.top()..area.bottom() {
+                    let row = Rect::new(area.x, y, area.width, 1);
+                    f.render_widget(Paragraph::new("▒".repeat(area.width as usize)).style(style), row);
+                }
+            }).unwrap();
+        });
+    });
+    group.bench_function("fill", |b| {
+        b.iter(|| {
+            terminal.draw(|f| {
+                let area = f.area();
+                let buf = f.buffer_mut();
+                for y in area.top()..area.bottom() {
+                    draw::fill(buf, area, area.x, y, area.width, "▒", style);
+                }
+            }).unwrap();
+        });
+    });
+    group.finish();
+}
+
+/// A full `render_game` per track type, in rain
+fn frames(c: &mut Criterion) {
+    let objects = Objects::standard();
+    let mut terminal = Terminal::new(TestBackend::new(WIDTH, HEIGHT)).unwrap();
+
+    let mut group = c.benchmark_group("frames");
+    for (track_type, name) in TRACK_TYPES {
+        let mut state = base_state(game_modes::SINGLE_PLAYER, track_type, weather::RAIN);
+        objects.attach(&mut state);
+        group.bench_function(name, |b| {
+            b.iter(|| {
+                terminal.draw(|f| rust_renderer::render_game(f, black_box(&state))).unwrap();
+            });
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, glyphs, frames);
+criterion_main!(benches);
@@ -0,0 +1,823 @@
+// <auto-generated>
+// Generated by rust-renderer/build.rs from the Rust sources. Do not edit.
+// </auto-generated>
+#nullable enable
+
+using System.Runtime.InteropServices;
+
+namespace TerminalRacer.Rendering.FFI;
+
+/// <summary>
+/// Category of the last failure
+/// </summary>
+public enum ErrorCode : int
+{
+    /// <summary>
+    /// No error has been recorded on this thread
+    /// </summary>
+    Ok = 0,
+    /// <summary>
+    /// A required pointer argument was null
+    /// </summary>
+    NullPointer = 1,
+    /// <summary>
+    /// A string argument was not valid UTF-8
+    /// </summary>
+    InvalidUtf8 = 2,
+    /// <summary>
+    /// An argument was out of range, e.g. a zero-sized headless renderer
+    /// </summary>
+    InvalidArgument = 3,
+    /// <summary>
+    /// The global renderer has not been initialized
+    /// </summary>
+    NotInitialized = 4,
+    /// <summary>
+    /// Another renderer already owns the terminal
+    /// </summary>
+    TerminalBusy = 5,
+    /// <summary>
+    /// The terminal refused raw mode
+    /// </summary>
+    RawModeDenied = 6,
+    /// <summary>
+    /// Reading input or drawing to the terminal failed
+    /// </summary>
+    Io = 7,
+    /// <summary>
+    /// The host struct layout does not match Rust
+    /// </summary>
+    LayoutMismatch = 8,
+    /// <summary>
+    /// The operation needs a headless renderer
+    /// </summary>
+    NotHeadless = 9,
+    /// <summary>
+    /// The renderer panicked and must be destroyed
+    /// </summary>
+    Panic = 10,
+    /// <summary>
+    /// A key binding table could not be parsed
+    /// </summary>
+    InvalidBindings = 11,
+    /// <summary>
+    /// A key is bound to more than one input
+    /// </summary>
+    BindingConflict = 12,
+    /// <summary>
+    /// An asset pack could not be parsed or a sprite does not fit
+    /// </summary>
+    InvalidAssets = 13,
+}
+
+/// <summary>
+/// Enhanced game state with comprehensive feature support
+/// Includes player state, multiplayer support, powerups, AI, obstacles, and environment
+/// </summary>
+[StructLayout(LayoutKind.Sequential)]
+public struct GameState
+{
+    public int PlayerPosition;
+    public float PlayerSpeed;
+    public float PlayerDistance;
+    public int PlayerHealth;
+    public int PlayerScore;
+    public int PlayerCarType;
+    [MarshalAs(UnmanagedType.I1)] public bool Player2Active;
+    public int Player2Position;
+    public float Player2Speed;
+    public float Player2Distance;
+    public int Player2Health;
+    public int Player2Score;
+    public int Player2CarType;
+    public float LapTime;
+    public int GameMode;
+    public int TrackType;
+    public int LaneCount;
+    public int Level;
+    public float CareerProgress;
+    [MarshalAs(UnmanagedType.I1)] public bool BoostActive;
+    public float BoostRemaining;
+    [MarshalAs(UnmanagedType.I1)] public bool ShieldActive;
+    public float ShieldRemaining;
+    [MarshalAs(UnmanagedType.I1)] public bool InvincibilityActive;
+    public float InvincibilityRemaining;
+    [MarshalAs(UnmanagedType.I1)] public bool MagnetActive;
+    public float MagnetRemaining;
+    [MarshalAs(UnmanagedType.I1)] public bool SlowmoActive;
+    public float SlowmoRemaining;
+    public int CarCount;
+    public IntPtr AiPositions;
+    public IntPtr AiDistances;
+    public IntPtr AiTypes;
+    public IntPtr AiIsBoss;
+    public int ObstacleCount;
+    public IntPtr ObstaclePositions;
+    public IntPtr ObstacleDistances;
+    public IntPtr ObstacleTypes;
+    public int BuildingCount;
+    public IntPtr BuildingPositions;
+    public IntPtr BuildingDistances;
+    public IntPtr BuildingHeights;
+    public IntPtr BuildingTypes;
+    public int Weather;
+    public float CurveOffset;
+    public float Elevation;
+    public float TunnelDarkness;
+    public int Combo;
+    [MarshalAs(UnmanagedType.I1)] public bool ReplayMode;
+    public float ReplayProgress;
+    public int GhostPosition;
+    public float GhostDistance;
+}
+
+/// <summary>
+/// Input state for both players and system controls
+/// Supports dual-player input with separate control schemes
+/// </summary>
+[StructLayout(LayoutKind.Sequential)]
+public struct InputState
+{
+    [MarshalAs(UnmanagedType.I1)] public bool P1Left;
+    [MarshalAs(UnmanagedType.I1)] public bool P1Right;
+    [MarshalAs(UnmanagedType.I1)] public bool P1Accel;
+    [MarshalAs(UnmanagedType.I1)] public bool P1Brake;
+    [MarshalAs(UnmanagedType.I1)] public bool P1Boost;
+    [MarshalAs(UnmanagedType.I1)] public bool P2Left;
+    [MarshalAs(UnmanagedType.I1)] public bool P2Right;
+    [MarshalAs(UnmanagedType.I1)] public bool P2Accel;
+    [MarshalAs(UnmanagedType.I1)] public bool P2Brake;
+    [MarshalAs(UnmanagedType.I1)] public bool P2Boost;
+    [MarshalAs(UnmanagedType.I1)] public bool Quit;
+    [MarshalAs(UnmanagedType.I1)] public bool Pause;
+    [MarshalAs(UnmanagedType.I1)] public bool Menu;
+    [MarshalAs(UnmanagedType.I1)] public bool MenuUp;
+    [MarshalAs(UnmanagedType.I1)] public bool MenuDown;
+    [MarshalAs(UnmanagedType.I1)] public bool MenuLeft;
+    [MarshalAs(UnmanagedType.I1)] public bool MenuRight;
+    [MarshalAs(UnmanagedType.I1)] public bool MenuConfirm;
+    [MarshalAs(UnmanagedType.I1)] public bool MenuBack;
+    [MarshalAs(UnmanagedType.I1)] public bool MenuPageUp;
+    [MarshalAs(UnmanagedType.I1)] public bool MenuPageDown;
+    [MarshalAs(UnmanagedType.I1)] public bool MenuErase;
+    public uint TextLen;
+    [MarshalAs(UnmanagedType.ByValArray, SizeConst = 16)] public uint[] Text;
+}
+
+/// <summary>
+/// Audio command structure for sound effects and music
+/// </summary>
+[StructLayout(LayoutKind.Sequential)]
+public struct AudioCommand
+{
+    [MarshalAs(UnmanagedType.I1)] public bool PlaySound;
+    public int SoundType;
+    public float Volume;
+}
+
+/// <summary>
+/// Options for creating a renderer handle
+/// `headless` renderers draw into a `width` x `height` off-screen buffer;
+/// otherwise the renderer takes over the real terminal and the size is ignored.
+/// `color_mode` is one of `color_modes`; `AUTO` detects the terminal's depth.
+/// </summary>
+[StructLayout(LayoutKind.Sequential)]
+public struct RendererConfig
+{
+    [MarshalAs(UnmanagedType.I1)] public bool Headless;
+    public ushort Width;
+    public ushort Height;
+    public int ColorMode;
+}
+
+/// <summary>
+/// Mouse press, release, drag or scroll, hit-tested against the last frame
+/// `column`/`row` are terminal cells. `item` is the menu option index for
+/// `hit_targets::MENU_ITEM`, and `position` the 0.0-1.0 point on the
+/// timeline for `hit_targets::REPLAY_TIMELINE`.
+/// </summary>
+[StructLayout(LayoutKind.Sequential)]
+public struct MouseEvent
+{
+    public int Kind;
+    public int Button;
+    public ushort Column;
+    public ushort Row;
+    public int Target;
+    public int Item;
+    public float Position;
+}
+
+/// <summary>
+/// One entry of the input event stream read with `ratatui_next_event`
+/// `kind` selects which fields are set: `key_*`, `modifiers` and `repeat` for
+/// key events, `width`/`height` for resizes, `mouse` for mouse events and
+/// `text_len` for pastes (the text itself comes from `ratatui_event_text`).
+/// For `key_codes::CHAR` keys `key_char` is the Unicode scalar value; for
+/// `key_codes::FUNCTION` keys it is the F-key number.
+/// </summary>
+[StructLayout(LayoutKind.Sequential)]
+public struct Event
+{
+    public int Kind;
+    public int KeyCode;
+    public uint KeyChar;
+    public int Modifiers;
+    [MarshalAs(UnmanagedType.I1)] public bool Repeat;
+    public ushort Width;
+    public ushort Height;
+    public MouseEvent Mouse;
+    public uint TextLen;
+}
+
+/// <summary>
+/// Frame pacing and quality of a renderer, read with `ratatui_frame_stats`
+/// `render_ms` is what the last game frame took to draw and flush, and
+/// `average_ms` its running average; `target_fps` is 0 while frames are not
+/// paced. `quality` is one of `quality_levels`, lowered and raised by the
+/// renderer itself while `adaptive` is set.
+/// </summary>
+[StructLayout(LayoutKind.Sequential)]
+public struct FrameStats
+{
+    public ulong Frame;
+    public float RenderMs;
+    public float AverageMs;
+    public uint TargetFps;
+    public int Quality;
+    [MarshalAs(UnmanagedType.I1)] public bool Adaptive;
+}
+
+/// <summary>
+/// Style of a single rendered cell, exported from headless snapshots
+/// Colors are packed as `0x00000000` = reset, `0x01RRGGBB` = RGB,
+/// `0x020000NN` = indexed (named colors use their ANSI index 0-15)
+/// </summary>
+[StructLayout(LayoutKind.Sequential)]
+public struct CellStyle
+{
+    public uint Fg;
+    public uint Bg;
+    public ushort Modifiers;
+}
+
+public static partial class RatatuiFFI
+{
+    private const string LibName = "rust_renderer";
+
+    /// <summary>
+    /// Version of the FFI surface
+    /// Bump whenever an exported function signature or `#[repr(C)]` layout changes
+    /// </summary>
+    public const uint AbiVersion = 6;
+
+    /// <summary>
+    /// Characters `InputState::text` holds per poll; later ones are dropped
+    /// </summary>
+    public const int InputTextCapacity = 16;
+
+    public static class GameModes
+    {
+        public const int SinglePlayer = 0;
+        public const int SplitScreen = 1;
+        public const int Career = 2;
+        public const int Replay = 3;
+    }
+
+    public static class TrackTypes
+    {
+        public const int Highway = 0;
+        public const int City = 1;
+        public const int Mountain = 2;
+        public const int Desert = 3;
+        public const int Tunnel = 4;
+    }
+
+    public static class Weather
+    {
+        public const int Clear = 0;
+        public const int Rain = 1;
+        public const int Fog = 2;
+        public const int Night = 3;
+    }
+
+    public static class ObstacleTypes
+    {
+        public const int Cone = 0;
+        public const int Oil = 1;
+        public const int Boost = 2;
+        public const int Star = 3;
+        public const int Magnet = 4;
+        public const int Clock = 5;
+    }
+
+    public static class AssetStyles
+    {
+        public const int Classic = 0;
+        public const int Detailed = 1;
+    }
+
+    public static class BuildingTypes
+    {
+        public const int Glass = 1;
+        public const int Concrete = 2;
+        public const int Brick = 3;
+    }
+
+    public static class AudioTypes
+    {
+        public const int Engine = 0;
+        public const int Boost = 1;
+        public const int Crash = 2;
+        public const int Powerup = 3;
+        public const int Music = 4;
+    }
+
+    public static class MouseKinds
+    {
+        public const int Down = 0;
+        public const int Up = 1;
+        public const int Drag = 2;
+        public const int ScrollUp = 3;
+        public const int ScrollDown = 4;
+    }
+
+    public static class MouseButtons
+    {
+        public const int None = 0;
+        public const int Left = 1;
+        public const int Right = 2;
+        public const int Middle = 3;
+    }
+
+    public static class HitTargets
+    {
+        public const int None = 0;
+        public const int MenuItem = 1;
+        public const int ReplayTimeline = 2;
+    }
+
+    public static class EventKinds
+    {
+        public const int KeyDown = 0;
+        public const int KeyUp = 1;
+        public const int Resize = 2;
+        public const int FocusGained = 3;
+        public const int FocusLost = 4;
+        public const int Mouse = 5;
+        public const int Paste = 6;
+    }
+
+    public static class KeyCodes
+    {
+        public const int Char = 0;
+        public const int Enter = 1;
+        public const int Esc = 2;
+        public const int Backspace = 3;
+        public const int Tab = 4;
+        public const int Left = 5;
+        public const int Right = 6;
+        public const int Up = 7;
+        public const int Down = 8;
+        public const int Home = 9;
+        public const int End = 10;
+        public const int PageUp = 11;
+        public const int PageDown = 12;
+        public const int Insert = 13;
+        public const int Delete = 14;
+        public const int Function = 15;
+        public const int Other = 16;
+    }
+
+    public static class KeyModifiers
+    {
+        public const int Shift = 1;
+        public const int Control = 2;
+        public const int Alt = 4;
+    }
+
+    public static class QualityLevels
+    {
+        public const int High = 0;
+        public const int Medium = 1;
+        public const int Low = 2;
+        public const int Minimal = 3;
+    }
+
+    public static class ColorModes
+    {
+        public const int Auto = 0;
+        public const int Truecolor = 1;
+        public const int Ansi256 = 2;
+        public const int Ansi16 = 3;
+    }
+
+    /// <summary>
+    /// Version of the FFI surface; hosts should refuse to run on a mismatch
+    /// </summary>
+    [DllImport(LibName, CallingConvention = CallingConvention.Cdecl)]
+    public static extern uint ratatui_abi_version();
+
+    /// <summary>
+    /// Check the host's `GameState` and `InputState` layouts against Rust
+    /// Each `*_offsets` array lists the byte offset of every field in declaration
+    /// order. On mismatch `false` is returned with the reason available from
+    /// `ratatui_last_error`, and every later `ratatui_init` / `ratatui_create`
+    /// fails until a validation passes.
+    /// </summary>
+    [DllImport(LibName, CallingConvention = CallingConvention.Cdecl)]
+    [return: MarshalAs(UnmanagedType.I1)]
+    public static extern bool ratatui_validate_layout(UIntPtr gameStateSize, [MarshalAs(UnmanagedType.LPArray)] UIntPtr[] gameStateOffsets, UIntPtr gameStateFieldCount, UIntPtr inputStateSize, [MarshalAs(UnmanagedType.LPArray)] UIntPtr[] inputStateOffsets, UIntPtr inputStateFieldCount);
+
+    /// <summary>
+    /// Code of the last failure on the calling thread, `Ok` if there was none
+    /// Failed calls keep their error until a later failure on the same thread
+    /// replaces it, so read it right after a call reports failure.
+    /// </summary>
+    [DllImport(LibName, CallingConvention = CallingConvention.Cdecl)]
+    public static extern ErrorCode ratatui_last_error_code();
+
+    /// <summary>
+    /// Copy the message of the last failure on the calling thread into `buf`
+    /// Uses the buffer protocol of `ratatui_snapshot_text`: returns the full
+    /// message length in bytes, or 0 (an empty string) when there was no failure.
+    /// </summary>
+    [DllImport(LibName, CallingConvention = CallingConvention.Cdecl)]
+    public static extern int ratatui_last_error([Out] byte[]? buf, UIntPtr len);
+
+    /// <summary>
+    /// Replace the active key bindings with a TOML or JSON table
+    /// The table maps `InputState` field names to lists of key specs; fields left
+    /// out keep their default keys. On a parse error or a key bound twice the
+    /// active bindings are left unchanged and `false` is returned.
+    /// </summary>
+    [DllImport(LibName, CallingConvention = CallingConvention.Cdecl)]
+    [return: MarshalAs(UnmanagedType.I1)]
+    public static extern bool ratatui_set_bindings([MarshalAs(UnmanagedType.LPUTF8Str)] string text);
+
+    /// <summary>
+    /// Load the active key bindings from a `.toml` or `.json` file
+    /// </summary>
+    [DllImport(LibName, CallingConvention = CallingConvention.Cdecl)]
+    [return: MarshalAs(UnmanagedType.I1)]
+    public static extern bool ratatui_load_bindings([MarshalAs(UnmanagedType.LPUTF8Str)] string path);
+
+    /// <summary>
+    /// Copy the active key bindings into `buf` as a JSON object
+    /// Lists every `InputState` field in declaration order and uses the buffer
+    /// protocol of `ratatui_snapshot_text`.
+    /// </summary>
+    [DllImport(LibName, CallingConvention = CallingConvention.Cdecl)]
+    public static extern int ratatui_get_bindings([Out] byte[]? buf, UIntPtr len);
+
+    /// <summary>
+    /// Switch every renderer to one of the built-in `asset_styles`
+    /// Takes effect from the next frame. An unknown style leaves the active
+    /// assets unchanged and returns `false`.
+    /// </summary>
+    [DllImport(LibName, CallingConvention = CallingConvention.Cdecl)]
+    [return: MarshalAs(UnmanagedType.I1)]
+    public static extern bool ratatui_set_asset_style(int style);
+
+    /// <summary>
+    /// Replace the active assets with a TOML or JSON asset pack
+    /// The pack overlays cars, powerups, buildings, road, scenery, HUD titles and
+    /// animations on its `base` style. On a parse error or a sprite that does not
+    /// fit, the active assets are left unchanged and `false` is returned.
+    /// </summary>
+    [DllImport(LibName, CallingConvention = CallingConvention.Cdecl)]
+    [return: MarshalAs(UnmanagedType.I1)]
+    public static extern bool ratatui_set_asset_pack([MarshalAs(UnmanagedType.LPUTF8Str)] string text);
+
+    /// <summary>
+    /// Load the active assets from a `.toml` or `.json` asset pack file
+    /// </summary>
+    [DllImport(LibName, CallingConvention = CallingConvention.Cdecl)]
+    [return: MarshalAs(UnmanagedType.I1)]
+    public static extern bool ratatui_load_asset_pack([MarshalAs(UnmanagedType.LPUTF8Str)] string path);
+
+    /// <summary>
+    /// Load an asset pack and reload it whenever the file changes
+    /// Meant for development: the file's modification time is checked before
+    /// each frame and a changed pack is swapped in between frames. A pack that
+    /// fails to load keeps the last good assets and is reported in an on-screen
+    /// overlay until fixed. Returns whether the first load succeeded; watching
+    /// starts either way.
+    /// </summary>
+    [DllImport(LibName, CallingConvention = CallingConvention.Cdecl)]
+    [return: MarshalAs(UnmanagedType.I1)]
+    public static extern bool ratatui_watch_asset_pack([MarshalAs(UnmanagedType.LPUTF8Str)] string path);
+
+    /// <summary>
+    /// Stop watching the asset pack; the assets last loaded stay active
+    /// </summary>
+    [DllImport(LibName, CallingConvention = CallingConvention.Cdecl)]
+    public static extern void ratatui_unwatch_asset_pack();
+
+    /// <summary>
+    /// Copy the reason the watched asset pack failed to load into `buf`
+    /// Returns 0 while the pack is loaded fine, otherwise uses the buffer
+    /// protocol of `ratatui_snapshot_text`.
+    /// </summary>
+    [DllImport(LibName, CallingConvention = CallingConvention.Cdecl)]
+    public static extern int ratatui_asset_reload_error([Out] byte[]? buf, UIntPtr len);
+
+    /// <summary>
+    /// Style of the active assets, one of `asset_styles`
+    /// </summary>
+    [DllImport(LibName, CallingConvention = CallingConvention.Cdecl)]
+    public static extern int ratatui_asset_style();
+
+    /// <summary>
+    /// Show or hide the debug performance overlay on every renderer
+    /// Game frames then carry FPS, frame and draw time, the cells the last
+    /// frame changed on the terminal, object counts, terminal size and quality
+    /// in their top right corner. F3 toggles it too.
+    /// </summary>
+    [DllImport(LibName, CallingConvention = CallingConvention.Cdecl)]
+    public static extern void ratatui_set_debug_overlay([MarshalAs(UnmanagedType.I1)] bool enabled);
+
+    /// <summary>
+    /// Whether the debug overlay is shown
+    /// </summary>
+    [DllImport(LibName, CallingConvention = CallingConvention.Cdecl)]
+    [return: MarshalAs(UnmanagedType.I1)]
+    public static extern bool ratatui_debug_overlay();
+
+    /// <summary>
+    /// Create an independent renderer
+    /// A null `config` creates an on-screen renderer. Only one on-screen renderer
+    /// may exist at a time, but any number of headless ones can run alongside it.
+    /// Returns null on failure (see `ratatui_last_error`). Release with
+    /// `ratatui_destroy`.
+    /// </summary>
+    [DllImport(LibName, CallingConvention = CallingConvention.Cdecl)]
+    public static extern IntPtr ratatui_create(ref RendererConfig config);
+
+    /// <summary>
+    /// Destroy a renderer, restoring the terminal if it owned it
+    /// </summary>
+    [DllImport(LibName, CallingConvention = CallingConvention.Cdecl)]
+    public static extern void ratatui_destroy(IntPtr renderer);
+
+    /// <summary>
+    /// Render the game on a renderer handle
+    /// </summary>
+    [DllImport(LibName, CallingConvention = CallingConvention.Cdecl)]
+    [return: MarshalAs(UnmanagedType.I1)]
+    public static extern bool ratatui_renderer_render(IntPtr renderer, ref GameState state);
+
+    /// <summary>
+    /// Render the menu on a renderer handle
+    /// </summary>
+    [DllImport(LibName, CallingConvention = CallingConvention.Cdecl)]
+    [return: MarshalAs(UnmanagedType.I1)]
+    public static extern bool ratatui_renderer_render_menu(IntPtr renderer, [MarshalAs(UnmanagedType.LPUTF8Str)] string title, [MarshalAs(UnmanagedType.LPArray, ArraySubType = UnmanagedType.LPUTF8Str)] string[] options, int optionCount, int selected);
+
+    /// <summary>
+    /// Pace a renderer handle's frames; see `ratatui_set_target_fps`
+    /// </summary>
+    [DllImport(LibName, CallingConvention = CallingConvention.Cdecl)]
+    [return: MarshalAs(UnmanagedType.I1)]
+    public static extern bool ratatui_renderer_set_target_fps(IntPtr renderer, uint fps);
+
+    /// <summary>
+    /// Wait for a renderer handle's next frame; see `ratatui_wait_frame`
+    /// </summary>
+    [DllImport(LibName, CallingConvention = CallingConvention.Cdecl)]
+    [return: MarshalAs(UnmanagedType.I1)]
+    public static extern bool ratatui_renderer_wait_frame(IntPtr renderer);
+
+    /// <summary>
+    /// Set a renderer handle's effect quality; see `ratatui_set_quality`
+    /// </summary>
+    [DllImport(LibName, CallingConvention = CallingConvention.Cdecl)]
+    [return: MarshalAs(UnmanagedType.I1)]
+    public static extern bool ratatui_renderer_set_quality(IntPtr renderer, int level, [MarshalAs(UnmanagedType.I1)] bool adaptive);
+
+    /// <summary>
+    /// Read a renderer handle's pacing figures; see `ratatui_frame_stats`
+    /// </summary>
+    [DllImport(LibName, CallingConvention = CallingConvention.Cdecl)]
+    [return: MarshalAs(UnmanagedType.I1)]
+    public static extern bool ratatui_renderer_frame_stats(IntPtr renderer, ref FrameStats stats);
+
+    /// <summary>
+    /// Set a renderer handle's colour depth; see `ratatui_set_color_mode`
+    /// </summary>
+    [DllImport(LibName, CallingConvention = CallingConvention.Cdecl)]
+    [return: MarshalAs(UnmanagedType.I1)]
+    public static extern bool ratatui_renderer_set_color_mode(IntPtr renderer, int mode);
+
+    /// <summary>
+    /// Colour depth a renderer handle draws at; see `ratatui_color_mode`
+    /// </summary>
+    [DllImport(LibName, CallingConvention = CallingConvention.Cdecl)]
+    public static extern int ratatui_renderer_color_mode(IntPtr renderer);
+
+    /// <summary>
+    /// Poll input for a renderer handle
+    /// Off-screen renderers always report an idle input state.
+    /// </summary>
+    [DllImport(LibName, CallingConvention = CallingConvention.Cdecl)]
+    [return: MarshalAs(UnmanagedType.I1)]
+    public static extern bool ratatui_renderer_poll_input(IntPtr renderer, ref InputState input);
+
+    /// <summary>
+    /// Turn mouse reporting on or off for a renderer handle
+    /// Off by default; has no effect on off-screen renderers.
+    /// </summary>
+    [DllImport(LibName, CallingConvention = CallingConvention.Cdecl)]
+    [return: MarshalAs(UnmanagedType.I1)]
+    public static extern bool ratatui_renderer_set_mouse_capture(IntPtr renderer, [MarshalAs(UnmanagedType.I1)] bool enabled);
+
+    /// <summary>
+    /// Take the next mouse event of a renderer handle
+    /// See `ratatui_poll_mouse`.
+    /// </summary>
+    [DllImport(LibName, CallingConvention = CallingConvention.Cdecl)]
+    [return: MarshalAs(UnmanagedType.I1)]
+    public static extern bool ratatui_renderer_poll_mouse(IntPtr renderer, ref MouseEvent @event);
+
+    /// <summary>
+    /// Take the next event of a renderer handle
+    /// See `ratatui_next_event`.
+    /// </summary>
+    [DllImport(LibName, CallingConvention = CallingConvention.Cdecl)]
+    [return: MarshalAs(UnmanagedType.I1)]
+    public static extern bool ratatui_renderer_next_event(IntPtr renderer, ref Event @event);
+
+    /// <summary>
+    /// Copy the last frame of a headless renderer as text
+    /// See `ratatui_snapshot_text` for the buffer protocol.
+    /// </summary>
+    [DllImport(LibName, CallingConvention = CallingConvention.Cdecl)]
+    public static extern int ratatui_renderer_snapshot_text(IntPtr renderer, [Out] byte[]? buf, UIntPtr len);
+
+    /// <summary>
+    /// Copy the cell styles of the last frame of a headless renderer
+    /// See `ratatui_snapshot_styles` for the buffer protocol.
+    /// </summary>
+    [DllImport(LibName, CallingConvention = CallingConvention.Cdecl)]
+    public static extern int ratatui_renderer_snapshot_styles(IntPtr renderer, [Out] CellStyle[]? cells, UIntPtr capacity);
+
+    /// <summary>
+    /// Initialize the rendering engine
+    /// Sets up terminal, raw mode, and alternate screen
+    /// </summary>
+    [DllImport(LibName, CallingConvention = CallingConvention.Cdecl)]
+    [return: MarshalAs(UnmanagedType.I1)]
+    public static extern bool ratatui_init();
+
+    /// <summary>
+    /// Initialize an off-screen renderer of the given size
+    /// Frames are kept in memory and can be read back with the snapshot calls
+    /// </summary>
+    [DllImport(LibName, CallingConvention = CallingConvention.Cdecl)]
+    [return: MarshalAs(UnmanagedType.I1)]
+    public static extern bool ratatui_init_headless(ushort width, ushort height);
+
+    /// <summary>
+    /// Clean up and restore terminal state
+    /// </summary>
+    [DllImport(LibName, CallingConvention = CallingConvention.Cdecl)]
+    public static extern void ratatui_cleanup();
+
+    /// <summary>
+    /// Poll for keyboard input and update input state
+    /// Waits up to 16 ms for the first event unless frames are paced with
+    /// `ratatui_set_target_fps`, in which case it never waits.
+    /// </summary>
+    [DllImport(LibName, CallingConvention = CallingConvention.Cdecl)]
+    [return: MarshalAs(UnmanagedType.I1)]
+    public static extern bool ratatui_poll_input(ref InputState input);
+
+    /// <summary>
+    /// Turn mouse reporting on or off
+    /// Off by default, since capturing the mouse disables text selection in most
+    /// terminals. Mouse events are read by `ratatui_poll_input`.
+    /// </summary>
+    [DllImport(LibName, CallingConvention = CallingConvention.Cdecl)]
+    [return: MarshalAs(UnmanagedType.I1)]
+    public static extern bool ratatui_set_mouse_capture([MarshalAs(UnmanagedType.I1)] bool enabled);
+
+    /// <summary>
+    /// Take the next mouse event read by `ratatui_poll_input`
+    /// Events are hit-tested against the last frame: clicks on menu options
+    /// report the option index, clicks and drags on the replay timeline the seek
+    /// position. Returns `false` once the queue is empty, so call it in a loop
+    /// after each poll.
+    /// </summary>
+    [DllImport(LibName, CallingConvention = CallingConvention.Cdecl)]
+    [return: MarshalAs(UnmanagedType.I1)]
+    public static extern bool ratatui_poll_mouse(ref MouseEvent @event);
+
+    /// <summary>
+    /// Take the next entry of the terminal event stream
+    /// Reports key presses and releases (with an auto-repeat flag), resizes with
+    /// the new size, focus changes, mouse events hit-tested like
+    /// `ratatui_poll_mouse`, and pastes. Never waits; returns `false` once no
+    /// event is pending, so call it in a loop each frame. Events read here still
+    /// count towards the next `ratatui_poll_input`.
+    /// </summary>
+    [DllImport(LibName, CallingConvention = CallingConvention.Cdecl)]
+    [return: MarshalAs(UnmanagedType.I1)]
+    public static extern bool ratatui_next_event(ref Event @event);
+
+    /// <summary>
+    /// Copy the text of the last paste event taken with `ratatui_next_event`
+    /// See `ratatui_snapshot_text` for the buffer protocol.
+    /// </summary>
+    [DllImport(LibName, CallingConvention = CallingConvention.Cdecl)]
+    public static extern int ratatui_event_text([Out] byte[]? buf, UIntPtr len);
+
+    /// <summary>
+    /// Render the game based on current game state
+    /// </summary>
+    [DllImport(LibName, CallingConvention = CallingConvention.Cdecl)]
+    [return: MarshalAs(UnmanagedType.I1)]
+    public static extern bool ratatui_render(ref GameState state);
+
+    /// <summary>
+    /// Render menu with title and options
+    /// </summary>
+    [DllImport(LibName, CallingConvention = CallingConvention.Cdecl)]
+    [return: MarshalAs(UnmanagedType.I1)]
+    public static extern bool ratatui_render_menu([MarshalAs(UnmanagedType.LPUTF8Str)] string title, [MarshalAs(UnmanagedType.LPArray, ArraySubType = UnmanagedType.LPUTF8Str)] string[] options, int optionCount, int selected);
+
+    /// <summary>
+    /// Pace frames at `fps` frames per second, or stop pacing with 0
+    /// Paced hosts call `ratatui_wait_frame` once per frame instead of sleeping,
+    /// and `ratatui_poll_input` stops waiting for input, so input latency no
+    /// longer depends on the frame rate. Unpaced by default.
+    /// </summary>
+    [DllImport(LibName, CallingConvention = CallingConvention.Cdecl)]
+    [return: MarshalAs(UnmanagedType.I1)]
+    public static extern bool ratatui_set_target_fps(uint fps);
+
+    /// <summary>
+    /// Wait until the next frame is due at the target rate
+    /// Input keeps being read while waiting and counts towards the next
+    /// `ratatui_poll_input`. A host running late is not made to catch up: the
+    /// next frame is due one period after this call. Returns at once while
+    /// frames are not paced.
+    /// </summary>
+    [DllImport(LibName, CallingConvention = CallingConvention.Cdecl)]
+    [return: MarshalAs(UnmanagedType.I1)]
+    public static extern bool ratatui_wait_frame();
+
+    /// <summary>
+    /// Draw at one of `quality_levels`
+    /// With `adaptive`, `level` is the best quality allowed: the renderer drops
+    /// a level (rain, then particles, then building windows) while game frames
+    /// take longer than the frame budget to draw and flush, and raises it again
+    /// once they take well under it. The budget is one frame at the target rate,
+    /// or at 60 FPS while unpaced. An unknown level returns `false`.
+    /// </summary>
+    [DllImport(LibName, CallingConvention = CallingConvention.Cdecl)]
+    [return: MarshalAs(UnmanagedType.I1)]
+    public static extern bool ratatui_set_quality(int level, [MarshalAs(UnmanagedType.I1)] bool adaptive);
+
+    /// <summary>
+    /// Copy the frame counter, measured frame cost, target rate and current
+    /// quality into `stats`
+    /// </summary>
+    [DllImport(LibName, CallingConvention = CallingConvention.Cdecl)]
+    [return: MarshalAs(UnmanagedType.I1)]
+    public static extern bool ratatui_frame_stats(ref FrameStats stats);
+
+    /// <summary>
+    /// Draw at one of `color_modes` from the next frame on
+    /// Below `TRUECOLOR`, every RGB colour is replaced by the nearest entry of
+    /// the 256-colour palette or of the 16 basic colours. `AUTO`, the default,
+    /// detects the depth from `COLORTERM` and `TERM`; headless renderers always
+    /// detect `TRUECOLOR`. An unknown mode returns `false`.
+    /// </summary>
+    [DllImport(LibName, CallingConvention = CallingConvention.Cdecl)]
+    [return: MarshalAs(UnmanagedType.I1)]
+    public static extern bool ratatui_set_color_mode(int mode);
+
+    /// <summary>
+    /// Colour depth frames are drawn at, one of `color_modes` other than `AUTO`
+    /// Returns -1 when no renderer is active.
+    /// </summary>
+    [DllImport(LibName, CallingConvention = CallingConvention.Cdecl)]
+    public static extern int ratatui_color_mode();
+
+    /// <summary>
+    /// Copy the last headless frame into `buf` as NUL-terminated UTF-8 text
+    /// Rows are separated by `\n`. Returns the full text length in bytes (without
+    /// the NUL), so a call with `len == 0` can be used to size the buffer.
+    /// Returns -1 when no headless renderer is active.
+    /// </summary>
+    [DllImport(LibName, CallingConvention = CallingConvention.Cdecl)]
+    public static extern int ratatui_snapshot_text([Out] byte[]? buf, UIntPtr len);
+
+    /// <summary>
+    /// Copy the styles of the last headless frame into `cells` in row-major order
+    /// Returns the total number of cells (width * height); at most `capacity`
+    /// entries are written. Returns -1 when no headless renderer is active.
+    /// </summary>
+    [DllImport(LibName, CallingConvention = CallingConvention.Cdecl)]
+    public static extern int ratatui_snapshot_styles([Out] CellStyle[]? cells, UIntPtr capacity);
+}
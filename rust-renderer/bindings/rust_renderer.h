@@ -0,0 +1,755 @@
+/* Generated by rust-renderer/build.rs from the Rust sources. Do not edit. */
+
+#ifndef RUST_RENDERER_H
+#define RUST_RENDERER_H
+
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+
+/**
+ * Version of the FFI surface
+ * Bump whenever an exported function signature or `#[repr(C)]` layout changes
+ */
+#define RATATUI_ABI_VERSION 6
+/** Characters `InputState::text` holds per poll; later ones are dropped */
+#define RATATUI_INPUT_TEXT_CAPACITY 16
+
+#define GAME_MODES_SINGLE_PLAYER 0
+#define GAME_MODES_SPLIT_SCREEN 1
+#define GAME_MODES_CAREER 2
+#define GAME_MODES_REPLAY 3
+
+#define TRACK_TYPES_HIGHWAY 0
+#define TRACK_TYPES_CITY 1
+#define TRACK_TYPES_MOUNTAIN 2
+#define TRACK_TYPES_DESERT 3
+#define TRACK_TYPES_TUNNEL 4
+
+#define WEATHER_CLEAR 0
+#define WEATHER_RAIN 1
+#define WEATHER_FOG 2
+#define WEATHER_NIGHT 3
+
+#define OBSTACLE_TYPES_CONE 0
+#define OBSTACLE_TYPES_OIL 1
+#define OBSTACLE_TYPES_BOOST 2
+#define OBSTACLE_TYPES_STAR 3
+#define OBSTACLE_TYPES_MAGNET 4
+#define OBSTACLE_TYPES_CLOCK 5
+
+#define ASSET_STYLES_CLASSIC 0
+#define ASSET_STYLES_DETAILED 1
+
+#define BUILDING_TYPES_GLASS 1
+#define BUILDING_TYPES_CONCRETE 2
+#define BUILDING_TYPES_BRICK 3
+
+#define AUDIO_TYPES_ENGINE 0
+#define AUDIO_TYPES_BOOST 1
+#define AUDIO_TYPES_CRASH 2
+#define AUDIO_TYPES_POWERUP 3
+#define AUDIO_TYPES_MUSIC 4
+
+#define MOUSE_KINDS_DOWN 0
+#define MOUSE_KINDS_UP 1
+#define MOUSE_KINDS_DRAG 2
+#define MOUSE_KINDS_SCROLL_UP 3
+#define MOUSE_KINDS_SCROLL_DOWN 4
+
+#define MOUSE_BUTTONS_NONE 0
+#define MOUSE_BUTTONS_LEFT 1
+#define MOUSE_BUTTONS_RIGHT 2
+#define MOUSE_BUTTONS_MIDDLE 3
+
+#define HIT_TARGETS_NONE 0
+#define HIT_TARGETS_MENU_ITEM 1
+#define HIT_TARGETS_REPLAY_TIMELINE 2
+
+#define EVENT_KINDS_KEY_DOWN 0
+#define EVENT_KINDS_KEY_UP 1
+#define EVENT_KINDS_RESIZE 2
+#define EVENT_KINDS_FOCUS_GAINED 3
+#define EVENT_KINDS_FOCUS_LOST 4
+#define EVENT_KINDS_MOUSE 5
+#define EVENT_KINDS_PASTE 6
+
+#define KEY_CODES_CHAR 0
+#define KEY_CODES_ENTER 1
+#define KEY_CODES_ESC 2
+#define KEY_CODES_BACKSPACE 3
+#define KEY_CODES_TAB 4
+#define KEY_CODES_LEFT 5
+#define KEY_CODES_RIGHT 6
+#define KEY_CODES_UP 7
+#define KEY_CODES_DOWN 8
+#define KEY_CODES_HOME 9
+#define KEY_CODES_END 10
+#define KEY_CODES_PAGE_UP 11
+#define KEY_CODES_PAGE_DOWN 12
+#define KEY_CODES_INSERT 13
+#define KEY_CODES_DELETE 14
+#define KEY_CODES_FUNCTION 15
+#define KEY_CODES_OTHER 16
+
+#define KEY_MODIFIERS_SHIFT 1
+#define KEY_MODIFIERS_CONTROL 2
+#define KEY_MODIFIERS_ALT 4
+
+#define QUALITY_LEVELS_HIGH 0
+#define QUALITY_LEVELS_MEDIUM 1
+#define QUALITY_LEVELS_LOW 2
+#define QUALITY_LEVELS_MINIMAL 3
+
+#define COLOR_MODES_AUTO 0
+#define COLOR_MODES_TRUECOLOR 1
+#define COLOR_MODES_ANSI_256 2
+#define COLOR_MODES_ANSI_16 3
+
+/** Category of the last failure */
+enum ErrorCode {
+    /** No error has been recorded on this thread */
+    ERROR_CODE_OK = 0,
+    /** A required pointer argument was null */
+    ERROR_CODE_NULL_POINTER = 1,
+    /** A string argument was not valid UTF-8 */
+    ERROR_CODE_INVALID_UTF8 = 2,
+    /** An argument was out of range, e.g. a zero-sized headless renderer */
+    ERROR_CODE_INVALID_ARGUMENT = 3,
+    /** The global renderer has not been initialized */
+    ERROR_CODE_NOT_INITIALIZED = 4,
+    /** Another renderer already owns the terminal */
+    ERROR_CODE_TERMINAL_BUSY = 5,
+    /** The terminal refused raw mode */
+    ERROR_CODE_RAW_MODE_DENIED = 6,
+    /** Reading input or drawing to the terminal failed */
+    ERROR_CODE_IO = 7,
+    /** The host struct layout does not match Rust */
+    ERROR_CODE_LAYOUT_MISMATCH = 8,
+    /** The operation needs a headless renderer */
+    ERROR_CODE_NOT_HEADLESS = 9,
+    /** The renderer panicked and must be destroyed */
+    ERROR_CODE_PANIC = 10,
+    /** A key binding table could not be parsed */
+    ERROR_CODE_INVALID_BINDINGS = 11,
+    /** A key is bound to more than one input */
+    ERROR_CODE_BINDING_CONFLICT = 12,
+    /** An asset pack could not be parsed or a sprite does not fit */
+    ERROR_CODE_INVALID_ASSETS = 13,
+};
+typedef int32_t ErrorCode;
+
+typedef struct Renderer Renderer;
+
+/**
+ * Enhanced game state with comprehensive feature support
+ * Includes player state, multiplayer support, powerups, AI, obstacles, and environment
+ */
+typedef struct GameState {
+    int32_t player_position;
+    float player_speed;
+    float player_distance;
+    int32_t player_health;
+    int32_t player_score;
+    int32_t player_car_type;
+    bool player2_active;
+    int32_t player2_position;
+    float player2_speed;
+    float player2_distance;
+    int32_t player2_health;
+    int32_t player2_score;
+    int32_t player2_car_type;
+    float lap_time;
+    int32_t game_mode;
+    int32_t track_type;
+    int32_t lane_count;
+    int32_t level;
+    float career_progress;
+    bool boost_active;
+    float boost_remaining;
+    bool shield_active;
+    float shield_remaining;
+    bool invincibility_active;
+    float invincibility_remaining;
+    bool magnet_active;
+    float magnet_remaining;
+    bool slowmo_active;
+    float slowmo_remaining;
+    int32_t car_count;
+    const int32_t *ai_positions;
+    const float *ai_distances;
+    const int32_t *ai_types;
+    const bool *ai_is_boss;
+    int32_t obstacle_count;
+    const int32_t *obstacle_positions;
+    const float *obstacle_distances;
+    const int32_t *obstacle_types;
+    int32_t building_count;
+    const int32_t *building_positions;
+    const float *building_distances;
+    const int32_t *building_heights;
+    const int32_t *building_types;
+    int32_t weather;
+    float curve_offset;
+    float elevation;
+    float tunnel_darkness;
+    int32_t combo;
+    bool replay_mode;
+    float replay_progress;
+    int32_t ghost_position;
+    float ghost_distance;
+} GameState;
+
+/**
+ * Input state for both players and system controls
+ * Supports dual-player input with separate control schemes
+ */
+typedef struct InputState {
+    bool p1_left;
+    bool p1_right;
+    bool p1_accel;
+    bool p1_brake;
+    bool p1_boost;
+    bool p2_left;
+    bool p2_right;
+    bool p2_accel;
+    bool p2_brake;
+    bool p2_boost;
+    bool quit;
+    bool pause;
+    bool menu;
+    bool menu_up;
+    bool menu_down;
+    bool menu_left;
+    bool menu_right;
+    bool menu_confirm;
+    bool menu_back;
+    bool menu_page_up;
+    bool menu_page_down;
+    bool menu_erase;
+    uint32_t text_len;
+    uint32_t text[16];
+} InputState;
+
+/** Audio command structure for sound effects and music */
+typedef struct AudioCommand {
+    bool play_sound;
+    int32_t sound_type;
+    float volume;
+} AudioCommand;
+
+/**
+ * Options for creating a renderer handle
+ * `headless` renderers draw into a `width` x `height` off-screen buffer;
+ * otherwise the renderer takes over the real terminal and the size is ignored.
+ * `color_mode` is one of `color_modes`; `AUTO` detects the terminal's depth.
+ */
+typedef struct RendererConfig {
+    bool headless;
+    uint16_t width;
+    uint16_t height;
+    int32_t color_mode;
+} RendererConfig;
+
+/**
+ * Mouse press, release, drag or scroll, hit-tested against the last frame
+ * `column`/`row` are terminal cells. `item` is the menu option index for
+ * `hit_targets::MENU_ITEM`, and `position` the 0.0-1.0 point on the
+ * timeline for `hit_targets::REPLAY_TIMELINE`.
+ */
+typedef struct MouseEvent {
+    int32_t kind;
+    int32_t button;
+    uint16_t column;
+    uint16_t row;
+    int32_t target;
+    int32_t item;
+    float position;
+} MouseEvent;
+
+/**
+ * One entry of the input event stream read with `ratatui_next_event`
+ * `kind` selects which fields are set: `key_*`, `modifiers` and `repeat` for
+ * key events, `width`/`height` for resizes, `mouse` for mouse events and
+ * `text_len` for pastes (the text itself comes from `ratatui_event_text`).
+ * For `key_codes::CHAR` keys `key_char` is the Unicode scalar value; for
+ * `key_codes::FUNCTION` keys it is the F-key number.
+ */
+typedef struct Event {
+    int32_t kind;
+    int32_t key_code;
+    uint32_t key_char;
+    int32_t modifiers;
+    bool repeat;
+    uint16_t width;
+    uint16_t height;
+    MouseEvent mouse;
+    uint32_t text_len;
+} Event;
+
+/**
+ * Frame pacing and quality of a renderer, read with `ratatui_frame_stats`
+ * `render_ms` is what the last game frame took to draw and flush, and
+ * `average_ms` its running average; `target_fps` is 0 while frames are not
+ * paced. `quality` is one of `quality_levels`, lowered and raised by the
+ * renderer itself while `adaptive` is set.
+ */
+typedef struct FrameStats {
+    uint64_t frame;
+    float render_ms;
+    float average_ms;
+    uint32_t target_fps;
+    int32_t quality;
+    bool adaptive;
+} FrameStats;
+
+/**
+ * Style of a single rendered cell, exported from headless snapshots
+ * Colors are packed as `0x00000000` = reset, `0x01RRGGBB` = RGB,
+ * `0x020000NN` = indexed (named colors use their ANSI index 0-15)
+ */
+typedef struct CellStyle {
+    uint32_t fg;
+    uint32_t bg;
+    uint16_t modifiers;
+} CellStyle;
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+/** Version of the FFI surface; hosts should refuse to run on a mismatch */
+uint32_t ratatui_abi_version(void);
+
+/**
+ * Check the host's `GameState` and `InputState` layouts against Rust
+ * Each `*_offsets` array lists the byte offset of every field in declaration
+ * order. On mismatch `false` is returned with the reason available from
+ * `ratatui_last_error`, and every later `ratatui_init` / `ratatui_create`
+ * fails until a validation passes.
+ *
+ * # Safety
+ * Each offsets pointer must be null or point to `*_field_count` values.
+ */
+bool ratatui_validate_layout(size_t game_state_size, const size_t *game_state_offsets, size_t game_state_field_count, size_t input_state_size, const size_t *input_state_offsets, size_t input_state_field_count);
+
+/**
+ * Code of the last failure on the calling thread, `Ok` if there was none
+ * Failed calls keep their error until a later failure on the same thread
+ * replaces it, so read it right after a call reports failure.
+ */
+ErrorCode ratatui_last_error_code(void);
+
+/**
+ * Copy the message of the last failure on the calling thread into `buf`
+ * Uses the buffer protocol of `ratatui_snapshot_text`: returns the full
+ * message length in bytes, or 0 (an empty string) when there was no failure.
+ *
+ * # Safety
+ * `buf` must be null or point to at least `len` writable bytes.
+ */
+int32_t ratatui_last_error(char *buf, size_t len);
+
+/**
+ * Replace the active key bindings with a TOML or JSON table
+ * The table maps `InputState` field names to lists of key specs; fields left
+ * out keep their default keys. On a parse error or a key bound twice the
+ * active bindings are left unchanged and `false` is returned.
+ *
+ * # Safety
+ * `text` must be null or a valid C string.
+ */
+bool ratatui_set_bindings(const char *text);
+
+/**
+ * Load the active key bindings from a `.toml` or `.json` file
+ *
+ * # Safety
+ * `path` must be null or a valid C string.
+ */
+bool ratatui_load_bindings(const char *path);
+
+/**
+ * Copy the active key bindings into `buf` as a JSON object
+ * Lists every `InputState` field in declaration order and uses the buffer
+ * protocol of `ratatui_snapshot_text`.
+ *
+ * # Safety
+ * `buf` must be null or point to at least `len` writable bytes.
+ */
+int32_t ratatui_get_bindings(char *buf, size_t len);
+
+/**
+ * Switch every renderer to one of the built-in `asset_styles`
+ * Takes effect from the next frame. An unknown style leaves the active
+ * assets unchanged and returns `false`.
+ */
+bool ratatui_set_asset_style(int32_t style);
+
+/**
+ * Replace the active assets with a TOML or JSON asset pack
+ * The pack overlays cars, powerups, buildings, road, scenery, HUD titles and
+ * animations on its `base` style. On a parse error or a sprite that does not
+ * fit, the active assets are left unchanged and `false` is returned.
+ *
+ * # Safety
+ * `text` must be null or a valid C string.
+ */
+bool ratatui_set_asset_pack(const char *text);
+
+/**
+ * Load the active assets from a `.toml` or `.json` asset pack file
+ *
+ * # Safety
+ * `path` must be null or a valid C string.
+ */
+bool ratatui_load_asset_pack(const char *path);
+
+/**
+ * Load an asset pack and reload it whenever the file changes
+ * Meant for development: the file's modification time is checked before
+ * each frame and a changed pack is swapped in between frames. A pack that
+ * fails to load keeps the last good assets and is reported in an on-screen
+ * overlay until fixed. Returns whether the first load succeeded; watching
+ * starts either way.
+ *
+ * # Safety
+ * `path` must be null or a valid C string.
+ */
+bool ratatui_watch_asset_pack(const char *path);
+
+/** Stop watching the asset pack; the assets last loaded stay active */
+void ratatui_unwatch_asset_pack(void);
+
+/**
+ * Copy the reason the watched asset pack failed to load into `buf`
+ * Returns 0 while the pack is loaded fine, otherwise uses the buffer
+ * protocol of `ratatui_snapshot_text`.
+ *
+ * # Safety
+ * `buf` must be null or point to at least `len` writable bytes.
+ */
+int32_t ratatui_asset_reload_error(char *buf, size_t len);
+
+/** Style of the active assets, one of `asset_styles` */
+int32_t ratatui_asset_style(void);
+
+/**
+ * Show or hide the debug performance overlay on every renderer
+ * Game frames then carry FPS, frame and draw time, the cells the last
+ * frame changed on the terminal, object counts, terminal size and quality
+ * in their top right corner. F3 toggles it too.
+ */
+void ratatui_set_debug_overlay(bool enabled);
+
+/** Whether the debug overlay is shown */
+bool ratatui_debug_overlay(void);
+
+/**
+ * Create an independent renderer
+ * A null `config` creates an on-screen renderer. Only one on-screen renderer
+ * may exist at a time, but any number of headless ones can run alongside it.
+ * Returns null on failure (see `ratatui_last_error`). Release with
+ * `ratatui_destroy`.
+ *
+ * # Safety
+ * `config` must be null or point to a valid `RendererConfig`.
+ */
+Renderer *ratatui_create(const RendererConfig *config);
+
+/**
+ * Destroy a renderer, restoring the terminal if it owned it
+ *
+ * # Safety
+ * `renderer` must be null or a handle from `ratatui_create` that has not
+ * been destroyed yet, and must not be in use on another thread.
+ */
+void ratatui_destroy(Renderer *renderer);
+
+/**
+ * Render the game on a renderer handle
+ *
+ * # Safety
+ * `renderer` must be null or a live handle from `ratatui_create`; `state`
+ * has the same requirements as in `ratatui_render`.
+ */
+bool ratatui_renderer_render(const Renderer *renderer, const GameState *state);
+
+/**
+ * Render the menu on a renderer handle
+ *
+ * # Safety
+ * `renderer` must be null or a live handle from `ratatui_create`; the
+ * strings have the same requirements as in `ratatui_render_menu`.
+ */
+bool ratatui_renderer_render_menu(const Renderer *renderer, const char *title, const char *const *options, int32_t option_count, int32_t selected);
+
+/**
+ * Pace a renderer handle's frames; see `ratatui_set_target_fps`
+ *
+ * # Safety
+ * `renderer` must be null or a live handle from `ratatui_create`.
+ */
+bool ratatui_renderer_set_target_fps(const Renderer *renderer, uint32_t fps);
+
+/**
+ * Wait for a renderer handle's next frame; see `ratatui_wait_frame`
+ *
+ * # Safety
+ * `renderer` must be null or a live handle from `ratatui_create`.
+ */
+bool ratatui_renderer_wait_frame(const Renderer *renderer);
+
+/**
+ * Set a renderer handle's effect quality; see `ratatui_set_quality`
+ *
+ * # Safety
+ * `renderer` must be null or a live handle from `ratatui_create`.
+ */
+bool ratatui_renderer_set_quality(const Renderer *renderer, int32_t level, bool adaptive);
+
+/**
+ * Read a renderer handle's pacing figures; see `ratatui_frame_stats`
+ *
+ * # Safety
+ * `renderer` must be null or a live handle from `ratatui_create`; `stats`
+ * must be null or point to a writable `FrameStats`.
+ */
+bool ratatui_renderer_frame_stats(const Renderer *renderer, FrameStats *stats);
+
+/**
+ * Set a renderer handle's colour depth; see `ratatui_set_color_mode`
+ *
+ * # Safety
+ * `renderer` must be null or a live handle from `ratatui_create`.
+ */
+bool ratatui_renderer_set_color_mode(const Renderer *renderer, int32_t mode);
+
+/**
+ * Colour depth a renderer handle draws at; see `ratatui_color_mode`
+ *
+ * # Safety
+ * `renderer` must be null or a live handle from `ratatui_create`.
+ */
+int32_t ratatui_renderer_color_mode(const Renderer *renderer);
+
+/**
+ * Poll input for a renderer handle
+ * Off-screen renderers always report an idle input state.
+ *
+ * # Safety
+ * `renderer` must be null or a live handle from `ratatui_create`; `input`
+ * must be null or point to a valid, writable `InputState`.
+ */
+bool ratatui_renderer_poll_input(const Renderer *renderer, InputState *input);
+
+/**
+ * Turn mouse reporting on or off for a renderer handle
+ * Off by default; has no effect on off-screen renderers.
+ *
+ * # Safety
+ * `renderer` must be null or a live handle from `ratatui_create`.
+ */
+bool ratatui_renderer_set_mouse_capture(const Renderer *renderer, bool enabled);
+
+/**
+ * Take the next mouse event of a renderer handle
+ * See `ratatui_poll_mouse`.
+ *
+ * # Safety
+ * `renderer` must be null or a live handle from `ratatui_create`; `event`
+ * must be null or point to a writable `MouseEvent`.
+ */
+bool ratatui_renderer_poll_mouse(const Renderer *renderer, MouseEvent *event);
+
+/**
+ * Take the next event of a renderer handle
+ * See `ratatui_next_event`.
+ *
+ * # Safety
+ * `renderer` must be null or a live handle from `ratatui_create`; `event`
+ * must be null or point to a writable `Event`.
+ */
+bool ratatui_renderer_next_event(const Renderer *renderer, Event *event);
+
+/**
+ * Copy the last frame of a headless renderer as text
+ * See `ratatui_snapshot_text` for the buffer protocol.
+ *
+ * # Safety
+ * `renderer` must be null or a live handle from `ratatui_create`; `buf`
+ * must be null or point to at least `len` writable bytes.
+ */
+int32_t ratatui_renderer_snapshot_text(const Renderer *renderer, char *buf, size_t len);
+
+/**
+ * Copy the cell styles of the last frame of a headless renderer
+ * See `ratatui_snapshot_styles` for the buffer protocol.
+ *
+ * # Safety
+ * `renderer` must be null or a live handle from `ratatui_create`; `cells`
+ * must be null or point to at least `capacity` writable `CellStyle`s.
+ */
+int32_t ratatui_renderer_snapshot_styles(const Renderer *renderer, CellStyle *cells, size_t capacity);
+
+/**
+ * Initialize the rendering engine
+ * Sets up terminal, raw mode, and alternate screen
+ */
+bool ratatui_init(void);
+
+/**
+ * Initialize an off-screen renderer of the given size
+ * Frames are kept in memory and can be read back with the snapshot calls
+ */
+bool ratatui_init_headless(uint16_t width, uint16_t height);
+
+/** Clean up and restore terminal state */
+void ratatui_cleanup(void);
+
+/**
+ * Poll for keyboard input and update input state
+ * Waits up to 16 ms for the first event unless frames are paced with
+ * `ratatui_set_target_fps`, in which case it never waits.
+ *
+ * # Safety
+ * `input` must be null or point to a valid, writable `InputState`.
+ */
+bool ratatui_poll_input(InputState *input);
+
+/**
+ * Turn mouse reporting on or off
+ * Off by default, since capturing the mouse disables text selection in most
+ * terminals. Mouse events are read by `ratatui_poll_input`.
+ */
+bool ratatui_set_mouse_capture(bool enabled);
+
+/**
+ * Take the next mouse event read by `ratatui_poll_input`
+ * Events are hit-tested against the last frame: clicks on menu options
+ * report the option index, clicks and drags on the replay timeline the seek
+ * position. Returns `false` once the queue is empty, so call it in a loop
+ * after each poll.
+ *
+ * # Safety
+ * `event` must be null or point to a writable `MouseEvent`.
+ */
+bool ratatui_poll_mouse(MouseEvent *event);
+
+/**
+ * Take the next entry of the terminal event stream
+ * Reports key presses and releases (with an auto-repeat flag), resizes with
+ * the new size, focus changes, mouse events hit-tested like
+ * `ratatui_poll_mouse`, and pastes. Never waits; returns `false` once no
+ * event is pending, so call it in a loop each frame. Events read here still
+ * count towards the next `ratatui_poll_input`.
+ *
+ * # Safety
+ * `event` must be null or point to a writable `Event`.
+ */
+bool ratatui_next_event(Event *event);
+
+/**
+ * Copy the text of the last paste event taken with `ratatui_next_event`
+ * See `ratatui_snapshot_text` for the buffer protocol.
+ *
+ * # Safety
+ * `buf` must be null or point to at least `len` writable bytes.
+ */
+int32_t ratatui_event_text(char *buf, size_t len);
+
+/**
+ * Render the game based on current game state
+ *
+ * # Safety
+ * `state` must be null or point to a valid `GameState` whose object arrays
+ * hold at least `car_count`, `obstacle_count` and `building_count` entries.
+ */
+bool ratatui_render(const GameState *state);
+
+/**
+ * Render menu with title and options
+ *
+ * # Safety
+ * `title` must be null or a valid C string and `options` must be null or
+ * point to `option_count` valid C strings.
+ */
+bool ratatui_render_menu(const char *title, const char *const *options, int32_t option_count, int32_t selected);
+
+/**
+ * Pace frames at `fps` frames per second, or stop pacing with 0
+ * Paced hosts call `ratatui_wait_frame` once per frame instead of sleeping,
+ * and `ratatui_poll_input` stops waiting for input, so input latency no
+ * longer depends on the frame rate. Unpaced by default.
+ */
+bool ratatui_set_target_fps(uint32_t fps);
+
+/**
+ * Wait until the next frame is due at the target rate
+ * Input keeps being read while waiting and counts towards the next
+ * `ratatui_poll_input`. A host running late is not made to catch up: the
+ * next frame is due one period after this call. Returns at once while
+ * frames are not paced.
+ */
+bool ratatui_wait_frame(void);
+
+/**
+ * Draw at one of `quality_levels`
+ * With `adaptive`, `level` is the best quality allowed: the renderer drops
+ * a level (rain, then particles, then building windows) while game frames
+ * take longer than the frame budget to draw and flush, and raises it again
+ * once they take well under it. The budget is one frame at the target rate,
+ * or at 60 FPS while unpaced. An unknown level returns `false`.
+ */
+bool ratatui_set_quality(int32_t level, bool adaptive);
+
+/**
+ * Copy the frame counter, measured frame cost, target rate and current
+ * quality into `stats`
+ *
+ * # Safety
+ * `stats` must be null or point to a writable `FrameStats`.
+ */
+bool ratatui_frame_stats(FrameStats *stats);
+
+/**
+ * Draw at one of `color_modes` from the next frame on
+ * Below `TRUECOLOR`, every RGB colour is replaced by the nearest entry of
+ * the 256-colour palette or of the 16 basic colours. `AUTO`, the default,
+ * detects the depth from `COLORTERM` and `TERM`; headless renderers always
+ * detect `TRUECOLOR`. An unknown mode returns `false`.
+ */
+bool ratatui_set_color_mode(int32_t mode);
+
+/**
+ * Colour depth frames are drawn at, one of `color_modes` other than `AUTO`
+ * Returns -1 when no renderer is active.
+ */
+int32_t ratatui_color_mode(void);
+
+/**
+ * Copy the last headless frame into `buf` as NUL-terminated UTF-8 text
+ * Rows are separated by `\n`. Returns the full text length in bytes (without
+ * the NUL), so a call with `len == 0` can be used to size the buffer.
+ * Returns -1 when no headless renderer is active.
+ *
+ * # Safety
+ * `buf` must be null or point to at least `len` writable bytes.
+ */
+int32_t ratatui_snapshot_text(char *buf, size_t len);
+
+/**
+ * Copy the styles of the last headless frame into `cells` in row-major order
+ * Returns the total number of cells (width * height); at most `capacity`
+ * entries are written. Returns -1 when no headless renderer is active.
+ *
+ * # Safety
+ * `cells` must be null or point to at least `capacity` writable `CellStyle`s.
+ */
+int32_t ratatui_snapshot_styles(CellStyle *cells, size_t capacity);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* RUST_RENDERER_H */
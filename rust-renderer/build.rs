@@ -0,0 +1,694 @@
+//! Generates the host bindings for the C FFI surface
+//! Parses the crate sources and writes `rust_renderer.h` and `RatatuiFFI.g.cs`
+//! to OUT_DIR, so the C and C# definitions always match Rust. With
+//! `UPDATE_BINDINGS` set they are also copied to the checked-in `bindings/`.
+
+use std::fmt::Write as _;
+use std::fs;
+use std::path::{Path, PathBuf};
+use syn::{Attribute, Expr, Fields, FnArg, Item, Lit, Meta, Pat, ReturnType, Type, UnOp};
+
+const HEADER_FILE: &str = "rust_renderer.h";
+const CSHARP_FILE: &str = "RatatuiFFI.g.cs";
+const BINDINGS_DIR: &str = "bindings";
+/// Opt-in to refreshing the checked-in copies; build scripts otherwise only
+/// write to OUT_DIR
+const UPDATE_ENV: &str = "UPDATE_BINDINGS";
+const CSHARP_NAMESPACE: &str = "TerminalRacer.Rendering.FFI";
+const LIB_NAME: &str = "rust_renderer";
+
+fn main() {
+    println!("cargo:rerun-if-changed=build.rs");
+    println!("cargo:rerun-if-changed=src");
+    println!("cargo:rerun-if-env-changed={}", UPDATE_ENV);
+
+    let api = Api::parse(Path::new("src"));
+    let outputs = [(HEADER_FILE, c_header(&api)), (CSHARP_FILE, csharp(&api))];
+
+    let out_dir = PathBuf::from(std::env::var_os("OUT_DIR").expect("OUT_DIR is set by cargo"));
+    let update = std::env::var_os(UPDATE_ENV).is_some_and(|v| !v.is_empty() && v != "0");
+    for (file, contents) in &outputs {
+        write_if_changed(&out_dir.join(file), contents);
+        if update {
+            write_if_changed(&Path::new(BINDINGS_DIR).join(file), contents);
+        }
+    }
+}
+
+/// Only touch the outputs when they change, so host builds are not invalidated
+fn write_if_changed(path: &Path, contents: &str) {
+    if fs::read_to_string(path).ok().as_deref() == Some(contents) {
+        return;
+    }
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).expect("create bindings directory");
+    }
+    fs::write(path, contents).unwrap_or_else(|e| panic!("write {}: {}", path.display(), e));
+}
+
+// ---------------------------------------------------------------------------
+// FFI model
+// ---------------------------------------------------------------------------
+
+#[derive(Clone, Debug, PartialEq)]
+enum Ty {
+    Unit,
+    Prim(String),
+    Named(String),
+    Ptr { mutable: bool, inner: Box<Ty> },
+    Array(Box<Ty>, usize),
+}
+
+struct Field {
+    name: String,
+    ty: Ty,
+    docs: Vec<String>,
+}
+
+struct Struct {
+    name: String,
+    docs: Vec<String>,
+    fields: Vec<Field>,
+}
+
+struct Enum {
+    name: String,
+    docs: Vec<String>,
+    variants: Vec<(String, i64, Vec<String>)>,
+}
+
+struct Const {
+    name: String,
+    ty: String,
+    value: i64,
+    docs: Vec<String>,
+}
+
+/// `pub mod` holding only integer constants, e.g. `game_modes`
+struct ConstModule {
+    name: String,
+    docs: Vec<String>,
+    consts: Vec<Const>,
+}
+
+struct Function {
+    name: String,
+    docs: Vec<String>,
+    params: Vec<(String, Ty)>,
+    ret: Ty,
+}
+
+#[derive(Default)]
+struct Api {
+    consts: Vec<Const>,
+    modules: Vec<ConstModule>,
+    enums: Vec<Enum>,
+    structs: Vec<Struct>,
+    functions: Vec<Function>,
+}
+
+impl Api {
+    /// Collect `#[repr(C)]` types and `extern "C"` functions from every source
+    /// file, and constants from the `core` module
+    /// Constants are read first so array lengths can refer to them.
+    fn parse(src: &Path) -> Self {
+        let files: Vec<(syn::File, bool)> = rust_files(src)
+            .into_iter()
+            .map(|path| {
+                let source = fs::read_to_string(&path).unwrap_or_else(|e| panic!("read {}: {}", path.display(), e));
+                let file = syn::parse_file(&source).unwrap_or_else(|e| panic!("parse {}: {}", path.display(), e));
+                (file, path.starts_with(src.join("core")))
+            })
+            .collect();
+
+        let mut api = Api::default();
+        for constants in [true, false] {
+            for (file, in_core) in &files {
+                let items = file.items.iter().filter(|item| matches!(item, Item::Const(_) | Item::Mod(_)) == constants);
+                for item in items {
+                    api.add_item(item, *in_core);
+                }
+            }
+        }
+        api
+    }
+
+    fn add_item(&mut self, item: &Item, in_core: bool) {
+        match item {
+            Item::Struct(s) if is_repr_c(&s.attrs) => {
+                let Fields::Named(named) = &s.fields else { return };
+                self.structs.push(Struct {
+                    name: s.ident.to_string(),
+                    docs: docs(&s.attrs),
+                    fields: named
+                        .named
+                        .iter()
+                        .map(|f| Field {
+                            name: f.ident.as_ref().unwrap().to_string(),
+                            ty: ty(&f.ty, &self.consts),
+                            docs: docs(&f.attrs),
+                        })
+                        .collect(),
+                });
+            },
+            Item::Enum(e) if is_repr_c(&e.attrs) => {
+                let mut next = 0;
+                let variants = e
+                    .variants
+                    .iter()
+                    .map(|v| {
+                        let value = v.discriminant.as_ref().map_or(next, |(_, expr)| int_value(expr));
+                        next = value + 1;
+                        (v.ident.to_string(), value, docs(&v.attrs))
+                    })
+                    .collect();
+                self.enums.push(Enum { name: e.ident.to_string(), docs: docs(&e.attrs), variants });
+            },
+            Item::Fn(f) if is_export(&f.attrs) && f.sig.abi.is_some() => {
+                let params = f
+                    .sig
+                    .inputs
+                    .iter()
+                    .filter_map(|arg| match arg {
+                        FnArg::Typed(t) => match &*t.pat {
+                            Pat::Ident(p) => Some((p.ident.to_string(), ty(&t.ty, &self.consts))),
+                            _ => None,
+                        },
+                        FnArg::Receiver(_) => None,
+                    })
+                    .collect();
+                let ret = match &f.sig.output {
+                    ReturnType::Default => Ty::Unit,
+                    ReturnType::Type(_, t) => ty(t, &self.consts),
+                };
+                self.functions.push(Function { name: f.sig.ident.to_string(), docs: docs(&f.attrs), params, ret });
+            },
+            Item::Const(c) if in_core && is_pub(&c.vis) => {
+                if let Some(constant) = int_const(c) {
+                    self.consts.push(constant);
+                }
+            },
+            Item::Mod(m) if in_core && is_pub(&m.vis) => {
+                let Some((_, items)) = &m.content else { return };
+                let consts: Option<Vec<Const>> = items
+                    .iter()
+                    .map(|item| match item {
+                        Item::Const(c) => int_const(c),
+                        _ => None,
+                    })
+                    .collect();
+                if let Some(consts) = consts.filter(|c| !c.is_empty()) {
+                    self.modules.push(ConstModule { name: m.ident.to_string(), docs: docs(&m.attrs), consts });
+                }
+            },
+            _ => {},
+        }
+    }
+
+    fn is_struct(&self, name: &str) -> bool {
+        self.structs.iter().any(|s| s.name == name)
+    }
+
+    fn is_enum(&self, name: &str) -> bool {
+        self.enums.iter().any(|e| e.name == name)
+    }
+
+    /// Named types only ever passed by pointer, e.g. `Renderer`
+    fn opaque_types(&self) -> Vec<String> {
+        let mut opaque = Vec::new();
+        for f in &self.functions {
+            for t in f.params.iter().map(|(_, t)| t).chain([&f.ret]) {
+                if let Some(name) = pointee_name(t)
+                    && !self.is_struct(name)
+                    && !self.is_enum(name)
+                    && !opaque.iter().any(|o| o == name)
+                {
+                    opaque.push(name.to_string());
+                }
+            }
+        }
+        opaque
+    }
+}
+
+fn rust_files(dir: &Path) -> Vec<PathBuf> {
+    let mut entries: Vec<PathBuf> = fs::read_dir(dir)
+        .unwrap_or_else(|e| panic!("read {}: {}", dir.display(), e))
+        .map(|entry| entry.expect("directory entry").path())
+        .collect();
+    entries.sort();
+
+    let mut files = Vec::new();
+    for path in entries {
+        if path.is_dir() {
+            files.extend(rust_files(&path));
+        } else if path.extension().is_some_and(|ext| ext == "rs") {
+            files.push(path);
+        }
+    }
+    files
+}
+
+fn docs(attrs: &[Attribute]) -> Vec<String> {
+    attrs
+        .iter()
+        .filter_map(|attr| match &attr.meta {
+            Meta::NameValue(nv) if nv.path.is_ident("doc") => match &nv.value {
+                Expr::Lit(lit) => match &lit.lit {
+                    Lit::Str(s) => Some(s.value().strip_prefix(' ').map(str::to_string).unwrap_or_else(|| s.value())),
+                    _ => None,
+                },
+                _ => None,
+            },
+            _ => None,
+        })
+        .collect()
+}
+
+/// Doc lines up to the first markdown section such as `# Safety`
+fn summary(docs: &[String]) -> Vec<String> {
+    let mut lines: Vec<String> = docs.iter().take_while(|l| !l.starts_with('#')).cloned().collect();
+    while lines.last().is_some_and(|l| l.is_empty()) {
+        lines.pop();
+    }
+    lines
+}
+
+fn is_repr_c(attrs: &[Attribute]) -> bool {
+    attrs.iter().any(|attr| {
+        let mut c = false;
+        if attr.path().is_ident("repr") {
+            let _ = attr.parse_nested_meta(|meta| {
+                c |= meta.path.is_ident("C") || meta.path.is_ident("i32");
+                Ok(())
+            });
+        }
+        c
+    })
+}
+
+fn is_export(attrs: &[Attribute]) -> bool {
+    attrs.iter().any(|attr| {
+        let mut export = false;
+        if attr.path().is_ident("unsafe") {
+            let _ = attr.parse_nested_meta(|meta| {
+                export |= meta.path.is_ident("no_mangle");
+                Ok(())
+            });
+        }
+        export || attr.path().is_ident("no_mangle")
+    })
+}
+
+fn is_pub(vis: &syn::Visibility) -> bool {
+    matches!(vis, syn::Visibility::Public(_))
+}
+
+fn int_value(expr: &Expr) -> i64 {
+    match expr {
+        Expr::Lit(lit) => match &lit.lit {
+            Lit::Int(i) => i.base10_parse().expect("integer literal"),
+            _ => panic!("unsupported constant expression"),
+        },
+        Expr::Unary(u) if matches!(u.op, UnOp::Neg(_)) => -int_value(&u.expr),
+        _ => panic!("unsupported constant expression"),
+    }
+}
+
+fn int_const(c: &syn::ItemConst) -> Option<Const> {
+    let Type::Path(_) = &*c.ty else { return None };
+    let Ty::Prim(ty) = ty(&c.ty, &[]) else { return None };
+    if ty == "bool" || ty.starts_with('f') {
+        return None;
+    }
+    Some(Const { name: c.ident.to_string(), ty, value: int_value(&c.expr), docs: docs(&c.attrs) })
+}
+
+/// Map a Rust type; array lengths may name a constant from `consts`
+fn ty(t: &Type, consts: &[Const]) -> Ty {
+    match t {
+        Type::Tuple(tuple) if tuple.elems.is_empty() => Ty::Unit,
+        Type::Ptr(p) => Ty::Ptr { mutable: p.mutability.is_some(), inner: Box::new(ty(&p.elem, consts)) },
+        Type::Array(a) => Ty::Array(Box::new(ty(&a.elem, consts)), array_len(&a.len, consts)),
+        Type::Path(p) => {
+            let name = p.path.segments.last().expect("type name").ident.to_string();
+            match name.as_str() {
+                "bool" | "i8" | "u8" | "i16" | "u16" | "i32" | "u32" | "i64" | "u64" | "f32" | "f64" | "usize"
+                | "isize" | "c_char" => Ty::Prim(name),
+                _ => Ty::Named(name),
+            }
+        },
+        _ => panic!("unsupported FFI type"),
+    }
+}
+
+fn array_len(expr: &Expr, consts: &[Const]) -> usize {
+    match expr {
+        Expr::Path(p) => {
+            let name = p.path.segments.last().expect("constant name").ident.to_string();
+            let c = consts.iter().find(|c| c.name == name).unwrap_or_else(|| panic!("unknown array length {}", name));
+            c.value as usize
+        },
+        _ => int_value(expr) as usize,
+    }
+}
+
+fn pointee_name(t: &Ty) -> Option<&str> {
+    match t {
+        Ty::Ptr { inner, .. } => match &**inner {
+            Ty::Named(name) => Some(name),
+            other => pointee_name(other),
+        },
+        _ => None,
+    }
+}
+
+fn is_c_char(t: &Ty) -> bool {
+    matches!(t, Ty::Prim(p) if p == "c_char")
+}
+
+fn pascal_case(snake: &str) -> String {
+    snake
+        .split('_')
+        .map(|word| {
+            let mut chars = word.chars();
+            match chars.next() {
+                Some(first) => first.to_ascii_uppercase().to_string() + &chars.as_str().to_ascii_lowercase(),
+                None => String::new(),
+            }
+        })
+        .collect()
+}
+
+fn camel_case(snake: &str) -> String {
+    let pascal = pascal_case(snake);
+    let mut chars = pascal.chars();
+    match chars.next() {
+        Some(first) => first.to_ascii_lowercase().to_string() + chars.as_str(),
+        None => pascal,
+    }
+}
+
+fn screaming_case(pascal: &str) -> String {
+    let mut out = String::new();
+    for (i, ch) in pascal.chars().enumerate() {
+        if ch.is_ascii_uppercase() && i > 0 {
+            out.push('_');
+        }
+        out.push(ch.to_ascii_uppercase());
+    }
+    out
+}
+
+// ---------------------------------------------------------------------------
+// C header
+// ---------------------------------------------------------------------------
+
+fn c_header(api: &Api) -> String {
+    let mut out = String::new();
+    out.push_str("/* Generated by rust-renderer/build.rs from the Rust sources. Do not edit. */\n\n");
+    out.push_str("#ifndef RUST_RENDERER_H\n#define RUST_RENDERER_H\n\n");
+    out.push_str("#include <stdbool.h>\n#include <stddef.h>\n#include <stdint.h>\n\n");
+
+    for c in &api.consts {
+        c_docs(&mut out, &c.docs, "");
+        writeln!(out, "#define RATATUI_{} {}", c.name, c.value).unwrap();
+    }
+    out.push('\n');
+
+    for m in &api.modules {
+        c_docs(&mut out, &m.docs, "");
+        for c in &m.consts {
+            writeln!(out, "#define {}_{} {}", m.name.to_ascii_uppercase(), c.name, c.value).unwrap();
+        }
+        out.push('\n');
+    }
+
+    for e in &api.enums {
+        c_docs(&mut out, &e.docs, "");
+        writeln!(out, "enum {} {{", e.name).unwrap();
+        for (variant, value, docs) in &e.variants {
+            c_docs(&mut out, docs, "    ");
+            writeln!(out, "    {}_{} = {},", screaming_case(&e.name), screaming_case(variant), value).unwrap();
+        }
+        writeln!(out, "}};\ntypedef int32_t {};\n", e.name).unwrap();
+    }
+
+    for name in api.opaque_types() {
+        writeln!(out, "typedef struct {} {};", name, name).unwrap();
+    }
+    out.push('\n');
+
+    for s in &api.structs {
+        c_docs(&mut out, &s.docs, "");
+        writeln!(out, "typedef struct {} {{", s.name).unwrap();
+        for f in &s.fields {
+            c_docs(&mut out, &f.docs, "    ");
+            writeln!(out, "    {};", c_decl(&f.ty, &f.name)).unwrap();
+        }
+        writeln!(out, "}} {};\n", s.name).unwrap();
+    }
+
+    out.push_str("#ifdef __cplusplus\nextern \"C\" {\n#endif\n\n");
+    for f in &api.functions {
+        c_docs(&mut out, &f.docs, "");
+        let params = if f.params.is_empty() {
+            "void".to_string()
+        } else {
+            f.params.iter().map(|(name, t)| c_decl(t, name)).collect::<Vec<_>>().join(", ")
+        };
+        writeln!(out, "{}({});\n", c_decl(&f.ret, &f.name), params).unwrap();
+    }
+    out.push_str("#ifdef __cplusplus\n}\n#endif\n\n#endif /* RUST_RENDERER_H */\n");
+    out
+}
+
+fn c_docs(out: &mut String, docs: &[String], indent: &str) {
+    match docs {
+        [] => return,
+        [line] => return writeln!(out, "{}/** {} */", indent, line).unwrap(),
+        _ => {},
+    }
+    writeln!(out, "{}/**", indent).unwrap();
+    for line in docs {
+        writeln!(out, "{} *{}{}", indent, if line.is_empty() { "" } else { " " }, line).unwrap();
+    }
+    writeln!(out, "{} */", indent).unwrap();
+}
+
+fn c_type(t: &Ty) -> String {
+    match t {
+        Ty::Unit => "void".into(),
+        Ty::Prim(p) => match p.as_str() {
+            "bool" => "bool",
+            "c_char" => "char",
+            "f32" => "float",
+            "f64" => "double",
+            "usize" => "size_t",
+            "isize" => "ptrdiff_t",
+            "i8" => "int8_t",
+            "u8" => "uint8_t",
+            "i16" => "int16_t",
+            "u16" => "uint16_t",
+            "i32" => "int32_t",
+            "u32" => "uint32_t",
+            "i64" => "int64_t",
+            "u64" => "uint64_t",
+            _ => unreachable!(),
+        }
+        .into(),
+        Ty::Named(name) => name.clone(),
+        Ty::Ptr { mutable, inner } => match &**inner {
+            Ty::Ptr { .. } => format!("{}{}*", c_type(inner), if *mutable { "" } else { "const " }),
+            _ => format!("{}{} *", if *mutable { "" } else { "const " }, c_type(inner)),
+        },
+        Ty::Array(..) => unreachable!("arrays are only declared through c_decl"),
+    }
+}
+
+fn c_decl(t: &Ty, name: &str) -> String {
+    match t {
+        Ty::Array(inner, len) => format!("{} {}[{}]", c_type(inner), name, len),
+        _ => {
+            let ty = c_type(t);
+            if ty.ends_with('*') { format!("{}{}", ty, name) } else { format!("{} {}", ty, name) }
+        },
+    }
+}
+
+// ---------------------------------------------------------------------------
+// C# bindings
+// ---------------------------------------------------------------------------
+
+fn csharp(api: &Api) -> String {
+    let mut out = String::new();
+    out.push_str("// <auto-generated>\n");
+    out.push_str("// Generated by rust-renderer/build.rs from the Rust sources. Do not edit.\n");
+    out.push_str("// </auto-generated>\n#nullable enable\n\n");
+    out.push_str("using System.Runtime.InteropServices;\n\n");
+    writeln!(out, "namespace {};\n", CSHARP_NAMESPACE).unwrap();
+
+    for e in &api.enums {
+        cs_docs(&mut out, &e.docs, "");
+        writeln!(out, "public enum {} : int\n{{", e.name).unwrap();
+        for (variant, value, docs) in &e.variants {
+            cs_docs(&mut out, docs, "    ");
+            writeln!(out, "    {} = {},", variant, value).unwrap();
+        }
+        out.push_str("}\n\n");
+    }
+
+    for s in &api.structs {
+        cs_docs(&mut out, &s.docs, "");
+        writeln!(out, "[StructLayout(LayoutKind.Sequential)]\npublic struct {}\n{{", s.name).unwrap();
+        for f in &s.fields {
+            cs_docs(&mut out, &f.docs, "    ");
+            let name = pascal_case(&f.name);
+            match &f.ty {
+                Ty::Array(inner, len) => writeln!(
+                    out,
+                    "    [MarshalAs(UnmanagedType.ByValArray, SizeConst = {})] public {}[] {};",
+                    len,
+                    cs_type(inner),
+                    name
+                )
+                .unwrap(),
+                Ty::Prim(p) if p == "bool" => {
+                    writeln!(out, "    [MarshalAs(UnmanagedType.I1)] public bool {};", name).unwrap()
+                },
+                t => writeln!(out, "    public {} {};", cs_type(t), name).unwrap(),
+            }
+        }
+        out.push_str("}\n\n");
+    }
+
+    out.push_str("public static partial class RatatuiFFI\n{\n");
+    writeln!(out, "    private const string LibName = \"{}\";", LIB_NAME).unwrap();
+    for c in &api.consts {
+        out.push('\n');
+        cs_docs(&mut out, &c.docs, "    ");
+        writeln!(out, "    public const {} {} = {};", cs_const_type(&c.ty), pascal_case(&c.name), c.value)
+            .unwrap();
+    }
+
+    for m in &api.modules {
+        out.push('\n');
+        cs_docs(&mut out, &m.docs, "    ");
+        writeln!(out, "    public static class {}\n    {{", pascal_case(&m.name)).unwrap();
+        for c in &m.consts {
+            cs_docs(&mut out, &c.docs, "        ");
+            writeln!(
+                out,
+                "        public const {} {} = {};",
+                cs_const_type(&c.ty),
+                pascal_case(&c.name),
+                c.value
+            )
+            .unwrap();
+        }
+        out.push_str("    }\n");
+    }
+
+    for f in &api.functions {
+        out.push('\n');
+        cs_docs(&mut out, &summary(&f.docs), "    ");
+        out.push_str("    [DllImport(LibName, CallingConvention = CallingConvention.Cdecl)]\n");
+        let ret = match &f.ret {
+            Ty::Prim(p) if p == "bool" => {
+                out.push_str("    [return: MarshalAs(UnmanagedType.I1)]\n");
+                "bool".to_string()
+            },
+            Ty::Ptr { .. } => "IntPtr".to_string(),
+            t => cs_type(t),
+        };
+        let params: Vec<String> = f
+            .params
+            .iter()
+            .enumerate()
+            .map(|(i, (name, t))| {
+                let followed_by_len = matches!(f.params.get(i + 1), Some((_, Ty::Prim(p))) if p == "usize");
+                format!("{} {}", cs_param(api, t, followed_by_len), cs_ident(&camel_case(name)))
+            })
+            .collect();
+        writeln!(out, "    public static extern {} {}({});", ret, f.name, params.join(", ")).unwrap();
+    }
+    out.push_str("}\n");
+    out
+}
+
+fn cs_docs(out: &mut String, docs: &[String], indent: &str) {
+    if docs.is_empty() {
+        return;
+    }
+    writeln!(out, "{}/// <summary>", indent).unwrap();
+    for line in docs {
+        let escaped = line.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;");
+        writeln!(out, "{}///{}{}", indent, if escaped.is_empty() { "" } else { " " }, escaped).unwrap();
+    }
+    writeln!(out, "{}/// </summary>", indent).unwrap();
+}
+
+/// C# constants cannot be pointer-sized, and sizes are `int` there anyway
+fn cs_const_type(ty: &str) -> String {
+    match ty {
+        "usize" | "isize" => "int".into(),
+        _ => cs_type(&Ty::Prim(ty.to_string())),
+    }
+}
+
+fn cs_type(t: &Ty) -> String {
+    match t {
+        Ty::Unit => "void".into(),
+        Ty::Prim(p) => match p.as_str() {
+            "bool" => "bool",
+            "c_char" | "u8" => "byte",
+            "i8" => "sbyte",
+            "f32" => "float",
+            "f64" => "double",
+            "usize" => "UIntPtr",
+            "isize" => "IntPtr",
+            "i16" => "short",
+            "u16" => "ushort",
+            "i32" => "int",
+            "u32" => "uint",
+            "i64" => "long",
+            "u64" => "ulong",
+            _ => unreachable!(),
+        }
+        .into(),
+        Ty::Named(name) => name.clone(),
+        Ty::Ptr { .. } => "IntPtr".into(),
+        Ty::Array(inner, _) => format!("{}[]", cs_type(inner)),
+    }
+}
+
+/// Parameter name, escaped when it is a C# keyword (`event` -> `@event`)
+fn cs_ident(name: &str) -> String {
+    const KEYWORDS: [&str; 12] =
+        ["event", "object", "string", "base", "params", "ref", "out", "in", "checked", "fixed", "lock", "operator"];
+    if KEYWORDS.contains(&name) { format!("@{}", name) } else { name.to_string() }
+}
+
+/// Marshalling for one function parameter
+/// A pointer directly followed by a `usize` length is treated as an array.
+/// Strings are marshalled as UTF-8, which the Rust side requires.
+fn cs_param(api: &Api, t: &Ty, followed_by_len: bool) -> String {
+    let Ty::Ptr { mutable, inner } = t else {
+        return match t {
+            Ty::Prim(p) if p == "bool" => "[MarshalAs(UnmanagedType.I1)] bool".into(),
+            _ => cs_type(t),
+        };
+    };
+
+    match &**inner {
+        Ty::Prim(_) if is_c_char(inner) && !mutable => "[MarshalAs(UnmanagedType.LPUTF8Str)] string".into(),
+        Ty::Ptr { inner: innermost, .. } if is_c_char(innermost) && !mutable => {
+            "[MarshalAs(UnmanagedType.LPArray, ArraySubType = UnmanagedType.LPUTF8Str)] string[]".into()
+        },
+        Ty::Named(name) if !api.is_struct(name) && !api.is_enum(name) => "IntPtr".into(),
+        _ if followed_by_len && *mutable => format!("[Out] {}[]?", cs_type(inner)),
+        _ if followed_by_len => format!("[MarshalAs(UnmanagedType.LPArray)] {}[]", cs_type(inner)),
+        Ty::Named(name) => format!("ref {}", name),
+        _ => "IntPtr".into(),
+    }
+}
@@ -0,0 +1,87 @@
+//! Error codes and messages reported across the C FFI
+//! Failed calls record their error per thread, to be read with `ratatui_last_error`
+
+use std::cell::RefCell;
+use std::fmt;
+
+/// Category of the last failure
+#[repr(i32)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ErrorCode {
+    /// No error has been recorded on this thread
+    Ok = 0,
+    /// A required pointer argument was null
+    NullPointer = 1,
+    /// A string argument was not valid UTF-8
+    InvalidUtf8 = 2,
+    /// An argument was out of range, e.g. a zero-sized headless renderer
+    InvalidArgument = 3,
+    /// The global renderer has not been initialized
+    NotInitialized = 4,
+    /// Another renderer already owns the terminal
+    TerminalBusy = 5,
+    /// The terminal refused raw mode
+    RawModeDenied = 6,
+    /// Reading input or drawing to the terminal failed
+    Io = 7,
+    /// The host struct layout does not match Rust
+    LayoutMismatch = 8,
+    /// The operation needs a headless renderer
+    NotHeadless = 9,
+    /// The renderer panicked and must be destroyed
+    Panic = 10,
+    /// A key binding table could not be parsed
+    InvalidBindings = 11,
+    /// A key is bound to more than one input
+    BindingConflict = 12,
+    /// An asset pack could not be parsed or a sprite does not fit
+    InvalidAssets = 13,
+}
+
+/// A failure with its code and a human-readable message
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Error {
+    pub code: ErrorCode,
+    pub message: String,
+}
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+impl Error {
+    pub fn new(code: ErrorCode, message: impl Into<String>) -> Self {
+        Error { code, message: message.into() }
+    }
+
+    /// A null pointer passed for `argument`
+    pub fn null(argument: &str) -> Self {
+        Error::new(ErrorCode::NullPointer, format!("`{}` must not be null", argument))
+    }
+
+    /// An I/O failure while doing `action`
+    pub fn io(action: &str, err: std::io::Error) -> Self {
+        Error::new(ErrorCode::Io, format!("{}: {}", action, err))
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+impl std::error::Error for Error {}
+
+thread_local! {
+    /// Last failure on this thread; kept until the next failure replaces it
+    static LAST_ERROR: RefCell<Option<Error>> = const { RefCell::new(None) };
+}
+
+/// Record a failure for `ratatui_last_error`
+pub fn set_last_error(error: Error) {
+    LAST_ERROR.with(|last| *last.borrow_mut() = Some(error));
+}
+
+/// Last failure recorded on this thread
+pub fn last_error() -> Option<Error> {
+    LAST_ERROR.with(|last| last.borrow().clone())
+}
@@ -0,0 +1,103 @@
+//! ABI version and struct layout tables for the C FFI
+//! Lets the host verify its hand-mirrored structs before any frame is drawn
+
+use std::mem::{offset_of, size_of};
+use std::sync::Mutex;
+use super::types::{GameState, InputState};
+
+/// Version of the FFI surface
+/// Bump whenever an exported function signature or `#[repr(C)]` layout changes
+pub const ABI_VERSION: u32 = 6;
+
+/// Build a `[(field name, byte offset)]` table in declaration order
+macro_rules! field_offsets {
+    ($ty:ty { $($field:ident),* $(,)? }) => {
+        [$((stringify!($field), offset_of!($ty, $field))),*]
+    };
+}
+
+/// Field offsets of `GameState`, in declaration order
+pub const GAME_STATE_FIELDS: [(&str, usize); 52] = field_offsets!(GameState {
+    player_position, player_speed, player_distance, player_health, player_score, player_car_type,
+    player2_active, player2_position, player2_speed, player2_distance, player2_health,
+    player2_score, player2_car_type,
+    lap_time, game_mode, track_type, lane_count, level, career_progress,
+    boost_active, boost_remaining, shield_active, shield_remaining,
+    invincibility_active, invincibility_remaining, magnet_active, magnet_remaining,
+    slowmo_active, slowmo_remaining,
+    car_count, ai_positions, ai_distances, ai_types, ai_is_boss,
+    obstacle_count, obstacle_positions, obstacle_distances, obstacle_types,
+    building_count, building_positions, building_distances, building_heights, building_types,
+    weather, curve_offset, elevation, tunnel_darkness,
+    combo, replay_mode, replay_progress, ghost_position, ghost_distance,
+});
+
+/// Field offsets of `InputState`, in declaration order
+pub const INPUT_STATE_FIELDS: [(&str, usize); 24] = field_offsets!(InputState {
+    p1_left, p1_right, p1_accel, p1_brake, p1_boost,
+    p2_left, p2_right, p2_accel, p2_brake, p2_boost,
+    quit, pause, menu,
+    menu_up, menu_down, menu_left, menu_right, menu_confirm, menu_back,
+    menu_page_up, menu_page_down, menu_erase,
+    text_len, text,
+});
+
+/// Layout of one struct as reported by the host
+pub struct HostLayout<'a> {
+    pub size: usize,
+    pub offsets: &'a [usize],
+}
+
+/// Compare a host struct layout against the Rust definition
+/// Returns a description of the first difference found
+pub fn check_struct(
+    name: &str,
+    rust_size: usize,
+    rust_fields: &[(&str, usize)],
+    host: &HostLayout,
+) -> Result<(), String> {
+    if host.size != rust_size {
+        return Err(format!("{} is {} bytes on the host but {} bytes in Rust", name, host.size, rust_size));
+    }
+
+    if host.offsets.len() != rust_fields.len() {
+        return Err(format!(
+            "{} has {} fields on the host but {} in Rust",
+            name,
+            host.offsets.len(),
+            rust_fields.len()
+        ));
+    }
+
+    for (&host_offset, &(field, rust_offset)) in host.offsets.iter().zip(rust_fields) {
+        if host_offset != rust_offset {
+            return Err(format!(
+                "{}.{} is at offset {} on the host but {} in Rust",
+                name, field, host_offset, rust_offset
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+/// Check both host structs against the Rust definitions
+pub fn check_layout(game_state: &HostLayout, input_state: &HostLayout) -> Result<(), String> {
+    check_struct("GameState", size_of::<GameState>(), &GAME_STATE_FIELDS, game_state)?;
+    check_struct("InputState", size_of::<InputState>(), &INPUT_STATE_FIELDS, input_state)
+}
+
+/// Last failed validation; renderers refuse to start while this is set
+static LAYOUT_MISMATCH: Mutex<Option<String>> = Mutex::new(None);
+
+/// Record the outcome of a host layout validation
+pub fn record_validation(result: Result<(), String>) -> bool {
+    let ok = result.is_ok();
+    *LAYOUT_MISMATCH.lock().unwrap_or_else(|e| e.into_inner()) = result.err();
+    ok
+}
+
+/// Error from the last failed validation, if any
+pub fn layout_mismatch() -> Option<String> {
+    LAYOUT_MISMATCH.lock().unwrap_or_else(|e| e.into_inner()).clone()
+}
@@ -1,7 +1,10 @@
-/// Core module - Data structures and utilities
-/// Contains game state definitions, type constants, and helper functions
+//! Core module - Data structures and utilities
+//! Contains game state definitions, type constants, and helper functions
 
+pub mod error;
+pub mod layout;
 pub mod types;
 pub mod utils;
 
-pub use types::{GameState, InputState, AudioCommand};
+pub use error::{Error, ErrorCode};
+pub use types::{GameState, InputState, AudioCommand, CellStyle, Event, FrameStats, MouseEvent, RendererConfig};
@@ -1,5 +1,5 @@
-/// Core data structures for the Terminal Racer game engine
-/// Defines all C-compatible game state, input, and audio structures
+//! Core data structures for the Terminal Racer game engine
+//! Defines all C-compatible game state, input, and audio structures
 
 /// Enhanced game state with comprehensive feature support
 /// Includes player state, multiplayer support, powerups, AI, obstacles, and environment
@@ -26,6 +26,7 @@ pub struct GameState {
     pub lap_time: f32,
     pub game_mode: i32,  // 0=single, 1=split, 2=career, 3=replay
     pub track_type: i32,  // 0=highway, 1=city, 2=mountain, 3=desert, 4=tunnel
+    pub lane_count: i32,  // 0 = the track's default lane count
     pub level: i32,
     pub career_progress: f32,
     
@@ -68,6 +69,7 @@ pub struct GameState {
     // Meta
     pub combo: i32,
     pub replay_mode: bool,
+    pub replay_progress: f32,  // 0.0-1.0 through the replay, drawn as the timeline
     pub ghost_position: i32,
     pub ghost_distance: f32,
 }
@@ -75,6 +77,7 @@ pub struct GameState {
 /// Input state for both players and system controls
 /// Supports dual-player input with separate control schemes
 #[repr(C)]
+#[derive(Default)]
 pub struct InputState {
     // Player 1
     pub p1_left: bool,
@@ -94,6 +97,39 @@ pub struct InputState {
     pub quit: bool,
     pub pause: bool,
     pub menu: bool,
+    
+    // Menu navigation
+    pub menu_up: bool,
+    pub menu_down: bool,
+    pub menu_left: bool,
+    pub menu_right: bool,
+    pub menu_confirm: bool,
+    pub menu_back: bool,
+    pub menu_page_up: bool,
+    pub menu_page_down: bool,
+    pub menu_erase: bool,
+    
+    // Characters typed this poll, as Unicode scalar values (name entry)
+    pub text_len: u32,
+    pub text: [u32; INPUT_TEXT_CAPACITY],
+}
+
+/// Characters `InputState::text` holds per poll; later ones are dropped
+pub const INPUT_TEXT_CAPACITY: usize = 16;
+
+impl InputState {
+    /// Append a typed character, returning `false` once the buffer is full
+    pub fn push_char(&mut self, c: char) -> bool {
+        let Some(slot) = self.text.get_mut(self.text_len as usize) else { return false };
+        *slot = c as u32;
+        self.text_len += 1;
+        true
+    }
+
+    /// Characters typed this poll
+    pub fn typed(&self) -> String {
+        self.text[..self.text_len as usize].iter().filter_map(|&c| char::from_u32(c)).collect()
+    }
 }
 
 /// Audio command structure for sound effects and music
@@ -104,6 +140,82 @@ pub struct AudioCommand {
     pub volume: f32,
 }
 
+/// Options for creating a renderer handle
+/// `headless` renderers draw into a `width` x `height` off-screen buffer;
+/// otherwise the renderer takes over the real terminal and the size is ignored.
+/// `color_mode` is one of `color_modes`; `AUTO` detects the terminal's depth.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default)]
+pub struct RendererConfig {
+    pub headless: bool,
+    pub width: u16,
+    pub height: u16,
+    pub color_mode: i32,
+}
+
+/// Mouse press, release, drag or scroll, hit-tested against the last frame
+/// `column`/`row` are terminal cells. `item` is the menu option index for
+/// `hit_targets::MENU_ITEM`, and `position` the 0.0-1.0 point on the
+/// timeline for `hit_targets::REPLAY_TIMELINE`.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct MouseEvent {
+    pub kind: i32,
+    pub button: i32,
+    pub column: u16,
+    pub row: u16,
+    pub target: i32,
+    pub item: i32,
+    pub position: f32,
+}
+
+/// One entry of the input event stream read with `ratatui_next_event`
+/// `kind` selects which fields are set: `key_*`, `modifiers` and `repeat` for
+/// key events, `width`/`height` for resizes, `mouse` for mouse events and
+/// `text_len` for pastes (the text itself comes from `ratatui_event_text`).
+/// For `key_codes::CHAR` keys `key_char` is the Unicode scalar value; for
+/// `key_codes::FUNCTION` keys it is the F-key number.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Event {
+    pub kind: i32,
+    pub key_code: i32,
+    pub key_char: u32,
+    pub modifiers: i32,
+    pub repeat: bool,
+    pub width: u16,
+    pub height: u16,
+    pub mouse: MouseEvent,
+    pub text_len: u32,
+}
+
+/// Frame pacing and quality of a renderer, read with `ratatui_frame_stats`
+/// `render_ms` is what the last game frame took to draw and flush, and
+/// `average_ms` its running average; `target_fps` is 0 while frames are not
+/// paced. `quality` is one of `quality_levels`, lowered and raised by the
+/// renderer itself while `adaptive` is set.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct FrameStats {
+    pub frame: u64,
+    pub render_ms: f32,
+    pub average_ms: f32,
+    pub target_fps: u32,
+    pub quality: i32,
+    pub adaptive: bool,
+}
+
+/// Style of a single rendered cell, exported from headless snapshots
+/// Colors are packed as `0x00000000` = reset, `0x01RRGGBB` = RGB,
+/// `0x020000NN` = indexed (named colors use their ANSI index 0-15)
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct CellStyle {
+    pub fg: u32,
+    pub bg: u32,
+    pub modifiers: u16,
+}
+
 // Game mode constants
 pub mod game_modes {
     pub const SINGLE_PLAYER: i32 = 0;
@@ -139,6 +251,12 @@ pub mod obstacle_types {
     pub const CLOCK: i32 = 5;
 }
 
+// Asset styles selectable with ratatui_set_asset_style
+pub mod asset_styles {
+    pub const CLASSIC: i32 = 0;
+    pub const DETAILED: i32 = 1;
+}
+
 // Building type constants
 pub mod building_types {
     pub const GLASS: i32 = 1;
@@ -154,3 +272,85 @@ pub mod audio_types {
     pub const POWERUP: i32 = 3;
     pub const MUSIC: i32 = 4;
 }
+
+// Mouse event kinds
+pub mod mouse_kinds {
+    pub const DOWN: i32 = 0;
+    pub const UP: i32 = 1;
+    pub const DRAG: i32 = 2;
+    pub const SCROLL_UP: i32 = 3;
+    pub const SCROLL_DOWN: i32 = 4;
+}
+
+// Mouse buttons
+pub mod mouse_buttons {
+    pub const NONE: i32 = 0;
+    pub const LEFT: i32 = 1;
+    pub const RIGHT: i32 = 2;
+    pub const MIDDLE: i32 = 3;
+}
+
+// What a mouse event landed on
+pub mod hit_targets {
+    pub const NONE: i32 = 0;
+    pub const MENU_ITEM: i32 = 1;
+    pub const REPLAY_TIMELINE: i32 = 2;
+}
+
+// Input event kinds
+pub mod event_kinds {
+    pub const KEY_DOWN: i32 = 0;
+    pub const KEY_UP: i32 = 1;
+    pub const RESIZE: i32 = 2;
+    pub const FOCUS_GAINED: i32 = 3;
+    pub const FOCUS_LOST: i32 = 4;
+    pub const MOUSE: i32 = 5;
+    pub const PASTE: i32 = 6;
+}
+
+// Key codes of key events
+pub mod key_codes {
+    pub const CHAR: i32 = 0;
+    pub const ENTER: i32 = 1;
+    pub const ESC: i32 = 2;
+    pub const BACKSPACE: i32 = 3;
+    pub const TAB: i32 = 4;
+    pub const LEFT: i32 = 5;
+    pub const RIGHT: i32 = 6;
+    pub const UP: i32 = 7;
+    pub const DOWN: i32 = 8;
+    pub const HOME: i32 = 9;
+    pub const END: i32 = 10;
+    pub const PAGE_UP: i32 = 11;
+    pub const PAGE_DOWN: i32 = 12;
+    pub const INSERT: i32 = 13;
+    pub const DELETE: i32 = 14;
+    pub const FUNCTION: i32 = 15;
+    pub const OTHER: i32 = 16;
+}
+
+// Modifier bits of key events
+pub mod key_modifiers {
+    pub const SHIFT: i32 = 1;
+    pub const CONTROL: i32 = 2;
+    pub const ALT: i32 = 4;
+}
+
+// Effect quality set with ratatui_set_quality; each level below HIGH drops
+// one more effect: rain, then particles, then building windows
+pub mod quality_levels {
+    pub const HIGH: i32 = 0;
+    pub const MEDIUM: i32 = 1;
+    pub const LOW: i32 = 2;
+    pub const MINIMAL: i32 = 3;
+}
+
+// Colour depth of a renderer, from RendererConfig or ratatui_set_color_mode;
+// AUTO detects it from COLORTERM and TERM, and RGB colours are mapped to
+// the nearest palette entry below TRUECOLOR
+pub mod color_modes {
+    pub const AUTO: i32 = 0;
+    pub const TRUECOLOR: i32 = 1;
+    pub const ANSI_256: i32 = 2;
+    pub const ANSI_16: i32 = 3;
+}
@@ -1,8 +1,7 @@
-/// Utility functions for colors, styling, and game data lookups
-/// Provides helper functions for rendering and game logic
+//! Utility functions for colors, styling, and game data lookups
+//! Provides helper functions for rendering and game logic
 
 use ratatui::style::{Color, Style};
-use super::types::{track_types, weather};
 
 /// Get color based on combo multiplier
 pub fn get_combo_color(combo: i32) -> Color {
@@ -72,8 +71,12 @@ pub fn get_track_style(track_type: i32, weather_type: i32, darkness: f32) -> Sty
 }
 
 /// Car design data structure
+/// `art` is the full-size sprite (7 columns); `medium` (5 columns) and
+/// `small` (3 columns) are drawn for cars further down the road
 pub struct CarDesign {
     pub art: [&'static str; 4],
+    pub medium: [&'static str; 2],
+    pub small: &'static str,
     pub color: Color,
     pub label: &'static str,
 }
@@ -88,6 +91,8 @@ pub fn get_car_design(car_type: i32, is_boss: bool) -> CarDesign {
                 "▐██▌██▌",
                 " BOSS! ",
             ],
+            medium: ["▄███▄", "▐█▌█▌"],
+            small: "▄█▄",
             color: Color::Red,
             label: "BOSS!",
         };
@@ -102,6 +107,8 @@ pub fn get_car_design(car_type: i32, is_boss: bool) -> CarDesign {
                 " ▐█▌█▌ ",
                 "  YOU  ",
             ],
+            medium: [" ▄█▄ ", "▐█▌█▌"],
+            small: "▄█▄",
             color: Color::Green,
             label: "YOU",
         },
@@ -113,6 +120,8 @@ pub fn get_car_design(car_type: i32, is_boss: bool) -> CarDesign {
                 " ▐█▌█▌ ",
                 "  🚔  ",
             ],
+            medium: [" ▄█▄ ", "▐🚨█▌"],
+            small: "▄█▄",
             color: Color::Blue,
             label: "POL",
         },
@@ -124,6 +133,8 @@ pub fn get_car_design(car_type: i32, is_boss: bool) -> CarDesign {
                 " ▐██▌▌ ",
                 "  🏁  ",
             ],
+            medium: [" ▀█▀ ", "▐██▌▌"],
+            small: "▀█▀",
             color: Color::Magenta,
             label: "RCR",
         },
@@ -135,6 +146,8 @@ pub fn get_car_design(car_type: i32, is_boss: bool) -> CarDesign {
                 "▐██▌██▌",
                 " TRUCK ",
             ],
+            medium: ["▄███▄", "▐█▌█▌"],
+            small: "███",
             color: Color::Yellow,
             label: "TRK",
         },
@@ -146,6 +159,8 @@ pub fn get_car_design(car_type: i32, is_boss: bool) -> CarDesign {
                 " ▐█▌█▌ ",
                 " TAXI ",
             ],
+            medium: [" ▄█▄ ", "▐▓▓█▌"],
+            small: "▄▓▄",
             color: Color::Yellow,
             label: "TXI",
         },
@@ -157,6 +172,8 @@ pub fn get_car_design(car_type: i32, is_boss: bool) -> CarDesign {
                 "▐█▌▌█▌ ",
                 "  VAN  ",
             ],
+            medium: ["▄███▄", "▐█▌█▌"],
+            small: "███",
             color: Color::Rgb(150, 150, 150),
             label: "VAN",
         },
@@ -168,6 +185,8 @@ pub fn get_car_design(car_type: i32, is_boss: bool) -> CarDesign {
                 " ▐██▌▌ ",
                 " MSCL ",
             ],
+            medium: [" ▄█▄ ", "▐▓█▓▌"],
+            small: "▄█▄",
             color: Color::Red,
             label: "MSC",
         },
@@ -179,6 +198,8 @@ pub fn get_car_design(car_type: i32, is_boss: bool) -> CarDesign {
                 " ▐█▌█▌ ",
                 " CONV ",
             ],
+            medium: [" ─█─ ", "▐▒▒█▌"],
+            small: "─█─",
             color: Color::Cyan,
             label: "CNV",
         },
@@ -190,6 +211,8 @@ pub fn get_car_design(car_type: i32, is_boss: bool) -> CarDesign {
                 "▐█▌▌▌█▌",
                 " LIMO! ",
             ],
+            medium: ["▄███▄", "▐█▌▌▌"],
+            small: "▄█▄",
             color: Color::Black,
             label: "LMO",
         },
@@ -201,25 +224,14 @@ pub fn get_car_design(car_type: i32, is_boss: bool) -> CarDesign {
                 " ▐█▌█▌ ",
                 "  CAR  ",
             ],
+            medium: [" ▄█▄ ", "▐█▌█▌"],
+            small: "▄█▄",
             color: Color::Gray,
             label: "CAR",
         },
     }
 }
 
-/// Get powerup icon and color
-pub fn get_powerup_icon(ptype: i32) -> (&'static str, Color) {
-    match ptype {
-        0 => ("🚧", Color::Yellow),
-        1 => ("💧", Color::Blue),
-        2 => ("⚡", Color::Magenta),
-        3 => ("⭐", Color::Yellow),
-        4 => ("🧲", Color::Red),
-        5 => ("🕐", Color::Cyan),
-        _ => ("⚠", Color::Red),
-    }
-}
-
 /// Get building character set and color
 pub fn get_building_style(btype: i32) -> (&'static str, Color) {
     match btype {
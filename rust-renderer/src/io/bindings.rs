@@ -0,0 +1,406 @@
+//! Key binding table mapping keys to `InputState` fields
+//! Loaded from TOML or JSON, checked for conflicts, and reported back to the
+//! host and the HUD control hints
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::sync::{Arc, LazyLock, PoisonError, RwLock};
+use crate::core::error::{Error, ErrorCode, Result};
+use crate::core::types::InputState;
+
+/// Something a key can do, one per `InputState` field
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Action {
+    P1Left,
+    P1Right,
+    P1Accel,
+    P1Brake,
+    P1Boost,
+    P2Left,
+    P2Right,
+    P2Accel,
+    P2Brake,
+    P2Boost,
+    Quit,
+    Pause,
+    Menu,
+    MenuUp,
+    MenuDown,
+    MenuLeft,
+    MenuRight,
+    MenuConfirm,
+    MenuBack,
+    MenuPageUp,
+    MenuPageDown,
+    MenuErase,
+}
+
+impl Action {
+    /// Every action, in `InputState` field order
+    pub const ALL: [Action; 22] = [
+        Action::P1Left, Action::P1Right, Action::P1Accel, Action::P1Brake, Action::P1Boost,
+        Action::P2Left, Action::P2Right, Action::P2Accel, Action::P2Brake, Action::P2Boost,
+        Action::Quit, Action::Pause, Action::Menu,
+        Action::MenuUp, Action::MenuDown, Action::MenuLeft, Action::MenuRight,
+        Action::MenuConfirm, Action::MenuBack, Action::MenuPageUp, Action::MenuPageDown,
+        Action::MenuErase,
+    ];
+
+    /// Name of the `InputState` field this action sets
+    pub fn name(self) -> &'static str {
+        match self {
+            Action::P1Left => "p1_left",
+            Action::P1Right => "p1_right",
+            Action::P1Accel => "p1_accel",
+            Action::P1Brake => "p1_brake",
+            Action::P1Boost => "p1_boost",
+            Action::P2Left => "p2_left",
+            Action::P2Right => "p2_right",
+            Action::P2Accel => "p2_accel",
+            Action::P2Brake => "p2_brake",
+            Action::P2Boost => "p2_boost",
+            Action::Quit => "quit",
+            Action::Pause => "pause",
+            Action::Menu => "menu",
+            Action::MenuUp => "menu_up",
+            Action::MenuDown => "menu_down",
+            Action::MenuLeft => "menu_left",
+            Action::MenuRight => "menu_right",
+            Action::MenuConfirm => "menu_confirm",
+            Action::MenuBack => "menu_back",
+            Action::MenuPageUp => "menu_page_up",
+            Action::MenuPageDown => "menu_page_down",
+            Action::MenuErase => "menu_erase",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Action> {
+        Action::ALL.into_iter().find(|a| a.name() == name)
+    }
+
+    /// Driving actions stay active while their key is held; system and menu
+    /// actions fire once per key press
+    pub fn is_held(self) -> bool {
+        (self as usize) < (Action::Quit as usize)
+    }
+
+    /// Menu navigation also fires on auto-repeat, so a held arrow scrolls
+    pub fn repeats(self) -> bool {
+        matches!(
+            self,
+            Action::MenuUp
+                | Action::MenuDown
+                | Action::MenuLeft
+                | Action::MenuRight
+                | Action::MenuPageUp
+                | Action::MenuPageDown
+                | Action::MenuErase
+        )
+    }
+
+    /// Menu actions are a separate channel: a key may drive a gameplay action
+    /// and a menu action at once, but only one of each
+    pub fn is_menu(self) -> bool {
+        (self as usize) >= (Action::MenuUp as usize)
+    }
+
+    pub(crate) fn apply(self, input_state: &mut InputState) {
+        let flag = match self {
+            Action::P1Left => &mut input_state.p1_left,
+            Action::P1Right => &mut input_state.p1_right,
+            Action::P1Accel => &mut input_state.p1_accel,
+            Action::P1Brake => &mut input_state.p1_brake,
+            Action::P1Boost => &mut input_state.p1_boost,
+            Action::P2Left => &mut input_state.p2_left,
+            Action::P2Right => &mut input_state.p2_right,
+            Action::P2Accel => &mut input_state.p2_accel,
+            Action::P2Brake => &mut input_state.p2_brake,
+            Action::P2Boost => &mut input_state.p2_boost,
+            Action::Quit => &mut input_state.quit,
+            Action::Pause => &mut input_state.pause,
+            Action::Menu => &mut input_state.menu,
+            Action::MenuUp => &mut input_state.menu_up,
+            Action::MenuDown => &mut input_state.menu_down,
+            Action::MenuLeft => &mut input_state.menu_left,
+            Action::MenuRight => &mut input_state.menu_right,
+            Action::MenuConfirm => &mut input_state.menu_confirm,
+            Action::MenuBack => &mut input_state.menu_back,
+            Action::MenuPageUp => &mut input_state.menu_page_up,
+            Action::MenuPageDown => &mut input_state.menu_page_down,
+            Action::MenuErase => &mut input_state.menu_erase,
+        };
+        *flag = true;
+    }
+}
+
+/// Modifiers that distinguish bindings; Shift only counts for non-letter keys
+const BINDING_MODIFIERS: KeyModifiers = KeyModifiers::CONTROL.union(KeyModifiers::ALT);
+
+/// One key combination, e.g. `Ctrl+Left` or `w`
+/// Letters match in either case.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct KeyBinding {
+    pub code: KeyCode,
+    pub modifiers: KeyModifiers,
+}
+
+impl KeyBinding {
+    pub fn new(code: KeyCode, modifiers: KeyModifiers) -> Self {
+        let (code, mask) = match code {
+            KeyCode::Char(c) => (KeyCode::Char(c.to_ascii_lowercase()), BINDING_MODIFIERS),
+            other => (other, BINDING_MODIFIERS | KeyModifiers::SHIFT),
+        };
+        KeyBinding { code, modifiers: modifiers & mask }
+    }
+
+    /// Binding a key event matches
+    pub fn from_event(key: &KeyEvent) -> Self {
+        KeyBinding::new(key.code, key.modifiers)
+    }
+
+    /// Parse a key spec such as `a`, `Space`, `Esc`, `F5` or `Ctrl+Up`
+    pub fn parse(spec: &str) -> Result<Self> {
+        let invalid = || Error::new(ErrorCode::InvalidBindings, format!("unknown key `{}`", spec));
+        let mut parts: Vec<&str> = spec.split('+').collect();
+        // The key itself is `+` only on its own or after a modifier, as in
+        // `Ctrl++`; `Ctrl+` names no key at all
+        if spec == "+" || spec.ends_with("++") {
+            parts.truncate(parts.len() - 2);
+            parts.push("+");
+        }
+        let (key, mods) = parts.split_last().ok_or_else(invalid)?;
+        if key.is_empty() {
+            return Err(Error::new(ErrorCode::InvalidBindings, format!("no key in `{}`", spec)));
+        }
+
+        let mut modifiers = KeyModifiers::NONE;
+        for m in mods {
+            modifiers |= match m.to_ascii_lowercase().as_str() {
+                "ctrl" | "control" => KeyModifiers::CONTROL,
+                "alt" => KeyModifiers::ALT,
+                "shift" => KeyModifiers::SHIFT,
+                _ => return Err(invalid()),
+            };
+        }
+
+        let mut chars = key.chars();
+        let code = match (chars.next(), chars.next()) {
+            (Some(c), None) => KeyCode::Char(c),
+            _ => match key.to_ascii_lowercase().as_str() {
+                "space" => KeyCode::Char(' '),
+                "left" => KeyCode::Left,
+                "right" => KeyCode::Right,
+                "up" => KeyCode::Up,
+                "down" => KeyCode::Down,
+                "esc" | "escape" => KeyCode::Esc,
+                "enter" | "return" => KeyCode::Enter,
+                "tab" => KeyCode::Tab,
+                "backspace" => KeyCode::Backspace,
+                "home" => KeyCode::Home,
+                "end" => KeyCode::End,
+                "pageup" => KeyCode::PageUp,
+                "pagedown" => KeyCode::PageDown,
+                "insert" => KeyCode::Insert,
+                "delete" => KeyCode::Delete,
+                f if f.starts_with('f') => KeyCode::F(f[1..].parse().map_err(|_| invalid())?),
+                _ => return Err(invalid()),
+            },
+        };
+        Ok(KeyBinding::new(code, modifiers))
+    }
+
+    /// Key spec that `parse` reads back
+    pub fn spec(&self) -> String {
+        format!("{}{}", self.modifier_prefix(), match self.code {
+            KeyCode::Char(' ') => "Space".to_string(),
+            KeyCode::Char(c) => c.to_string(),
+            KeyCode::F(n) => format!("F{}", n),
+            KeyCode::PageUp => "PageUp".to_string(),
+            KeyCode::PageDown => "PageDown".to_string(),
+            other => format!("{:?}", other),
+        })
+    }
+
+    /// Short label for on-screen control hints
+    pub fn label(&self) -> String {
+        format!("{}{}", self.modifier_prefix(), match self.code {
+            KeyCode::Left => "←".to_string(),
+            KeyCode::Right => "→".to_string(),
+            KeyCode::Up => "↑".to_string(),
+            KeyCode::Down => "↓".to_string(),
+            KeyCode::Char(' ') => "SPACE".to_string(),
+            KeyCode::Char(c) => c.to_ascii_uppercase().to_string(),
+            KeyCode::Esc => "ESC".to_string(),
+            _ => self.spec().rsplit('+').next().unwrap_or_default().to_ascii_uppercase(),
+        })
+    }
+
+    fn modifier_prefix(&self) -> String {
+        let mut prefix = String::new();
+        for (flag, name) in [(KeyModifiers::CONTROL, "Ctrl+"), (KeyModifiers::ALT, "Alt+"), (KeyModifiers::SHIFT, "Shift+")] {
+            if self.modifiers.contains(flag) {
+                prefix.push_str(name);
+            }
+        }
+        prefix
+    }
+}
+
+/// Keys bound to every action
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Bindings {
+    keys: Vec<(Action, Vec<KeyBinding>)>,
+}
+
+impl Default for Bindings {
+    /// WASD/arrows + Space for player 1, IJKL + U for player 2, Q/Esc, P and M;
+    /// menus use the arrows, Enter, Esc, PageUp/PageDown and Backspace
+    fn default() -> Self {
+        let key = |spec| KeyBinding::parse(spec).expect("default key spec");
+        let table = [
+            (Action::P1Left, &["Left", "a"][..]),
+            (Action::P1Right, &["Right", "d"]),
+            (Action::P1Accel, &["Up", "w"]),
+            (Action::P1Brake, &["Down", "s"]),
+            (Action::P1Boost, &["Space"]),
+            (Action::P2Left, &["j"]),
+            (Action::P2Right, &["l"]),
+            (Action::P2Accel, &["i"]),
+            (Action::P2Brake, &["k"]),
+            (Action::P2Boost, &["u"]),
+            (Action::Quit, &["q", "Esc"]),
+            (Action::Pause, &["p"]),
+            (Action::Menu, &["m"]),
+            (Action::MenuUp, &["Up"]),
+            (Action::MenuDown, &["Down"]),
+            (Action::MenuLeft, &["Left"]),
+            (Action::MenuRight, &["Right"]),
+            (Action::MenuConfirm, &["Enter"]),
+            (Action::MenuBack, &["Esc"]),
+            (Action::MenuPageUp, &["PageUp"]),
+            (Action::MenuPageDown, &["PageDown"]),
+            (Action::MenuErase, &["Backspace"]),
+        ];
+        Bindings { keys: table.iter().map(|(a, specs)| (*a, specs.iter().map(|s| key(s)).collect())).collect() }
+    }
+}
+
+impl Bindings {
+    /// Parse a binding table from JSON (an object) or TOML
+    /// Maps `InputState` field names to lists of key specs; fields left out
+    /// keep their default keys and an empty list unbinds a field.
+    pub fn parse(text: &str) -> Result<Self> {
+        let invalid = |e: &dyn std::fmt::Display| Error::new(ErrorCode::InvalidBindings, format!("invalid bindings: {}", e));
+        let table: BTreeMap<String, Vec<String>> = if text.trim_start().starts_with('{') {
+            serde_json::from_str(text).map_err(|e| invalid(&e))?
+        } else {
+            toml::from_str(text).map_err(|e| invalid(&e))?
+        };
+
+        let mut bindings = Bindings::default();
+        for (name, specs) in table {
+            let action = Action::from_name(&name)
+                .ok_or_else(|| invalid(&format!("unknown input `{}`", name)))?;
+            let keys = specs.iter().map(|s| KeyBinding::parse(s)).collect::<Result<Vec<_>>>()?;
+            bindings.set(action, keys);
+        }
+
+        bindings.check_conflicts()?;
+        Ok(bindings)
+    }
+
+    /// Load a binding file (`.toml` or `.json`)
+    pub fn load(path: &Path) -> Result<Self> {
+        let text = std::fs::read_to_string(path)
+            .map_err(|e| Error::io(&format!("failed to read {}", path.display()), e))?;
+        Bindings::parse(&text)
+    }
+
+    /// Replace the keys of one action
+    pub fn set(&mut self, action: Action, keys: Vec<KeyBinding>) {
+        if let Some(entry) = self.keys.iter_mut().find(|(a, _)| *a == action) {
+            entry.1 = keys;
+        }
+    }
+
+    /// Keys bound to `action`
+    pub fn keys(&self, action: Action) -> &[KeyBinding] {
+        self.keys.iter().find(|(a, _)| *a == action).map_or(&[], |(_, k)| k)
+    }
+
+    /// Actions bound to a key event: at most one gameplay and one menu action
+    pub fn actions_for(&self, key: &KeyEvent) -> Vec<Action> {
+        let binding = KeyBinding::from_event(key);
+        self.keys.iter().filter(|(_, keys)| keys.contains(&binding)).map(|(a, _)| *a).collect()
+    }
+
+    /// First action bound to a key event, preferring gameplay over menus
+    pub fn action_for(&self, key: &KeyEvent) -> Option<Action> {
+        self.actions_for(key).first().copied()
+    }
+
+    /// Fail if any key is bound to more than one action of the same channel
+    pub fn check_conflicts(&self) -> Result<()> {
+        for (i, (action, keys)) in self.keys.iter().enumerate() {
+            for key in keys {
+                let mut later = self.keys[i + 1..].iter().filter(|(a, _)| a.is_menu() == action.is_menu());
+                if let Some((other, _)) = later.find(|(_, k)| k.contains(key)) {
+                    return Err(Error::new(
+                        ErrorCode::BindingConflict,
+                        format!("key `{}` is bound to both {} and {}", key.spec(), action.name(), other.name()),
+                    ));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// The full table as JSON, in `InputState` field order
+    pub fn to_json(&self) -> String {
+        let fields: Vec<String> = self
+            .keys
+            .iter()
+            .map(|(action, keys)| {
+                let specs: Vec<String> = keys.iter().map(KeyBinding::spec).collect();
+                format!("  \"{}\": {}", action.name(), serde_json::to_string(&specs).unwrap_or_default())
+            })
+            .collect();
+        format!("{{\n{}\n}}", fields.join(",\n"))
+    }
+
+    /// Hint label of the first key bound to `action`
+    pub fn label(&self, action: Action) -> String {
+        self.keys(action).first().map_or_else(|| "-".to_string(), KeyBinding::label)
+    }
+
+    /// Driving controls of one player, e.g. `← → Move | ↑ Accel | ↓ Brake | SPACE Boost`
+    pub fn driving_hints(&self, player: i32) -> String {
+        let [left, right, accel, brake, boost] = if player == 2 {
+            [Action::P2Left, Action::P2Right, Action::P2Accel, Action::P2Brake, Action::P2Boost]
+        } else {
+            [Action::P1Left, Action::P1Right, Action::P1Accel, Action::P1Brake, Action::P1Boost]
+        };
+        format!(
+            "{} {} Move | {} Accel | {} Brake | {} Boost",
+            self.label(left),
+            self.label(right),
+            self.label(accel),
+            self.label(brake),
+            self.label(boost)
+        )
+    }
+}
+
+/// Bindings used for terminal input and the HUD
+static ACTIVE: LazyLock<RwLock<Arc<Bindings>>> = LazyLock::new(|| RwLock::new(Arc::new(Bindings::default())));
+
+/// Currently active bindings
+pub fn active() -> Arc<Bindings> {
+    ACTIVE.read().unwrap_or_else(PoisonError::into_inner).clone()
+}
+
+/// Make `bindings` the active table
+pub fn set_active(bindings: Bindings) {
+    *ACTIVE.write().unwrap_or_else(PoisonError::into_inner) = Arc::new(bindings);
+}
@@ -0,0 +1,147 @@
+//! Event queue module - the tagged input stream behind `ratatui_next_event`
+//! Keeps what `InputState` cannot express: key releases, resizes, focus
+//! changes and pastes
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers, MouseEvent as TermMouseEvent};
+use std::collections::VecDeque;
+use std::sync::{Mutex, PoisonError};
+use crate::core::types::{event_kinds, key_codes, key_modifiers, Event};
+use crate::rendering::hit::HitMap;
+use super::mouse::{self, MouseTracker};
+
+/// Events kept for a host that is not reading them; older ones are dropped
+pub const QUEUE_CAPACITY: usize = 256;
+
+/// Event as read from the terminal, converted when the host takes it
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Queued {
+    KeyDown { key: KeyEvent, repeat: bool },
+    KeyUp(KeyEvent),
+    Resize(u16, u16),
+    Focus(bool),
+    Mouse(TermMouseEvent),
+    Paste(String),
+}
+
+/// Pending events plus the text of the last paste handed out
+pub struct EventQueue {
+    queue: VecDeque<Queued>,
+    mouse: MouseTracker,
+    text: String,
+}
+
+impl Default for EventQueue {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl EventQueue {
+    pub const fn new() -> Self {
+        EventQueue { queue: VecDeque::new(), mouse: MouseTracker::new(), text: String::new() }
+    }
+
+    /// Queue one event; unreported mouse movement is ignored
+    pub fn push(&mut self, event: Queued) {
+        if let Queued::Mouse(m) = &event
+            && !mouse::is_reported(m)
+        {
+            return;
+        }
+        if self.queue.len() == QUEUE_CAPACITY {
+            self.queue.pop_front();
+        }
+        self.queue.push_back(event);
+    }
+
+    /// Next event, with mouse events hit-tested against `hits`
+    pub fn next(&mut self, hits: &HitMap) -> Option<Event> {
+        let event = match self.queue.pop_front()? {
+            Queued::KeyDown { key, repeat } => Event { kind: event_kinds::KEY_DOWN, repeat, ..key_event(&key) },
+            Queued::KeyUp(key) => Event { kind: event_kinds::KEY_UP, ..key_event(&key) },
+            Queued::Resize(width, height) => Event { kind: event_kinds::RESIZE, width, height, ..Default::default() },
+            Queued::Focus(gained) => Event {
+                kind: if gained { event_kinds::FOCUS_GAINED } else { event_kinds::FOCUS_LOST },
+                ..Default::default()
+            },
+            Queued::Mouse(m) => Event { kind: event_kinds::MOUSE, mouse: self.mouse.translate(m, hits), ..Default::default() },
+            Queued::Paste(text) => {
+                let text_len = text.len() as u32;
+                self.text = text;
+                Event { kind: event_kinds::PASTE, text_len, ..Default::default() }
+            },
+        };
+        Some(event)
+    }
+
+    /// Text of the last paste returned by `next`
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    /// Drop pending events
+    pub fn clear(&mut self) {
+        self.queue.clear();
+        self.mouse.clear();
+    }
+}
+
+/// Key fields of an event
+fn key_event(key: &KeyEvent) -> Event {
+    let (key_code, key_char) = match key.code {
+        KeyCode::Char(c) => (key_codes::CHAR, c as u32),
+        KeyCode::Enter => (key_codes::ENTER, 0),
+        KeyCode::Esc => (key_codes::ESC, 0),
+        KeyCode::Backspace => (key_codes::BACKSPACE, 0),
+        KeyCode::Tab | KeyCode::BackTab => (key_codes::TAB, 0),
+        KeyCode::Left => (key_codes::LEFT, 0),
+        KeyCode::Right => (key_codes::RIGHT, 0),
+        KeyCode::Up => (key_codes::UP, 0),
+        KeyCode::Down => (key_codes::DOWN, 0),
+        KeyCode::Home => (key_codes::HOME, 0),
+        KeyCode::End => (key_codes::END, 0),
+        KeyCode::PageUp => (key_codes::PAGE_UP, 0),
+        KeyCode::PageDown => (key_codes::PAGE_DOWN, 0),
+        KeyCode::Insert => (key_codes::INSERT, 0),
+        KeyCode::Delete => (key_codes::DELETE, 0),
+        KeyCode::F(n) => (key_codes::FUNCTION, n as u32),
+        _ => (key_codes::OTHER, 0),
+    };
+
+    let mut modifiers = 0;
+    // BackTab is how terminals report Shift+Tab
+    if key.modifiers.contains(KeyModifiers::SHIFT) || key.code == KeyCode::BackTab {
+        modifiers |= key_modifiers::SHIFT;
+    }
+    if key.modifiers.contains(KeyModifiers::CONTROL) {
+        modifiers |= key_modifiers::CONTROL;
+    }
+    if key.modifiers.contains(KeyModifiers::ALT) {
+        modifiers |= key_modifiers::ALT;
+    }
+
+    Event { key_code, key_char, modifiers, ..Default::default() }
+}
+
+/// Events of the real terminal, filled as input is read
+static EVENTS: Mutex<EventQueue> = Mutex::new(EventQueue::new());
+
+/// Queue an event read from the real terminal
+pub fn push(event: Queued) {
+    EVENTS.lock().unwrap_or_else(PoisonError::into_inner).push(event);
+}
+
+/// Next event of the real terminal
+pub fn next(hits: &HitMap) -> Option<Event> {
+    EVENTS.lock().unwrap_or_else(PoisonError::into_inner).next(hits)
+}
+
+/// Text of the last paste returned by `next`
+pub fn text() -> String {
+    EVENTS.lock().unwrap_or_else(PoisonError::into_inner).text().to_string()
+}
+
+/// Forget events of the real terminal
+pub fn clear() {
+    EVENTS.lock().unwrap_or_else(PoisonError::into_inner).clear();
+}
@@ -0,0 +1,63 @@
+//! Panic guard for the C FFI boundary
+//! Keeps Rust panics from unwinding into the host and restores the terminal
+
+use std::any::Any;
+use std::panic::{self, AssertUnwindSafe};
+use crate::core::error::{set_last_error, Error, ErrorCode, Result};
+use super::renderer::{self, Renderer};
+use super::terminal;
+
+/// Run an FFI entry point, returning `fallback` if it fails or panics
+/// Failures are recorded for `ratatui_last_error`. On panic the real terminal
+/// is restored (leaving raw mode and the alternate screen) and the global
+/// renderer is dropped before the panic message is recorded.
+pub fn ffi_guard<T>(fallback: T, f: impl FnOnce() -> Result<T>) -> T {
+    guard(fallback, f, || {
+        terminal::release_tty();
+        renderer::cleanup_global();
+    })
+}
+
+/// Run an FFI entry point on one renderer handle, returning `fallback` if it
+/// fails or panics
+/// A null handle fails with `NullPointer`. On panic only this renderer is
+/// torn down: it is poisoned, and gives the terminal back if it owns it. The
+/// global renderer and other handles keep running.
+pub fn handle_guard<T>(renderer: Option<&Renderer>, fallback: T, f: impl FnOnce(&Renderer) -> Result<T>) -> T {
+    let Some(r) = renderer else {
+        set_last_error(Error::null("renderer"));
+        return fallback;
+    };
+    guard(fallback, || r.check_live().and_then(|_| f(r)), || r.poison())
+}
+
+/// Run an FFI entry point that creates or destroys a renderer handle
+/// Nothing else is torn down on panic: a renderer half created or destroyed
+/// drops its claim on the terminal, and so restores it, while unwinding.
+pub fn lifecycle_guard<T>(fallback: T, f: impl FnOnce() -> Result<T>) -> T {
+    guard(fallback, f, || {})
+}
+
+fn guard<T>(fallback: T, f: impl FnOnce() -> Result<T>, teardown: impl FnOnce()) -> T {
+    let error = match panic::catch_unwind(AssertUnwindSafe(f)) {
+        Ok(Ok(value)) => return value,
+        Ok(Err(error)) => error,
+        Err(payload) => {
+            teardown();
+            Error::new(ErrorCode::Panic, format!("rust-renderer panicked: {}", panic_message(payload.as_ref())))
+        }
+    };
+    set_last_error(error);
+    fallback
+}
+
+/// Extract the message from a panic payload
+pub fn panic_message(payload: &(dyn Any + Send)) -> &str {
+    if let Some(s) = payload.downcast_ref::<&str>() {
+        s
+    } else if let Some(s) = payload.downcast_ref::<String>() {
+        s
+    } else {
+        "unknown panic"
+    }
+}
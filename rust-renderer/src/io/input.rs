@@ -1,67 +1,282 @@
-/// Input handling module for keyboard and system events
-/// Manages player input polling and state management
+//! Input handling module for keyboard, mouse and system events
+//! Tracks held keys across polls so steering, throttle and both players can be
+//! active at the same time
 
-use crossterm::event::{self, Event, KeyCode};
-use std::time::Duration;
-use crate::core::types::InputState;
+use crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use std::sync::{Arc, LazyLock, Mutex, PoisonError};
+use std::time::{Duration, Instant};
+use crate::core::error::{Error, Result};
+use crate::core::types::{self, InputState};
+use crate::rendering::debug;
+use crate::rendering::hit::HitMap;
+use super::bindings::{self, Action, Bindings, KeyBinding};
+use super::events::{self, Queued};
+use super::{mouse, terminal};
 
-/// Poll for keyboard input and update input state
-/// Handles both single and multiplayer input schemes
-pub fn poll_input(input: *mut InputState) -> bool {
-    if input.is_null() {
-        return false;
+/// Without release events, a key first pressed counts as held this long;
+/// covers the usual auto-repeat delay before the first repeat arrives
+pub const FIRST_REPEAT_TIMEOUT: Duration = Duration::from_millis(550);
+
+/// Without release events, a repeating key counts as released once no
+/// repeat has arrived for this long
+pub const REPEAT_TIMEOUT: Duration = Duration::from_millis(100);
+
+/// Longest `poll_input` waits for the first event while the host does not
+/// pace frames; hosts that do wait in `wait` instead
+pub const UNPACED_POLL_WAIT: Duration = Duration::from_millis(16);
+
+/// Key that turns the debug overlay on and off, in every renderer
+/// Still reported like any other key.
+pub const DEBUG_OVERLAY_KEY: KeyCode = KeyCode::F(3);
+
+/// Key currently considered held
+struct HeldKey {
+    key: KeyBinding,
+    actions: Vec<Action>,
+    last_seen: Instant,
+    repeating: bool,
+}
+
+/// Held-key state carried between polls
+/// With release events (kitty keyboard protocol) a key is held from press to
+/// release. Otherwise releases are inferred from auto-repeat going quiet.
+pub struct KeyTracker {
+    bindings: Arc<Bindings>,
+    held: Vec<HeldKey>,
+    release_events: bool,
+    /// Keys dropped by the repeat timeout, not yet reported as released
+    expired: Vec<KeyBinding>,
+    /// Last `DEBUG_OVERLAY_KEY` press, without release events
+    overlay_seen: Option<Instant>,
+}
+
+impl Default for KeyTracker {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl KeyTracker {
+    /// Tracker using the default bindings
+    pub fn new() -> Self {
+        KeyTracker {
+            bindings: Arc::new(Bindings::default()),
+            held: Vec::new(),
+            release_events: false,
+            expired: Vec::new(),
+            overlay_seen: None,
+        }
+    }
+
+    /// Switch to another binding table
+    /// Forgets held keys when the table changes
+    pub fn set_bindings(&mut self, bindings: Arc<Bindings>) {
+        if !Arc::ptr_eq(&self.bindings, &bindings) {
+            self.bindings = bindings;
+            self.held.clear();
+        }
+    }
+
+    /// Switch between exact release events and the repeat-timeout heuristic
+    /// Forgets held keys when the mode changes
+    pub fn set_release_events(&mut self, enabled: bool) {
+        if self.release_events != enabled {
+            self.release_events = enabled;
+            self.held.clear();
+        }
+    }
+
+    /// Process one key event
+    /// Presses also mark their actions in `input_state`, so a key pressed and
+    /// released within a single poll still registers. Printable presses are
+    /// also collected as typed text. Returns whether a press repeats a key
+    /// that is already held.
+    pub fn handle(&mut self, key: KeyEvent, now: Instant, input_state: &mut InputState) -> bool {
+        let binding = KeyBinding::from_event(&key);
+        self.expire(now);
+
+        if key.kind != KeyEventKind::Release
+            && let KeyCode::Char(c) = key.code
+            && !key.modifiers.intersects(KeyModifiers::CONTROL | KeyModifiers::ALT)
+        {
+            input_state.push_char(c);
+        }
+
+        match key.kind {
+            // Released by key code alone, so a modifier let go first does not
+            // leave the key stuck
+            KeyEventKind::Release => {
+                self.held.retain(|k| k.key.code != binding.code);
+                false
+            },
+            KeyEventKind::Press | KeyEventKind::Repeat => {
+                let actions = self.bindings.actions_for(&key);
+                if actions.is_empty() {
+                    return key.kind == KeyEventKind::Repeat;
+                }
+                // System keys are tracked too, so their auto-repeat is ignored
+                let is_repeat = match self.held.iter_mut().find(|k| k.key == binding) {
+                    Some(k) => {
+                        k.last_seen = now;
+                        k.repeating = true;
+                        true
+                    },
+                    None => {
+                        self.held.push(HeldKey { key: binding, actions: actions.clone(), last_seen: now, repeating: false });
+                        key.kind == KeyEventKind::Repeat
+                    },
+                };
+
+                for action in actions {
+                    if action.is_held() || action.repeats() || !is_repeat {
+                        action.apply(input_state);
+                    }
+                }
+                is_repeat
+            },
+        }
+    }
+
+    /// Whether `key` toggles the debug overlay
+    /// With release events only true repeats are ignored. Otherwise a press
+    /// within `REPEAT_TIMEOUT` of the last one is auto-repeat, so quick taps
+    /// each toggle while a held key flips at most once more, when its first
+    /// repeat arrives after the terminal's repeat delay.
+    pub fn toggles_overlay(&mut self, key: &KeyEvent, now: Instant) -> bool {
+        if key.code != DEBUG_OVERLAY_KEY || key.kind != KeyEventKind::Press {
+            return false;
+        }
+        if self.release_events {
+            return true;
+        }
+        let last = self.overlay_seen.replace(now);
+        last.is_none_or(|last| now.duration_since(last) >= REPEAT_TIMEOUT)
+    }
+
+    /// Report the keys still held at `now`
+    pub fn apply(&mut self, now: Instant, input_state: &mut InputState) {
+        self.expire(now);
+        for action in self.held.iter().flat_map(|k| &k.actions) {
+            if action.is_held() {
+                action.apply(input_state);
+            }
+        }
+    }
+
+    /// Keys released by the repeat timeout since the last call
+    /// Only bound keys are tracked, and only without release events.
+    pub fn take_expired(&mut self) -> Vec<KeyBinding> {
+        std::mem::take(&mut self.expired)
     }
-    
-    let input_state = unsafe { &mut *input };
-    
-    // Reset all inputs
-    *input_state = InputState {
-        p1_left: false,
-        p1_right: false,
-        p1_accel: false,
-        p1_brake: false,
-        p1_boost: false,
-        p2_left: false,
-        p2_right: false,
-        p2_accel: false,
-        p2_brake: false,
-        p2_boost: false,
-        quit: false,
-        pause: false,
-        menu: false,
-    };
-    
-    // Poll with 16ms timeout (60 FPS)
-    if event::poll(Duration::from_millis(16)).unwrap_or(false) {
-        if let Ok(Event::Key(key)) = event::read() {
-            handle_key_event(key.code, input_state);
+
+    /// Without release events, forget keys whose auto-repeat has gone quiet
+    fn expire(&mut self, now: Instant) {
+        if self.release_events {
+            return;
         }
+        let (held, expired) = std::mem::take(&mut self.held).into_iter().partition(|k| {
+            let timeout = if k.repeating { REPEAT_TIMEOUT } else { FIRST_REPEAT_TIMEOUT };
+            now.duration_since(k.last_seen) < timeout
+        });
+        self.held = held;
+        self.expired.extend(expired.into_iter().map(|k: HeldKey| k.key));
     }
-    
-    true
 }
 
-/// Process individual key events and update input state
-fn handle_key_event(code: KeyCode, input_state: &mut InputState) {
-    match code {
-        // Player 1 (Arrows/WASD)
-        KeyCode::Left | KeyCode::Char('a') | KeyCode::Char('A') => input_state.p1_left = true,
-        KeyCode::Right | KeyCode::Char('d') | KeyCode::Char('D') => input_state.p1_right = true,
-        KeyCode::Up | KeyCode::Char('w') | KeyCode::Char('W') => input_state.p1_accel = true,
-        KeyCode::Down | KeyCode::Char('s') | KeyCode::Char('S') => input_state.p1_brake = true,
-        KeyCode::Char(' ') => input_state.p1_boost = true,
-        
-        // Player 2 (IJKL)
-        KeyCode::Char('j') | KeyCode::Char('J') => input_state.p2_left = true,
-        KeyCode::Char('l') | KeyCode::Char('L') => input_state.p2_right = true,
-        KeyCode::Char('i') | KeyCode::Char('I') => input_state.p2_accel = true,
-        KeyCode::Char('k') | KeyCode::Char('K') => input_state.p2_brake = true,
-        KeyCode::Char('u') | KeyCode::Char('U') => input_state.p2_boost = true,
-        
-        // System
-        KeyCode::Char('q') | KeyCode::Char('Q') | KeyCode::Esc => input_state.quit = true,
-        KeyCode::Char('p') | KeyCode::Char('P') => input_state.pause = true,
-        KeyCode::Char('m') | KeyCode::Char('M') => input_state.menu = true,
-        _ => {},
+/// Input of the real terminal carried between reads
+struct TerminalInput {
+    keys: KeyTracker,
+    /// Actions and text seen since the last `poll_input`, including events
+    /// read on behalf of `next_event`
+    pending: InputState,
+}
+
+impl TerminalInput {
+    /// Read every pending terminal event, waiting up to `timeout` for the first
+    /// Keys update the held-key state and `pending`; everything is also queued
+    /// for the event stream, and mouse events for `mouse::next`.
+    fn pump(&mut self, mut timeout: Duration) -> Result<()> {
+        self.keys.set_release_events(terminal::keyboard_enhanced());
+        self.keys.set_bindings(bindings::active());
+
+        while event::poll(timeout).map_err(|e| Error::io("failed to poll input", e))? {
+            match event::read().map_err(|e| Error::io("failed to read input", e))? {
+                Event::Key(key) => {
+                    let now = Instant::now();
+                    if self.keys.toggles_overlay(&key, now) {
+                        debug::toggle();
+                    }
+                    let repeat = self.keys.handle(key, now, &mut self.pending);
+                    self.queue_expired();
+                    events::push(match key.kind {
+                        KeyEventKind::Release => Queued::KeyUp(key),
+                        _ => Queued::KeyDown { key, repeat },
+                    });
+                },
+                Event::Mouse(m) => {
+                    mouse::push(m);
+                    events::push(Queued::Mouse(m));
+                },
+                Event::Resize(width, height) => {
+                    terminal::notify_resize();
+                    events::push(Queued::Resize(width, height));
+                },
+                Event::FocusGained => events::push(Queued::Focus(true)),
+                Event::FocusLost => events::push(Queued::Focus(false)),
+                Event::Paste(text) => {
+                    for c in text.chars() {
+                        self.pending.push_char(c);
+                    }
+                    events::push(Queued::Paste(text));
+                },
+            }
+            timeout = Duration::ZERO;
+        }
+        Ok(())
     }
+
+    /// Report keys dropped by the repeat timeout as released
+    fn queue_expired(&mut self) {
+        for key in self.keys.take_expired() {
+            events::push(Queued::KeyUp(KeyEvent::new_with_kind(key.code, key.modifiers, KeyEventKind::Release)));
+        }
+    }
+}
+
+/// Input of the real terminal
+static INPUT: LazyLock<Mutex<TerminalInput>> =
+    LazyLock::new(|| Mutex::new(TerminalInput { keys: KeyTracker::new(), pending: InputState::default() }));
+
+/// Poll for keyboard input and update input state
+/// Drains every pending event, waiting up to `wait` for the first, and
+/// reports everything pressed since the last poll plus the keys still held
+pub fn poll_input(input_state: &mut InputState, wait: Duration) -> Result<()> {
+    let mut input = INPUT.lock().unwrap_or_else(PoisonError::into_inner);
+    input.pump(wait)?;
+
+    *input_state = std::mem::take(&mut input.pending);
+    input.keys.apply(Instant::now(), input_state);
+    input.queue_expired();
+    Ok(())
+}
+
+/// Read terminal events as they arrive for `duration`
+/// Lets a paced host sleep until its next frame without delaying input: key
+/// timings stay accurate and everything read counts towards the next poll.
+pub fn wait(duration: Duration) -> Result<()> {
+    let deadline = Instant::now() + duration;
+    loop {
+        let now = Instant::now();
+        if now >= deadline {
+            return Ok(());
+        }
+        INPUT.lock().unwrap_or_else(PoisonError::into_inner).pump(deadline - now)?;
+    }
+}
+
+/// Next entry of the event stream, reading pending terminal events first
+/// Never waits; mouse events are hit-tested against `hits`
+pub fn next_event(hits: &HitMap) -> Result<Option<types::Event>> {
+    INPUT.lock().unwrap_or_else(PoisonError::into_inner).pump(Duration::ZERO)?;
+    Ok(events::next(hits))
 }
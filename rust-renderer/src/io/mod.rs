@@ -1,8 +1,15 @@
-/// I/O module - Input handling and terminal management
-/// Manages keyboard input, terminal initialization, and raw mode
+//! I/O module - Input handling and terminal management
+//! Manages keyboard input, terminal initialization, and raw mode
 
+pub mod bindings;
+pub mod events;
+pub mod guard;
 pub mod input;
+pub mod mouse;
+pub mod renderer;
+pub mod snapshot;
 pub mod terminal;
 
 pub use input::poll_input;
-pub use terminal::{init, cleanup, get_terminal};
+pub use renderer::Renderer;
+pub use terminal::RenderTarget;
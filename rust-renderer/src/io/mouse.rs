@@ -0,0 +1,113 @@
+//! Mouse input module - queues terminal mouse events for the host
+//! Events are hit-tested against the last drawn frame when they are read
+
+use crossterm::event::{MouseButton, MouseEvent as TermMouseEvent, MouseEventKind};
+use std::collections::VecDeque;
+use std::sync::{Mutex, PoisonError};
+use crate::core::types::{hit_targets, mouse_buttons, mouse_kinds, MouseEvent};
+use crate::rendering::hit::{HitMap, HitTarget};
+
+/// Events kept for a host that is not reading them; older ones are dropped
+pub const QUEUE_CAPACITY: usize = 64;
+
+/// Pending mouse events and drag state carried between reads
+pub struct MouseTracker {
+    queue: VecDeque<TermMouseEvent>,
+    dragging_timeline: bool,
+}
+
+impl Default for MouseTracker {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl MouseTracker {
+    pub const fn new() -> Self {
+        MouseTracker { queue: VecDeque::new(), dragging_timeline: false }
+    }
+
+    /// Queue one terminal event; plain pointer moves are ignored
+    pub fn push(&mut self, event: TermMouseEvent) {
+        if !is_reported(&event) {
+            return;
+        }
+        if self.queue.len() == QUEUE_CAPACITY {
+            self.queue.pop_front();
+        }
+        self.queue.push_back(event);
+    }
+
+    /// Next queued event, hit-tested against `hits`
+    /// A drag that starts on the replay timeline keeps seeking after it
+    /// leaves the bar, until the button is released.
+    pub fn next(&mut self, hits: &HitMap) -> Option<MouseEvent> {
+        let event = self.queue.pop_front()?;
+        Some(self.translate(event, hits))
+    }
+
+    /// Hit-test one terminal event, updating the drag state
+    pub fn translate(&mut self, event: TermMouseEvent, hits: &HitMap) -> MouseEvent {
+        let (kind, button) = match event.kind {
+            MouseEventKind::Down(b) => (mouse_kinds::DOWN, button_code(b)),
+            MouseEventKind::Up(b) => (mouse_kinds::UP, button_code(b)),
+            MouseEventKind::Drag(b) => (mouse_kinds::DRAG, button_code(b)),
+            MouseEventKind::ScrollUp => (mouse_kinds::SCROLL_UP, mouse_buttons::NONE),
+            _ => (mouse_kinds::SCROLL_DOWN, mouse_buttons::NONE),
+        };
+
+        let mut hit = hits.hit(event.column, event.row);
+        if self.dragging_timeline && kind != mouse_kinds::DOWN {
+            hit = hits.timeline_position(event.column).map(HitTarget::ReplayTimeline);
+        }
+        match kind {
+            mouse_kinds::DOWN => self.dragging_timeline = matches!(hit, Some(HitTarget::ReplayTimeline(_))),
+            mouse_kinds::UP => self.dragging_timeline = false,
+            _ => {},
+        }
+
+        let (target, item, position) = match hit {
+            Some(HitTarget::MenuItem(i)) => (hit_targets::MENU_ITEM, i as i32, 0.0),
+            Some(HitTarget::ReplayTimeline(p)) => (hit_targets::REPLAY_TIMELINE, -1, p),
+            None => (hit_targets::NONE, -1, 0.0),
+        };
+        MouseEvent { kind, button, column: event.column, row: event.row, target, item, position }
+    }
+
+    /// Drop pending events and any drag in progress
+    pub fn clear(&mut self) {
+        self.queue.clear();
+        self.dragging_timeline = false;
+    }
+}
+
+/// Pointer moves and horizontal scrolling are not passed to the host
+pub fn is_reported(event: &TermMouseEvent) -> bool {
+    !matches!(event.kind, MouseEventKind::Moved | MouseEventKind::ScrollLeft | MouseEventKind::ScrollRight)
+}
+
+fn button_code(button: MouseButton) -> i32 {
+    match button {
+        MouseButton::Left => mouse_buttons::LEFT,
+        MouseButton::Right => mouse_buttons::RIGHT,
+        MouseButton::Middle => mouse_buttons::MIDDLE,
+    }
+}
+
+/// Mouse events of the real terminal, filled by `input::poll_input`
+static MOUSE: Mutex<MouseTracker> = Mutex::new(MouseTracker::new());
+
+/// Queue a mouse event read from the real terminal
+pub fn push(event: TermMouseEvent) {
+    MOUSE.lock().unwrap_or_else(PoisonError::into_inner).push(event);
+}
+
+/// Next mouse event of the real terminal, hit-tested against `hits`
+pub fn next(hits: &HitMap) -> Option<MouseEvent> {
+    MOUSE.lock().unwrap_or_else(PoisonError::into_inner).next(hits)
+}
+
+/// Forget mouse events of the real terminal
+pub fn clear() {
+    MOUSE.lock().unwrap_or_else(PoisonError::into_inner).clear();
+}
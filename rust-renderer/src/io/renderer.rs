@@ -0,0 +1,293 @@
+//! Renderer handles - independent render targets behind the FFI
+//! Each renderer owns its terminal; the legacy global API drives one shared instance
+
+use std::sync::atomic::{AtomicBool, AtomicI32, Ordering};
+use std::sync::{Mutex, MutexGuard, PoisonError};
+use std::time::{Duration, Instant};
+use crate::core::error::{Error, ErrorCode, Result};
+use crate::core::layout::layout_mismatch;
+use crate::core::types::{CellStyle, Event, FrameStats, GameState, InputState, MouseEvent, RendererConfig};
+use crate::rendering::color;
+use crate::rendering::debug::{self, DebugStats, FrameMeter};
+use crate::rendering::hit::HitMap;
+use crate::rendering::reload;
+use crate::rendering::scene::{FrameTime, Scene};
+use super::snapshot::{buffer_styles, buffer_to_text};
+use super::terminal::RenderTarget;
+
+/// An independent renderer, handed to the host as an opaque pointer
+/// All methods lock internally, so a handle may be shared between threads.
+/// A renderer that panicked stays unusable until destroyed.
+pub struct Renderer {
+    target: Mutex<RenderTarget>,
+    /// Clickable areas of the last frame, for mouse hit-testing
+    hits: Mutex<HitMap>,
+    /// Frame clock, particles and pacing carried from frame to frame
+    scene: Mutex<Scene>,
+    /// Frame rate and changed cells of the game frames, for the debug overlay
+    meter: Mutex<FrameMeter>,
+    /// Colour depth frames are drawn at, one of `color_modes` but never AUTO
+    color_mode: AtomicI32,
+    /// Set once a call on this renderer panicked
+    poisoned: AtomicBool,
+}
+
+impl Renderer {
+    /// Create a renderer for the given configuration
+    /// Fails while the host's struct layout is known not to match
+    pub fn new(config: &RendererConfig) -> Result<Self> {
+        if let Some(mismatch) = layout_mismatch() {
+            return Err(Error::new(ErrorCode::LayoutMismatch, format!("struct layout mismatch: {}", mismatch)));
+        }
+
+        let color_mode = color::resolve(config.color_mode, !config.headless)?;
+        let target = if config.headless {
+            RenderTarget::headless(config.width, config.height)?
+        } else {
+            let target = RenderTarget::crossterm()?;
+            // Events left over from an earlier renderer belong to its session
+            super::events::clear();
+            target
+        };
+
+        Ok(Renderer {
+            target: Mutex::new(target),
+            hits: Mutex::new(HitMap::default()),
+            scene: Mutex::new(Scene::default()),
+            meter: Mutex::new(FrameMeter::default()),
+            color_mode: AtomicI32::new(color_mode),
+            poisoned: AtomicBool::new(false),
+        })
+    }
+
+    fn lock(&self) -> Result<MutexGuard<'_, RenderTarget>> {
+        self.target.lock().map_err(|_| poisoned())
+    }
+
+    /// Fail once a call on this renderer has panicked
+    pub fn check_live(&self) -> Result<()> {
+        if self.poisoned.load(Ordering::SeqCst) {
+            return Err(poisoned());
+        }
+        Ok(())
+    }
+
+    /// Make the renderer unusable after a panic in one of its calls
+    /// Gives the terminal back if this renderer owns it, so the host's
+    /// console works again even before the handle is destroyed.
+    pub fn poison(&self) {
+        self.poisoned.store(true, Ordering::SeqCst);
+        self.target.lock().unwrap_or_else(PoisonError::into_inner).release_tty();
+    }
+
+    /// Render one game frame
+    /// A changed asset pack under watch is swapped in before drawing, and
+    /// the scene advances by the time since the previous frame. What the
+    /// frame took to draw and flush is recorded by the pacer, and the debug
+    /// overlay goes on top when enabled. Colours are quantized last.
+    pub fn render(&self, state: &GameState) -> Result<()> {
+        reload::poll();
+        let start = Instant::now();
+        let mut scene = self.lock_scene();
+        let time = scene.tick(state);
+        let overlay = debug::enabled()
+            .then(|| DebugStats::new(&self.lock_meter(), &scene.pacer().stats(time.frame), state));
+        let mut hits = HitMap::default();
+        let color_mode = self.color_mode();
+        let cells = self.lock()?.draw(|f| {
+            crate::render_game_with(f, state, &scene);
+            reload::render_overlay(f);
+            if let Some(stats) = &overlay {
+                debug::render_overlay(f, stats);
+            }
+            color::quantize_buffer(f.buffer_mut(), color_mode);
+            hits = HitMap::for_game(f.area(), state);
+        })?;
+        scene.pacer_mut().record(start.elapsed());
+        self.lock_meter().record(time.delta, cells);
+        self.set_hits(hits);
+        Ok(())
+    }
+
+    /// Render the menu screen
+    pub fn render_menu(&self, title: &str, options: &[String], selected: i32) -> Result<()> {
+        reload::poll();
+        self.lock_scene().tick_idle();
+        let mut hits = HitMap::default();
+        let color_mode = self.color_mode();
+        self.lock()?.draw(|f| {
+            crate::rendering::hud::render_menu(f, title, options, selected);
+            reload::render_overlay(f);
+            color::quantize_buffer(f.buffer_mut(), color_mode);
+            hits = HitMap::for_menu(f.area(), options.len());
+        })?;
+        self.set_hits(hits);
+        Ok(())
+    }
+
+    fn lock_scene(&self) -> MutexGuard<'_, Scene> {
+        self.scene.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn lock_meter(&self) -> MutexGuard<'_, FrameMeter> {
+        self.meter.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Frame rate and changed cells of the game frames drawn so far
+    pub fn frame_meter(&self) -> FrameMeter {
+        *self.lock_meter()
+    }
+
+    /// Frame counter and timing of the last frame drawn
+    pub fn frame_time(&self) -> FrameTime {
+        self.lock_scene().time()
+    }
+
+    /// Pace frames at `fps` with `wait_frame`, or not at all for 0
+    /// While frames are paced, `poll_input` no longer waits for input.
+    pub fn set_target_fps(&self, fps: u32) {
+        self.lock_scene().pacer_mut().set_target_fps(fps);
+    }
+
+    /// Draw at one of `quality_levels`, or at most at it while `adaptive`
+    pub fn set_quality(&self, level: i32, adaptive: bool) -> Result<()> {
+        self.lock_scene().pacer_mut().set_quality(level, adaptive)
+    }
+
+    /// Wait until the next frame is due at the target rate
+    /// The renderer owning the real terminal keeps reading input meanwhile.
+    /// Returns at once while frames are not paced.
+    pub fn wait_frame(&self) -> Result<()> {
+        let wait = self.lock_scene().pacer_mut().until_next_frame(Instant::now());
+        if wait.is_zero() {
+            return Ok(());
+        }
+        if self.lock()?.is_tty() {
+            super::input::wait(wait)
+        } else {
+            std::thread::sleep(wait);
+            Ok(())
+        }
+    }
+
+    /// Draw at one of `color_modes` from the next frame on
+    /// `AUTO` detects the depth again, which off-screen is always TRUECOLOR.
+    pub fn set_color_mode(&self, mode: i32) -> Result<()> {
+        let is_tty = self.lock()?.is_tty();
+        self.color_mode.store(color::resolve(mode, is_tty)?, Ordering::SeqCst);
+        Ok(())
+    }
+
+    /// Colour depth frames are drawn at
+    pub fn color_mode(&self) -> i32 {
+        self.color_mode.load(Ordering::SeqCst)
+    }
+
+    /// Frame counter, measured frame cost, target rate and quality
+    pub fn frame_stats(&self) -> FrameStats {
+        let scene = self.lock_scene();
+        scene.pacer().stats(scene.time().frame)
+    }
+
+    fn set_hits(&self, hits: HitMap) {
+        *self.hits.lock().unwrap_or_else(PoisonError::into_inner) = hits;
+    }
+
+    /// Clickable areas of the last frame
+    pub fn hits(&self) -> HitMap {
+        self.hits.lock().unwrap_or_else(PoisonError::into_inner).clone()
+    }
+
+    /// Turn mouse reporting on or off
+    /// Pending mouse events are dropped either way
+    pub fn set_mouse_capture(&self, enabled: bool) -> Result<()> {
+        let mut target = self.lock()?;
+        target.set_mouse_capture(enabled)?;
+        if target.is_tty() {
+            super::mouse::clear();
+        }
+        Ok(())
+    }
+
+    /// Next mouse event, hit-tested against the last frame
+    /// Like key events, only the renderer owning the real terminal gets any
+    pub fn next_mouse_event(&self) -> Result<Option<MouseEvent>> {
+        if !self.lock()?.is_tty() {
+            return Ok(None);
+        }
+        Ok(super::mouse::next(&self.hits()))
+    }
+
+    /// Take the next entry of the event stream
+    /// Only the renderer that owns the real terminal receives events
+    pub fn next_event(&self) -> Result<Option<Event>> {
+        if !self.lock()?.is_tty() {
+            return Ok(None);
+        }
+        super::input::next_event(&self.hits())
+    }
+
+    /// Poll keyboard input for this renderer
+    /// Only the renderer that owns the real terminal receives key events;
+    /// off-screen renderers always report an idle input state. Waits up to
+    /// a frame for input unless frames are paced.
+    pub fn poll_input(&self, input: &mut InputState) -> Result<()> {
+        let is_tty = self.lock()?.is_tty();
+        if is_tty {
+            let paced = self.lock_scene().pacer().target_fps() > 0;
+            let wait = if paced { Duration::ZERO } else { super::input::UNPACED_POLL_WAIT };
+            super::input::poll_input(input, wait)
+        } else {
+            *input = InputState::default();
+            Ok(())
+        }
+    }
+
+    /// Last frame as text (headless renderers only)
+    pub fn snapshot_text(&self) -> Result<String> {
+        self.lock()?.snapshot().map(buffer_to_text).ok_or_else(not_headless)
+    }
+
+    /// Last frame cell styles (headless renderers only)
+    pub fn snapshot_styles(&self) -> Result<Vec<CellStyle>> {
+        self.lock()?.snapshot().map(buffer_styles).ok_or_else(not_headless)
+    }
+}
+
+fn poisoned() -> Error {
+    Error::new(ErrorCode::Panic, "renderer panicked during an earlier call and must be destroyed")
+}
+
+fn not_headless() -> Error {
+    Error::new(ErrorCode::NotHeadless, "snapshots are only available from headless renderers")
+}
+
+/// Renderer driven by the handle-less compatibility functions
+static GLOBAL: Mutex<Option<Renderer>> = Mutex::new(None);
+
+fn lock_global() -> MutexGuard<'static, Option<Renderer>> {
+    GLOBAL.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+/// Replace the global renderer, creating the new one only after the old one
+/// has released the terminal
+pub fn init_global(config: &RendererConfig) -> Result<()> {
+    let mut global = lock_global();
+    *global = None;
+    *global = Some(Renderer::new(config)?);
+    Ok(())
+}
+
+/// Drop the global renderer, restoring the terminal if it owned it
+pub fn cleanup_global() {
+    *lock_global() = None;
+}
+
+/// Run `f` with the global renderer
+/// Fails with `NotInitialized` when there is none
+pub fn with_global<R>(f: impl FnOnce(&Renderer) -> Result<R>) -> Result<R> {
+    match lock_global().as_ref() {
+        Some(renderer) => f(renderer),
+        None => Err(Error::new(ErrorCode::NotInitialized, "renderer is not initialized; call ratatui_init first")),
+    }
+}
@@ -0,0 +1,68 @@
+//! Snapshot export for headless rendering
+//! Converts a rendered buffer into plain text and per-cell styles
+
+use ratatui::{buffer::Buffer, style::Color};
+use unicode_width::UnicodeWidthStr;
+use crate::core::types::CellStyle;
+
+/// Convert a buffer to text, one line per row separated by `\n`
+/// Cells hidden behind wide glyphs are skipped so each line displays at buffer width
+pub fn buffer_to_text(buffer: &Buffer) -> String {
+    let width = buffer.area.width as usize;
+    let mut text = String::with_capacity(buffer.content.len() + buffer.area.height as usize);
+
+    for (row, cells) in buffer.content.chunks(width.max(1)).enumerate() {
+        if row > 0 {
+            text.push('\n');
+        }
+
+        let mut skip: usize = 0;
+        for cell in cells {
+            if skip == 0 {
+                text.push_str(cell.symbol());
+            }
+            skip = skip.max(cell.symbol().width()).saturating_sub(1);
+        }
+    }
+
+    text
+}
+
+/// Collect the style of every cell in row-major order
+pub fn buffer_styles(buffer: &Buffer) -> Vec<CellStyle> {
+    buffer
+        .content
+        .iter()
+        .map(|cell| CellStyle {
+            fg: pack_color(cell.fg),
+            bg: pack_color(cell.bg),
+            modifiers: cell.modifier.bits(),
+        })
+        .collect()
+}
+
+/// Pack a color into the `CellStyle` encoding
+pub fn pack_color(color: Color) -> u32 {
+    let indexed = |i: u8| 0x0200_0000 | i as u32;
+    match color {
+        Color::Reset => 0,
+        Color::Black => indexed(0),
+        Color::Red => indexed(1),
+        Color::Green => indexed(2),
+        Color::Yellow => indexed(3),
+        Color::Blue => indexed(4),
+        Color::Magenta => indexed(5),
+        Color::Cyan => indexed(6),
+        Color::Gray => indexed(7),
+        Color::DarkGray => indexed(8),
+        Color::LightRed => indexed(9),
+        Color::LightGreen => indexed(10),
+        Color::LightYellow => indexed(11),
+        Color::LightBlue => indexed(12),
+        Color::LightMagenta => indexed(13),
+        Color::LightCyan => indexed(14),
+        Color::White => indexed(15),
+        Color::Indexed(i) => indexed(i),
+        Color::Rgb(r, g, b) => 0x0100_0000 | (r as u32) << 16 | (g as u32) << 8 | b as u32,
+    }
+}
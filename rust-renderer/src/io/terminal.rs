@@ -1,69 +1,284 @@
-/// Terminal management module for initialization and cleanup
-/// Handles raw mode, alternate screen, and terminal setup
+//! Terminal management module for initialization and cleanup
+//! Handles raw mode, alternate screen, and terminal setup
 
 use ratatui::{
-    backend::CrosstermBackend,
+    backend::{Backend, ClearType, CrosstermBackend, TestBackend, WindowSize},
+    buffer::{Buffer, Cell},
+    layout::{Position, Size},
+    Frame,
     Terminal,
 };
 use crossterm::{
+    event::{
+        DisableBracketedPaste, DisableFocusChange, DisableMouseCapture, EnableBracketedPaste, EnableFocusChange,
+        EnableMouseCapture, KeyboardEnhancementFlags, PopKeyboardEnhancementFlags, PushKeyboardEnhancementFlags,
+    },
     execute,
-    terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
+    terminal::{
+        disable_raw_mode, enable_raw_mode, supports_keyboard_enhancement, EnterAlternateScreen,
+        LeaveAlternateScreen,
+    },
 };
 use std::io;
+use crate::core::error::{Error, ErrorCode, Result};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
 
-/// Global terminal instance
-pub static mut TERMINAL: Option<Terminal<CrosstermBackend<io::Stdout>>> = None;
-
-/// Initialize the terminal for rendering
-/// Sets up raw mode and alternate screen
-pub fn init() -> bool {
-    match enable_raw_mode() {
-        Ok(_) => {},
-        Err(e) => {
-            eprintln!("Failed to enable raw mode: {}", e);
-            return false;
+/// Token of the claim that currently owns the real terminal (0 = none)
+static TTY_OWNER: AtomicU64 = AtomicU64::new(0);
+static NEXT_CLAIM: AtomicU64 = AtomicU64::new(1);
+
+/// Whether the kitty keyboard flags were pushed and must be popped on restore
+static KEYBOARD_ENHANCED: AtomicBool = AtomicBool::new(false);
+
+/// Whether mouse reporting is on and must be turned off on restore
+static MOUSE_CAPTURED: AtomicBool = AtomicBool::new(false);
+
+/// Whether the real terminal was resized since the last frame
+static RESIZED: AtomicBool = AtomicBool::new(false);
+
+/// Exclusive claim on the real terminal
+/// Restores the terminal when dropped, unless it was already force-released
+pub struct TtyClaim(u64);
+
+impl TtyClaim {
+    fn acquire() -> Option<Self> {
+        let token = NEXT_CLAIM.fetch_add(1, Ordering::SeqCst);
+        TTY_OWNER
+            .compare_exchange(0, token, Ordering::SeqCst, Ordering::SeqCst)
+            .ok()
+            .map(|_| TtyClaim(token))
+    }
+
+    /// Restore the terminal if this claim still owns it
+    fn release(&self) {
+        if TTY_OWNER.compare_exchange(self.0, 0, Ordering::SeqCst, Ordering::SeqCst).is_ok() {
+            restore_tty();
         }
     }
-    
-    match execute!(io::stdout(), EnterAlternateScreen) {
-        Ok(_) => {},
-        Err(e) => {
-            eprintln!("Failed to enter alternate screen: {}", e);
-            let _ = disable_raw_mode();
-            return false;
+}
+
+impl Drop for TtyClaim {
+    fn drop(&mut self) {
+        self.release();
+    }
+}
+
+/// Backend that counts the cells ratatui's diff hands it
+/// Tells how much of each frame actually reached the terminal, which is
+/// what costs time over slow links.
+pub struct Counted<B> {
+    inner: B,
+    cells: usize,
+}
+
+impl<B> Counted<B> {
+    fn new(inner: B) -> Self {
+        Counted { inner, cells: 0 }
+    }
+
+    /// Cells written since the last call
+    fn take(&mut self) -> usize {
+        std::mem::take(&mut self.cells)
+    }
+}
+
+impl<B: Backend> Backend for Counted<B> {
+    fn draw<'a, I>(&mut self, content: I) -> io::Result<()>
+    where
+        I: Iterator<Item = (u16, u16, &'a Cell)>,
+    {
+        let cells = &mut self.cells;
+        self.inner.draw(content.inspect(|_| *cells += 1))
+    }
+
+    fn append_lines(&mut self, n: u16) -> io::Result<()> {
+        self.inner.append_lines(n)
+    }
+
+    fn hide_cursor(&mut self) -> io::Result<()> {
+        self.inner.hide_cursor()
+    }
+
+    fn show_cursor(&mut self) -> io::Result<()> {
+        self.inner.show_cursor()
+    }
+
+    fn get_cursor_position(&mut self) -> io::Result<Position> {
+        self.inner.get_cursor_position()
+    }
+
+    fn set_cursor_position<P: Into<Position>>(&mut self, position: P) -> io::Result<()> {
+        self.inner.set_cursor_position(position)
+    }
+
+    fn clear(&mut self) -> io::Result<()> {
+        self.inner.clear()
+    }
+
+    fn clear_region(&mut self, clear_type: ClearType) -> io::Result<()> {
+        self.inner.clear_region(clear_type)
+    }
+
+    fn size(&self) -> io::Result<Size> {
+        self.inner.size()
+    }
+
+    fn window_size(&mut self) -> io::Result<WindowSize> {
+        self.inner.window_size()
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+/// Where frames are drawn: the real terminal or an off-screen buffer
+pub enum RenderTarget {
+    /// Interactive terminal in raw mode on the alternate screen
+    Crossterm(Terminal<Counted<CrosstermBackend<io::Stdout>>>, TtyClaim),
+    /// Fixed-size in-memory buffer for deterministic snapshots
+    Headless(Terminal<Counted<TestBackend>>),
+}
+
+impl RenderTarget {
+    /// Take over the real terminal for rendering
+    /// Sets up raw mode and alternate screen. Only one target may own the
+    /// terminal at a time; the terminal is restored when the target is dropped.
+    pub fn crossterm() -> Result<Self> {
+        let claim = TtyClaim::acquire()
+            .ok_or_else(|| Error::new(ErrorCode::TerminalBusy, "terminal is already owned by another renderer"))?;
+
+        // From here on, dropping `claim` on an error path restores the terminal
+        enable_raw_mode().map_err(|e| Error::new(ErrorCode::RawModeDenied, format!("failed to enable raw mode: {}", e)))?;
+        execute!(io::stdout(), EnterAlternateScreen).map_err(|e| Error::io("failed to enter alternate screen", e))?;
+        enable_keyboard_enhancement();
+        // Focus and paste reports feed the event stream; terminals without
+        // support ignore the requests
+        let _ = execute!(io::stdout(), EnableFocusChange, EnableBracketedPaste);
+
+        let terminal = Terminal::new(Counted::new(CrosstermBackend::new(io::stdout())))
+            .map_err(|e| Error::io("failed to create terminal", e))?;
+        Ok(RenderTarget::Crossterm(terminal, claim))
+    }
+
+    /// Create an off-screen terminal of the given size
+    /// Leaves the real terminal untouched so it works without a TTY
+    pub fn headless(width: u16, height: u16) -> Result<Self> {
+        if width == 0 || height == 0 {
+            return Err(Error::new(
+                ErrorCode::InvalidArgument,
+                format!("invalid headless size: {}x{}", width, height),
+            ));
+        }
+
+        Terminal::new(Counted::new(TestBackend::new(width, height)))
+            .map(RenderTarget::Headless)
+            .map_err(|e| Error::io("failed to create headless terminal", e))
+    }
+
+    /// Whether this target draws to the real terminal
+    pub fn is_tty(&self) -> bool {
+        matches!(self, RenderTarget::Crossterm(..))
+    }
+
+    /// Give the real terminal back early, if this target still owns it
+    /// Used after a panic; another renderer may claim the terminal from then on.
+    pub fn release_tty(&self) {
+        if let RenderTarget::Crossterm(_, claim) = self {
+            claim.release();
+        }
+    }
+
+    /// Draw a single frame to the target
+    /// Returns how many cells differed from the previous frame and were
+    /// written out.
+    pub fn draw<F: FnOnce(&mut Frame)>(&mut self, render: F) -> Result<usize> {
+        let drawn = match self {
+            // After a resize the screen may hold reflowed leftovers of the
+            // old frame, so repaint every cell instead of a diff
+            RenderTarget::Crossterm(t, _) if RESIZED.swap(false, Ordering::SeqCst) => {
+                t.clear().and_then(|_| t.draw(render)).map(|_| ())
+            },
+            RenderTarget::Crossterm(t, _) => t.draw(render).map(|_| ()),
+            RenderTarget::Headless(t) => t.draw(render).map(|_| ()),
+        };
+        drawn.map_err(|e| Error::io("failed to draw frame", e))?;
+
+        Ok(match self {
+            RenderTarget::Crossterm(t, _) => t.backend_mut().take(),
+            RenderTarget::Headless(t) => t.backend_mut().take(),
+        })
+    }
+
+    /// Turn terminal mouse reporting on or off
+    /// Off by default, since capturing the mouse disables text selection in
+    /// most terminals. Headless targets have no mouse and accept either.
+    pub fn set_mouse_capture(&mut self, enabled: bool) -> Result<()> {
+        if !self.is_tty() || MOUSE_CAPTURED.load(Ordering::SeqCst) == enabled {
+            return Ok(());
         }
+        let result = if enabled {
+            execute!(io::stdout(), EnableMouseCapture)
+        } else {
+            execute!(io::stdout(), DisableMouseCapture)
+        };
+        result.map_err(|e| Error::io("failed to change mouse capture", e))?;
+        MOUSE_CAPTURED.store(enabled, Ordering::SeqCst);
+        Ok(())
     }
-    
-    let backend = CrosstermBackend::new(io::stdout());
-    let terminal = Terminal::new(backend);
-    
-    match terminal {
-        Ok(t) => {
-            unsafe { TERMINAL = Some(t); }
-            true
-        },
-        Err(e) => {
-            eprintln!("Failed to create terminal: {}", e);
-            let _ = disable_raw_mode();
-            let _ = execute!(io::stdout(), LeaveAlternateScreen);
-            false
+
+    /// Buffer holding the last drawn frame (headless targets only)
+    pub fn snapshot(&self) -> Option<&Buffer> {
+        match self {
+            RenderTarget::Crossterm(..) => None,
+            RenderTarget::Headless(t) => Some(t.backend().inner.buffer()),
         }
     }
 }
 
-/// Clean up and restore terminal to normal state
-pub fn cleanup() {
-    unsafe { TERMINAL = None; }
-    let _ = disable_raw_mode();
-    let _ = execute!(io::stdout(), LeaveAlternateScreen);
+/// Ask the terminal to report key repeat and release events (kitty keyboard
+/// protocol). Letter keys only report releases as escape codes, so all keys
+/// are requested that way. Terminals without support keep legacy input.
+fn enable_keyboard_enhancement() {
+    if !supports_keyboard_enhancement().unwrap_or(false) {
+        return;
+    }
+
+    let flags = KeyboardEnhancementFlags::DISAMBIGUATE_ESCAPE_CODES
+        | KeyboardEnhancementFlags::REPORT_EVENT_TYPES
+        | KeyboardEnhancementFlags::REPORT_ALL_KEYS_AS_ESCAPE_CODES;
+    if execute!(io::stdout(), PushKeyboardEnhancementFlags(flags)).is_ok() {
+        KEYBOARD_ENHANCED.store(true, Ordering::SeqCst);
+    }
 }
 
-/// Get mutable reference to the terminal
-pub fn get_terminal() -> Option<&'static mut Terminal<CrosstermBackend<io::Stdout>>> {
-    unsafe {
-        match std::ptr::addr_of_mut!(TERMINAL).as_mut() {
-            Some(Some(t)) => Some(t),
-            _ => None,
-        }
+/// Record a resize of the real terminal; the next frame is fully redrawn
+pub fn notify_resize() {
+    RESIZED.store(true, Ordering::SeqCst);
+}
+
+/// Whether the real terminal reports key release events
+pub fn keyboard_enhanced() -> bool {
+    KEYBOARD_ENHANCED.load(Ordering::SeqCst)
+}
+
+/// Force the real terminal back to normal state, whichever renderer owns it
+/// Used after a panic; the owning renderer's later drop becomes a no-op
+pub fn release_tty() {
+    if TTY_OWNER.swap(0, Ordering::SeqCst) != 0 {
+        restore_tty();
     }
 }
+
+/// Leave raw mode and the alternate screen
+fn restore_tty() {
+    if MOUSE_CAPTURED.swap(false, Ordering::SeqCst) {
+        let _ = execute!(io::stdout(), DisableMouseCapture);
+    }
+    if KEYBOARD_ENHANCED.swap(false, Ordering::SeqCst) {
+        let _ = execute!(io::stdout(), PopKeyboardEnhancementFlags);
+    }
+    let _ = execute!(io::stdout(), DisableBracketedPaste, DisableFocusChange);
+    let _ = disable_raw_mode();
+    let _ = execute!(io::stdout(), LeaveAlternateScreen);
+}
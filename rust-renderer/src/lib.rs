@@ -14,69 +14,812 @@ pub mod io;        // Input handling and terminal management
 pub mod rendering; // All visual rendering and UI
 
 // Re-export public types for C FFI
-pub use core::{GameState, InputState, AudioCommand};
+pub use core::{GameState, InputState, AudioCommand, CellStyle, Event, FrameStats, MouseEvent, RendererConfig, ErrorCode};
+pub use io::renderer::Renderer;
 
 // Import commonly used items
+use std::ffi::CStr;
+use std::os::raw::c_char;
+use crate::core::error::{self, Error, Result};
 use crate::core::types::game_modes;
+use crate::io::guard::{ffi_guard, handle_guard, lifecycle_guard};
+use crate::io::renderer;
+
+// Every `extern "C"` entry point runs inside a guard so a panic never unwinds
+// into the host: `handle_guard` for renderer handles, which only tears down
+// the handle that panicked, and `ffi_guard` for everything else, which gives
+// the terminal back and drops the global renderer.
+
+// ---------------------------------------------------------------------------
+// ABI checks
+// ---------------------------------------------------------------------------
+
+/// Version of the FFI surface; hosts should refuse to run on a mismatch
+#[unsafe(no_mangle)]
+pub extern "C" fn ratatui_abi_version() -> u32 {
+    core::layout::ABI_VERSION
+}
+
+/// Check the host's `GameState` and `InputState` layouts against Rust
+/// Each `*_offsets` array lists the byte offset of every field in declaration
+/// order. On mismatch `false` is returned with the reason available from
+/// `ratatui_last_error`, and every later `ratatui_init` / `ratatui_create`
+/// fails until a validation passes.
+///
+/// # Safety
+/// Each offsets pointer must be null or point to `*_field_count` values.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn ratatui_validate_layout(
+    game_state_size: usize,
+    game_state_offsets: *const usize,
+    game_state_field_count: usize,
+    input_state_size: usize,
+    input_state_offsets: *const usize,
+    input_state_field_count: usize,
+) -> bool {
+    ffi_guard(false, || {
+        let game_offsets = unsafe { ffi_slice(game_state_offsets, game_state_field_count) }
+            .ok_or_else(|| Error::null("game_state_offsets"))?;
+        let input_offsets = unsafe { ffi_slice(input_state_offsets, input_state_field_count) }
+            .ok_or_else(|| Error::null("input_state_offsets"))?;
+        
+        let result = core::layout::check_layout(
+            &core::layout::HostLayout { size: game_state_size, offsets: game_offsets },
+            &core::layout::HostLayout { size: input_state_size, offsets: input_offsets },
+        );
+        core::layout::record_validation(result.clone());
+        result.map(|_| true).map_err(|mismatch| Error::new(ErrorCode::LayoutMismatch, mismatch))
+    })
+}
+
+// ---------------------------------------------------------------------------
+// Errors
+// ---------------------------------------------------------------------------
+
+/// Code of the last failure on the calling thread, `Ok` if there was none
+/// Failed calls keep their error until a later failure on the same thread
+/// replaces it, so read it right after a call reports failure.
+#[unsafe(no_mangle)]
+pub extern "C" fn ratatui_last_error_code() -> ErrorCode {
+    ffi_guard(ErrorCode::Panic, || Ok(error::last_error().map_or(ErrorCode::Ok, |e| e.code)))
+}
+
+/// Copy the message of the last failure on the calling thread into `buf`
+/// Uses the buffer protocol of `ratatui_snapshot_text`: returns the full
+/// message length in bytes, or 0 (an empty string) when there was no failure.
+///
+/// # Safety
+/// `buf` must be null or point to at least `len` writable bytes.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn ratatui_last_error(buf: *mut c_char, len: usize) -> i32 {
+    ffi_guard(-1, || {
+        let message = error::last_error().map(|e| e.message).unwrap_or_default();
+        Ok(unsafe { copy_text(&message, buf, len) })
+    })
+}
+
+// ---------------------------------------------------------------------------
+// Key bindings
+// ---------------------------------------------------------------------------
+
+/// Replace the active key bindings with a TOML or JSON table
+/// The table maps `InputState` field names to lists of key specs; fields left
+/// out keep their default keys. On a parse error or a key bound twice the
+/// active bindings are left unchanged and `false` is returned.
+///
+/// # Safety
+/// `text` must be null or a valid C string.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn ratatui_set_bindings(text: *const c_char) -> bool {
+    ffi_guard(false, || {
+        if text.is_null() {
+            return Err(Error::null("text"));
+        }
+        let text = unsafe { utf8(text, "bindings") }?;
+        io::bindings::set_active(io::bindings::Bindings::parse(&text)?);
+        Ok(true)
+    })
+}
+
+/// Load the active key bindings from a `.toml` or `.json` file
+///
+/// # Safety
+/// `path` must be null or a valid C string.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn ratatui_load_bindings(path: *const c_char) -> bool {
+    ffi_guard(false, || {
+        if path.is_null() {
+            return Err(Error::null("path"));
+        }
+        let path = unsafe { utf8(path, "path") }?;
+        io::bindings::set_active(io::bindings::Bindings::load(std::path::Path::new(&path))?);
+        Ok(true)
+    })
+}
+
+/// Copy the active key bindings into `buf` as a JSON object
+/// Lists every `InputState` field in declaration order and uses the buffer
+/// protocol of `ratatui_snapshot_text`.
+///
+/// # Safety
+/// `buf` must be null or point to at least `len` writable bytes.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn ratatui_get_bindings(buf: *mut c_char, len: usize) -> i32 {
+    ffi_guard(-1, || Ok(unsafe { copy_text(&io::bindings::active().to_json(), buf, len) }))
+}
+
+// ---------------------------------------------------------------------------
+// Assets
+// ---------------------------------------------------------------------------
+
+/// Switch every renderer to one of the built-in `asset_styles`
+/// Takes effect from the next frame. An unknown style leaves the active
+/// assets unchanged and returns `false`.
+#[unsafe(no_mangle)]
+pub extern "C" fn ratatui_set_asset_style(style: i32) -> bool {
+    ffi_guard(false, || rendering::assets::set_style(style).map(|_| true))
+}
+
+/// Replace the active assets with a TOML or JSON asset pack
+/// The pack overlays cars, powerups, buildings, road, scenery, HUD titles and
+/// animations on its `base` style. On a parse error or a sprite that does not
+/// fit, the active assets are left unchanged and `false` is returned.
+///
+/// # Safety
+/// `text` must be null or a valid C string.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn ratatui_set_asset_pack(text: *const c_char) -> bool {
+    ffi_guard(false, || {
+        if text.is_null() {
+            return Err(Error::null("text"));
+        }
+        let text = unsafe { utf8(text, "asset pack") }?;
+        rendering::assets::set_active(rendering::assets::AssetRegistry::parse_pack(&text)?);
+        Ok(true)
+    })
+}
+
+/// Load the active assets from a `.toml` or `.json` asset pack file
+///
+/// # Safety
+/// `path` must be null or a valid C string.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn ratatui_load_asset_pack(path: *const c_char) -> bool {
+    ffi_guard(false, || {
+        if path.is_null() {
+            return Err(Error::null("path"));
+        }
+        let path = unsafe { utf8(path, "path") }?;
+        rendering::assets::set_active(rendering::assets::AssetRegistry::load_pack(std::path::Path::new(&path))?);
+        Ok(true)
+    })
+}
+
+/// Load an asset pack and reload it whenever the file changes
+/// Meant for development: the file's modification time is checked before
+/// each frame and a changed pack is swapped in between frames. A pack that
+/// fails to load keeps the last good assets and is reported in an on-screen
+/// overlay until fixed. Returns whether the first load succeeded; watching
+/// starts either way.
+///
+/// # Safety
+/// `path` must be null or a valid C string.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn ratatui_watch_asset_pack(path: *const c_char) -> bool {
+    ffi_guard(false, || {
+        if path.is_null() {
+            return Err(Error::null("path"));
+        }
+        let path = unsafe { utf8(path, "path") }?;
+        rendering::reload::watch(std::path::Path::new(&path)).map(|_| true)
+    })
+}
+
+/// Stop watching the asset pack; the assets last loaded stay active
+#[unsafe(no_mangle)]
+pub extern "C" fn ratatui_unwatch_asset_pack() {
+    ffi_guard((), || {
+        rendering::reload::unwatch();
+        Ok(())
+    })
+}
+
+/// Copy the reason the watched asset pack failed to load into `buf`
+/// Returns 0 while the pack is loaded fine, otherwise uses the buffer
+/// protocol of `ratatui_snapshot_text`.
+///
+/// # Safety
+/// `buf` must be null or point to at least `len` writable bytes.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn ratatui_asset_reload_error(buf: *mut c_char, len: usize) -> i32 {
+    ffi_guard(-1, || Ok(unsafe { copy_text(&rendering::reload::error().unwrap_or_default(), buf, len) }))
+}
+
+/// Style of the active assets, one of `asset_styles`
+#[unsafe(no_mangle)]
+pub extern "C" fn ratatui_asset_style() -> i32 {
+    ffi_guard(-1, || Ok(rendering::assets::active().style()))
+}
+
+// ---------------------------------------------------------------------------
+// Diagnostics
+// ---------------------------------------------------------------------------
+
+/// Show or hide the debug performance overlay on every renderer
+/// Game frames then carry FPS, frame and draw time, the cells the last
+/// frame changed on the terminal, object counts, terminal size and quality
+/// in their top right corner. F3 toggles it too.
+#[unsafe(no_mangle)]
+pub extern "C" fn ratatui_set_debug_overlay(enabled: bool) {
+    ffi_guard((), || {
+        rendering::debug::set_enabled(enabled);
+        Ok(())
+    })
+}
+
+/// Whether the debug overlay is shown
+#[unsafe(no_mangle)]
+pub extern "C" fn ratatui_debug_overlay() -> bool {
+    ffi_guard(false, || Ok(rendering::debug::enabled()))
+}
+
+// ---------------------------------------------------------------------------
+// Renderer handles
+// ---------------------------------------------------------------------------
+
+/// Create an independent renderer
+/// A null `config` creates an on-screen renderer. Only one on-screen renderer
+/// may exist at a time, but any number of headless ones can run alongside it.
+/// Returns null on failure (see `ratatui_last_error`). Release with
+/// `ratatui_destroy`.
+///
+/// # Safety
+/// `config` must be null or point to a valid `RendererConfig`.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn ratatui_create(config: *const RendererConfig) -> *mut Renderer {
+    lifecycle_guard(std::ptr::null_mut(), || {
+        let config = unsafe { config.as_ref() }.copied().unwrap_or_default();
+        Renderer::new(&config).map(|r| Box::into_raw(Box::new(r)))
+    })
+}
+
+/// Destroy a renderer, restoring the terminal if it owned it
+///
+/// # Safety
+/// `renderer` must be null or a handle from `ratatui_create` that has not
+/// been destroyed yet, and must not be in use on another thread.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn ratatui_destroy(renderer: *mut Renderer) {
+    lifecycle_guard((), || {
+        if !renderer.is_null() {
+            drop(unsafe { Box::from_raw(renderer) });
+        }
+        Ok(())
+    })
+}
+
+/// Render the game on a renderer handle
+///
+/// # Safety
+/// `renderer` must be null or a live handle from `ratatui_create`; `state`
+/// has the same requirements as in `ratatui_render`.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn ratatui_renderer_render(renderer: *const Renderer, state: *const GameState) -> bool {
+    handle_guard(unsafe { renderer.as_ref() }, false, |r| {
+        let s = unsafe { state.as_ref() }.ok_or_else(|| Error::null("state"))?;
+        r.render(s).map(|_| true)
+    })
+}
+
+/// Render the menu on a renderer handle
+///
+/// # Safety
+/// `renderer` must be null or a live handle from `ratatui_create`; the
+/// strings have the same requirements as in `ratatui_render_menu`.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn ratatui_renderer_render_menu(
+    renderer: *const Renderer,
+    title: *const c_char,
+    options: *const *const c_char,
+    option_count: i32,
+    selected: i32,
+) -> bool {
+    handle_guard(unsafe { renderer.as_ref() }, false, |r| {
+        let (title, options) = unsafe { menu_strings(title, options, option_count) }?;
+        r.render_menu(&title, &options, selected).map(|_| true)
+    })
+}
+
+/// Pace a renderer handle's frames; see `ratatui_set_target_fps`
+///
+/// # Safety
+/// `renderer` must be null or a live handle from `ratatui_create`.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn ratatui_renderer_set_target_fps(renderer: *const Renderer, fps: u32) -> bool {
+    handle_guard(unsafe { renderer.as_ref() }, false, |r| {
+        r.set_target_fps(fps);
+        Ok(true)
+    })
+}
+
+/// Wait for a renderer handle's next frame; see `ratatui_wait_frame`
+///
+/// # Safety
+/// `renderer` must be null or a live handle from `ratatui_create`.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn ratatui_renderer_wait_frame(renderer: *const Renderer) -> bool {
+    handle_guard(unsafe { renderer.as_ref() }, false, |r| {
+        r.wait_frame().map(|_| true)
+    })
+}
+
+/// Set a renderer handle's effect quality; see `ratatui_set_quality`
+///
+/// # Safety
+/// `renderer` must be null or a live handle from `ratatui_create`.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn ratatui_renderer_set_quality(renderer: *const Renderer, level: i32, adaptive: bool) -> bool {
+    handle_guard(unsafe { renderer.as_ref() }, false, |r| {
+        r.set_quality(level, adaptive).map(|_| true)
+    })
+}
+
+/// Read a renderer handle's pacing figures; see `ratatui_frame_stats`
+///
+/// # Safety
+/// `renderer` must be null or a live handle from `ratatui_create`; `stats`
+/// must be null or point to a writable `FrameStats`.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn ratatui_renderer_frame_stats(renderer: *const Renderer, stats: *mut FrameStats) -> bool {
+    handle_guard(unsafe { renderer.as_ref() }, false, |r| {
+        let s = unsafe { stats.as_mut() }.ok_or_else(|| Error::null("stats"))?;
+        *s = r.frame_stats();
+        Ok(true)
+    })
+}
+
+/// Set a renderer handle's colour depth; see `ratatui_set_color_mode`
+///
+/// # Safety
+/// `renderer` must be null or a live handle from `ratatui_create`.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn ratatui_renderer_set_color_mode(renderer: *const Renderer, mode: i32) -> bool {
+    handle_guard(unsafe { renderer.as_ref() }, false, |r| {
+        r.set_color_mode(mode).map(|_| true)
+    })
+}
+
+/// Colour depth a renderer handle draws at; see `ratatui_color_mode`
+///
+/// # Safety
+/// `renderer` must be null or a live handle from `ratatui_create`.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn ratatui_renderer_color_mode(renderer: *const Renderer) -> i32 {
+    handle_guard(unsafe { renderer.as_ref() }, -1, |r| {
+        Ok(r.color_mode())
+    })
+}
+
+/// Poll input for a renderer handle
+/// Off-screen renderers always report an idle input state.
+///
+/// # Safety
+/// `renderer` must be null or a live handle from `ratatui_create`; `input`
+/// must be null or point to a valid, writable `InputState`.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn ratatui_renderer_poll_input(renderer: *const Renderer, input: *mut InputState) -> bool {
+    handle_guard(unsafe { renderer.as_ref() }, false, |r| {
+        let i = unsafe { input.as_mut() }.ok_or_else(|| Error::null("input"))?;
+        r.poll_input(i).map(|_| true)
+    })
+}
+
+/// Turn mouse reporting on or off for a renderer handle
+/// Off by default; has no effect on off-screen renderers.
+///
+/// # Safety
+/// `renderer` must be null or a live handle from `ratatui_create`.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn ratatui_renderer_set_mouse_capture(renderer: *const Renderer, enabled: bool) -> bool {
+    handle_guard(unsafe { renderer.as_ref() }, false, |r| {
+        r.set_mouse_capture(enabled).map(|_| true)
+    })
+}
+
+/// Take the next mouse event of a renderer handle
+/// See `ratatui_poll_mouse`.
+///
+/// # Safety
+/// `renderer` must be null or a live handle from `ratatui_create`; `event`
+/// must be null or point to a writable `MouseEvent`.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn ratatui_renderer_poll_mouse(renderer: *const Renderer, event: *mut MouseEvent) -> bool {
+    handle_guard(unsafe { renderer.as_ref() }, false, |r| {
+        let e = unsafe { event.as_mut() }.ok_or_else(|| Error::null("event"))?;
+        Ok(r.next_mouse_event()?.map(|next| *e = next).is_some())
+    })
+}
+
+/// Take the next event of a renderer handle
+/// See `ratatui_next_event`.
+///
+/// # Safety
+/// `renderer` must be null or a live handle from `ratatui_create`; `event`
+/// must be null or point to a writable `Event`.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn ratatui_renderer_next_event(renderer: *const Renderer, event: *mut Event) -> bool {
+    handle_guard(unsafe { renderer.as_ref() }, false, |r| {
+        let e = unsafe { event.as_mut() }.ok_or_else(|| Error::null("event"))?;
+        Ok(r.next_event()?.map(|next| *e = next).is_some())
+    })
+}
+
+/// Copy the last frame of a headless renderer as text
+/// See `ratatui_snapshot_text` for the buffer protocol.
+///
+/// # Safety
+/// `renderer` must be null or a live handle from `ratatui_create`; `buf`
+/// must be null or point to at least `len` writable bytes.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn ratatui_renderer_snapshot_text(renderer: *const Renderer, buf: *mut c_char, len: usize) -> i32 {
+    handle_guard(unsafe { renderer.as_ref() }, -1, |r| {
+        Ok(unsafe { copy_text(&r.snapshot_text()?, buf, len) })
+    })
+}
+
+/// Copy the cell styles of the last frame of a headless renderer
+/// See `ratatui_snapshot_styles` for the buffer protocol.
+///
+/// # Safety
+/// `renderer` must be null or a live handle from `ratatui_create`; `cells`
+/// must be null or point to at least `capacity` writable `CellStyle`s.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn ratatui_renderer_snapshot_styles(renderer: *const Renderer, cells: *mut CellStyle, capacity: usize) -> i32 {
+    handle_guard(unsafe { renderer.as_ref() }, -1, |r| {
+        Ok(unsafe { copy_styles(&r.snapshot_styles()?, cells, capacity) })
+    })
+}
+
+// ---------------------------------------------------------------------------
+// Global renderer (compatibility layer over a single shared handle)
+// ---------------------------------------------------------------------------
 
 /// Initialize the rendering engine
 /// Sets up terminal, raw mode, and alternate screen
 #[unsafe(no_mangle)]
 pub extern "C" fn ratatui_init() -> bool {
-    io::terminal::init()
+    ffi_guard(false, || renderer::init_global(&RendererConfig::default()).map(|_| true))
+}
+
+/// Initialize an off-screen renderer of the given size
+/// Frames are kept in memory and can be read back with the snapshot calls
+#[unsafe(no_mangle)]
+pub extern "C" fn ratatui_init_headless(width: u16, height: u16) -> bool {
+    ffi_guard(false, || renderer::init_global(&RendererConfig { headless: true, width, height, ..Default::default() }).map(|_| true))
 }
 
 /// Clean up and restore terminal state
 #[unsafe(no_mangle)]
 pub extern "C" fn ratatui_cleanup() {
-    io::terminal::cleanup()
+    ffi_guard((), || {
+        renderer::cleanup_global();
+        Ok(())
+    })
 }
 
 /// Poll for keyboard input and update input state
+/// Waits up to 16 ms for the first event unless frames are paced with
+/// `ratatui_set_target_fps`, in which case it never waits.
+///
+/// # Safety
+/// `input` must be null or point to a valid, writable `InputState`.
 #[unsafe(no_mangle)]
-pub extern "C" fn ratatui_poll_input(input: *mut InputState) -> bool {
-    io::input::poll_input(input)
+pub unsafe extern "C" fn ratatui_poll_input(input: *mut InputState) -> bool {
+    ffi_guard(false, || {
+        let input_state = unsafe { input.as_mut() }.ok_or_else(|| Error::null("input"))?;
+        match renderer::with_global(|r| r.poll_input(input_state)) {
+            Err(e) if e.code == ErrorCode::NotInitialized => {
+                io::input::poll_input(input_state, io::input::UNPACED_POLL_WAIT)
+            },
+            result => result,
+        }
+        .map(|_| true)
+    })
+}
+
+/// Turn mouse reporting on or off
+/// Off by default, since capturing the mouse disables text selection in most
+/// terminals. Mouse events are read by `ratatui_poll_input`.
+#[unsafe(no_mangle)]
+pub extern "C" fn ratatui_set_mouse_capture(enabled: bool) -> bool {
+    ffi_guard(false, || renderer::with_global(|r| r.set_mouse_capture(enabled)).map(|_| true))
+}
+
+/// Take the next mouse event read by `ratatui_poll_input`
+/// Events are hit-tested against the last frame: clicks on menu options
+/// report the option index, clicks and drags on the replay timeline the seek
+/// position. Returns `false` once the queue is empty, so call it in a loop
+/// after each poll.
+///
+/// # Safety
+/// `event` must be null or point to a writable `MouseEvent`.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn ratatui_poll_mouse(event: *mut MouseEvent) -> bool {
+    ffi_guard(false, || {
+        let e = unsafe { event.as_mut() }.ok_or_else(|| Error::null("event"))?;
+        renderer::with_global(|r| Ok(r.next_mouse_event()?.map(|next| *e = next).is_some()))
+    })
+}
+
+/// Take the next entry of the terminal event stream
+/// Reports key presses and releases (with an auto-repeat flag), resizes with
+/// the new size, focus changes, mouse events hit-tested like
+/// `ratatui_poll_mouse`, and pastes. Never waits; returns `false` once no
+/// event is pending, so call it in a loop each frame. Events read here still
+/// count towards the next `ratatui_poll_input`.
+///
+/// # Safety
+/// `event` must be null or point to a writable `Event`.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn ratatui_next_event(event: *mut Event) -> bool {
+    ffi_guard(false, || {
+        let e = unsafe { event.as_mut() }.ok_or_else(|| Error::null("event"))?;
+        renderer::with_global(|r| Ok(r.next_event()?.map(|next| *e = next).is_some()))
+    })
+}
+
+/// Copy the text of the last paste event taken with `ratatui_next_event`
+/// See `ratatui_snapshot_text` for the buffer protocol.
+///
+/// # Safety
+/// `buf` must be null or point to at least `len` writable bytes.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn ratatui_event_text(buf: *mut c_char, len: usize) -> i32 {
+    ffi_guard(-1, || Ok(unsafe { copy_text(&io::events::text(), buf, len) }))
 }
 
 /// Render the game based on current game state
+///
+/// # Safety
+/// `state` must be null or point to a valid `GameState` whose object arrays
+/// hold at least `car_count`, `obstacle_count` and `building_count` entries.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn ratatui_render(state: *const GameState) -> bool {
+    ffi_guard(false, || {
+        let game_state = unsafe { state.as_ref() }.ok_or_else(|| Error::null("state"))?;
+        renderer::with_global(|r| r.render(game_state)).map(|_| true)
+    })
+}
+
+/// Render menu with title and options
+///
+/// # Safety
+/// `title` must be null or a valid C string and `options` must be null or
+/// point to `option_count` valid C strings.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn ratatui_render_menu(
+    title: *const c_char,
+    options: *const *const c_char,
+    option_count: i32,
+    selected: i32,
+) -> bool {
+    ffi_guard(false, || {
+        let (title, options) = unsafe { menu_strings(title, options, option_count) }?;
+        renderer::with_global(|r| r.render_menu(&title, &options, selected)).map(|_| true)
+    })
+}
+
+/// Pace frames at `fps` frames per second, or stop pacing with 0
+/// Paced hosts call `ratatui_wait_frame` once per frame instead of sleeping,
+/// and `ratatui_poll_input` stops waiting for input, so input latency no
+/// longer depends on the frame rate. Unpaced by default.
+#[unsafe(no_mangle)]
+pub extern "C" fn ratatui_set_target_fps(fps: u32) -> bool {
+    ffi_guard(false, || renderer::with_global(|r| {
+        r.set_target_fps(fps);
+        Ok(true)
+    }))
+}
+
+/// Wait until the next frame is due at the target rate
+/// Input keeps being read while waiting and counts towards the next
+/// `ratatui_poll_input`. A host running late is not made to catch up: the
+/// next frame is due one period after this call. Returns at once while
+/// frames are not paced.
+#[unsafe(no_mangle)]
+pub extern "C" fn ratatui_wait_frame() -> bool {
+    ffi_guard(false, || renderer::with_global(Renderer::wait_frame).map(|_| true))
+}
+
+/// Draw at one of `quality_levels`
+/// With `adaptive`, `level` is the best quality allowed: the renderer drops
+/// a level (rain, then particles, then building windows) while game frames
+/// take longer than the frame budget to draw and flush, and raises it again
+/// once they take well under it. The budget is one frame at the target rate,
+/// or at 60 FPS while unpaced. An unknown level returns `false`.
+#[unsafe(no_mangle)]
+pub extern "C" fn ratatui_set_quality(level: i32, adaptive: bool) -> bool {
+    ffi_guard(false, || renderer::with_global(|r| r.set_quality(level, adaptive)).map(|_| true))
+}
+
+/// Copy the frame counter, measured frame cost, target rate and current
+/// quality into `stats`
+///
+/// # Safety
+/// `stats` must be null or point to a writable `FrameStats`.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn ratatui_frame_stats(stats: *mut FrameStats) -> bool {
+    ffi_guard(false, || {
+        let s = unsafe { stats.as_mut() }.ok_or_else(|| Error::null("stats"))?;
+        *s = renderer::with_global(|r| Ok(r.frame_stats()))?;
+        Ok(true)
+    })
+}
+
+/// Draw at one of `color_modes` from the next frame on
+/// Below `TRUECOLOR`, every RGB colour is replaced by the nearest entry of
+/// the 256-colour palette or of the 16 basic colours. `AUTO`, the default,
+/// detects the depth from `COLORTERM` and `TERM`; headless renderers always
+/// detect `TRUECOLOR`. An unknown mode returns `false`.
+#[unsafe(no_mangle)]
+pub extern "C" fn ratatui_set_color_mode(mode: i32) -> bool {
+    ffi_guard(false, || renderer::with_global(|r| r.set_color_mode(mode)).map(|_| true))
+}
+
+/// Colour depth frames are drawn at, one of `color_modes` other than `AUTO`
+/// Returns -1 when no renderer is active.
+#[unsafe(no_mangle)]
+pub extern "C" fn ratatui_color_mode() -> i32 {
+    ffi_guard(-1, || renderer::with_global(|r| Ok(r.color_mode())))
+}
+
+/// Copy the last headless frame into `buf` as NUL-terminated UTF-8 text
+/// Rows are separated by `\n`. Returns the full text length in bytes (without
+/// the NUL), so a call with `len == 0` can be used to size the buffer.
+/// Returns -1 when no headless renderer is active.
+///
+/// # Safety
+/// `buf` must be null or point to at least `len` writable bytes.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn ratatui_snapshot_text(buf: *mut c_char, len: usize) -> i32 {
+    ffi_guard(-1, || {
+        let text = renderer::with_global(Renderer::snapshot_text)?;
+        Ok(unsafe { copy_text(&text, buf, len) })
+    })
+}
+
+/// Copy the styles of the last headless frame into `cells` in row-major order
+/// Returns the total number of cells (width * height); at most `capacity`
+/// entries are written. Returns -1 when no headless renderer is active.
+///
+/// # Safety
+/// `cells` must be null or point to at least `capacity` writable `CellStyle`s.
 #[unsafe(no_mangle)]
-pub extern "C" fn ratatui_render(state: *const GameState) -> bool {
-    if state.is_null() {
-        return false;
+pub unsafe extern "C" fn ratatui_snapshot_styles(cells: *mut CellStyle, capacity: usize) -> i32 {
+    ffi_guard(-1, || {
+        let styles = renderer::with_global(Renderer::snapshot_styles)?;
+        Ok(unsafe { copy_styles(&styles, cells, capacity) })
+    })
+}
+
+// ---------------------------------------------------------------------------
+// Marshalling helpers
+// ---------------------------------------------------------------------------
+
+/// Convert the C menu strings, rejecting null pointers and invalid UTF-8
+unsafe fn menu_strings(
+    title: *const c_char,
+    options: *const *const c_char,
+    option_count: i32,
+) -> Result<(String, Vec<String>)> {
+    if title.is_null() {
+        return Err(Error::null("title"));
+    }
+    if options.is_null() && option_count > 0 {
+        return Err(Error::null("options"));
     }
     
-    let game_state = unsafe { &*state };
+    let title_str = unsafe { utf8(title, "menu title") }?;
     
-    let terminal = match io::terminal::get_terminal() {
-        Some(t) => t,
-        None => return false,
-    };
+    let mut menu_options = Vec::new();
+    for i in 0..option_count.max(0) {
+        let option_ptr = unsafe { *options.offset(i as isize) };
+        if option_ptr.is_null() {
+            return Err(Error::null(&format!("options[{}]", i)));
+        }
+        menu_options.push(unsafe { utf8(option_ptr, &format!("menu option {}", i)) }?);
+    }
     
-    let result = terminal.draw(|f| {
-        let size = f.area();
-        
-        // Route to appropriate render function based on game mode
-        match game_state.game_mode {
-            game_modes::SPLIT_SCREEN if game_state.player2_active => {
-                render_splitscreen(f, size, game_state);
-            },
-            game_modes::CAREER => {
-                render_career_mode(f, size, game_state);
-            },
-            game_modes::REPLAY => {
-                render_replay_mode(f, size, game_state);
-            },
-            _ => {
-                render_singleplayer(f, size, game_state);
-            },
+    Ok((title_str, menu_options))
+}
+
+/// Read a non-null C string as UTF-8
+unsafe fn utf8(ptr: *const c_char, what: &str) -> Result<String> {
+    unsafe { CStr::from_ptr(ptr) }
+        .to_str()
+        .map(str::to_string)
+        .map_err(|e| Error::new(ErrorCode::InvalidUtf8, format!("{} is not valid UTF-8: {}", what, e)))
+}
+
+/// View a C array as a slice; null is only accepted for an empty array
+unsafe fn ffi_slice<'a, T>(ptr: *const T, len: usize) -> Option<&'a [T]> {
+    if len == 0 {
+        Some(&[])
+    } else if ptr.is_null() {
+        None
+    } else {
+        Some(unsafe { std::slice::from_raw_parts(ptr, len) })
+    }
+}
+
+/// Copy `text` into a NUL-terminated C buffer, returning the full length
+unsafe fn copy_text(text: &str, buf: *mut c_char, len: usize) -> i32 {
+    let bytes = text.as_bytes();
+    
+    if !buf.is_null() && len > 0 {
+        let copied = bytes.len().min(len - 1);
+        unsafe {
+            std::ptr::copy_nonoverlapping(bytes.as_ptr(), buf as *mut u8, copied);
+            *buf.add(copied) = 0;
         }
-    });
+    }
+    
+    bytes.len() as i32
+}
+
+/// Copy cell styles into a C array, returning the full cell count
+unsafe fn copy_styles(styles: &[CellStyle], cells: *mut CellStyle, capacity: usize) -> i32 {
+    if !cells.is_null() {
+        let copied = styles.len().min(capacity);
+        unsafe { std::ptr::copy_nonoverlapping(styles.as_ptr(), cells, copied); }
+    }
+    
+    styles.len() as i32
+}
+
+// ---------------------------------------------------------------------------
+// Game mode layouts
+// ---------------------------------------------------------------------------
+
+/// Render one full game frame, routing on the game mode
+/// Terminals below the mode's minimum size get a resize prompt instead
+pub fn render_game(f: &mut ratatui::Frame, state: &GameState) {
+    render_game_with(f, state, &rendering::Scene::default());
+}
+
+/// Render one full game frame at the time and with the particles of `scene`
+/// `render_game()` draws a still frame: nothing animated, no particles.
+pub fn render_game_with(f: &mut ratatui::Frame, state: &GameState, scene: &rendering::Scene) {
+    let size = f.area();
+    
+    if !rendering::size::fits(size, state) {
+        rendering::size::render_too_small(f, size, state);
+        return;
+    }
     
-    result.is_ok()
+    match state.game_mode {
+        game_modes::SPLIT_SCREEN if state.player2_active => {
+            render_splitscreen(f, size, state, scene);
+        },
+        game_modes::CAREER => {
+            render_career_mode(f, size, state, scene);
+        },
+        game_modes::REPLAY => {
+            render_replay_mode(f, size, state, scene);
+        },
+        _ => {
+            render_singleplayer(f, size, state, scene);
+        },
+    }
 }
 
 /// Render single-player game mode
-fn render_singleplayer(f: &mut ratatui::Frame, area: ratatui::layout::Rect, state: &GameState) {
+fn render_singleplayer(
+    f: &mut ratatui::Frame,
+    area: ratatui::layout::Rect,
+    state: &GameState,
+    scene: &rendering::Scene,
+) {
     use ratatui::layout::{Constraint, Direction, Layout};
     
     let chunks = Layout::default()
@@ -88,12 +831,18 @@ fn render_singleplayer(f: &mut ratatui::Frame, area: ratatui::layout::Rect, stat
         ])
         .split(area);
     
-    rendering::hud::render_enhanced_hud(f, chunks[0], state);
-    rendering::track::render_track(f, chunks[1], state, state.player_position, state.player_distance, true);
+    rendering::hud::render_enhanced_hud(f, chunks[0], state, scene.time());
+    rendering::track::render_track(f, chunks[1], state, state.player_position, state.player_distance, true, scene);
+    rendering::hud::render_controls(f, chunks[2], state.game_mode, &io::bindings::active());
 }
 
 /// Render split-screen multiplayer mode
-fn render_splitscreen(f: &mut ratatui::Frame, area: ratatui::layout::Rect, state: &GameState) {
+fn render_splitscreen(
+    f: &mut ratatui::Frame,
+    area: ratatui::layout::Rect,
+    state: &GameState,
+    scene: &rendering::Scene,
+) {
     use ratatui::layout::{Constraint, Direction, Layout};
     
     let h_chunks = Layout::default()
@@ -108,7 +857,8 @@ fn render_splitscreen(f: &mut ratatui::Frame, area: ratatui::layout::Rect, state
         .split(h_chunks[0]);
     
     rendering::hud::render_player_hud(f, p1_chunks[0], state, 1);
-    rendering::track::render_track(f, p1_chunks[1], state, state.player_position, state.player_distance, true);
+    rendering::track::render_track(f, p1_chunks[1], state, state.player_position, state.player_distance, true, scene);
+    rendering::hud::render_player_controls(f, p1_chunks[2], 1, &io::bindings::active());
     
     // Player 2 side
     let p2_chunks = Layout::default()
@@ -117,11 +867,17 @@ fn render_splitscreen(f: &mut ratatui::Frame, area: ratatui::layout::Rect, state
         .split(h_chunks[1]);
     
     rendering::hud::render_player_hud(f, p2_chunks[0], state, 2);
-    rendering::track::render_track(f, p2_chunks[1], state, state.player2_position, state.player2_distance, false);
+    rendering::track::render_track(f, p2_chunks[1], state, state.player2_position, state.player2_distance, false, scene);
+    rendering::hud::render_player_controls(f, p2_chunks[2], 2, &io::bindings::active());
 }
 
 /// Render career mode with progression tracking
-fn render_career_mode(f: &mut ratatui::Frame, area: ratatui::layout::Rect, state: &GameState) {
+fn render_career_mode(
+    f: &mut ratatui::Frame,
+    area: ratatui::layout::Rect,
+    state: &GameState,
+    scene: &rendering::Scene,
+) {
     use ratatui::layout::{Constraint, Direction, Layout};
     
     let chunks = Layout::default()
@@ -133,58 +889,24 @@ fn render_career_mode(f: &mut ratatui::Frame, area: ratatui::layout::Rect, state
         ])
         .split(area);
     
-    rendering::hud::render_enhanced_hud(f, chunks[0], state);
-    rendering::track::render_track(f, chunks[1], state, state.player_position, state.player_distance, true);
+    rendering::hud::render_enhanced_hud(f, chunks[0], state, scene.time());
+    rendering::track::render_track(f, chunks[1], state, state.player_position, state.player_distance, true, scene);
     rendering::hud::render_career_info(f, chunks[2], state);
 }
 
 /// Render replay mode with playback controls
-fn render_replay_mode(f: &mut ratatui::Frame, area: ratatui::layout::Rect, state: &GameState) {
-    use ratatui::layout::{Constraint, Direction, Layout};
+fn render_replay_mode(
+    f: &mut ratatui::Frame,
+    area: ratatui::layout::Rect,
+    state: &GameState,
+    scene: &rendering::Scene,
+) {
+    let chunks = rendering::hud::replay_layout(area);
     
-    let chunks = Layout::default()
-        .direction(Direction::Vertical)
-        .constraints([
-            Constraint::Length(3),   // Replay controls
-            Constraint::Min(0),      // Game area
-            Constraint::Length(2),   // Replay info
-        ])
-        .split(area);
-    
-    rendering::hud::render_replay_controls(f, chunks[0]);
-    rendering::track::render_track(f, chunks[1], state, state.player_position, state.player_distance, true);
+    rendering::hud::render_replay_controls(f, chunks[0], &io::bindings::active());
+    rendering::track::render_track(f, chunks[1], state, state.player_position, state.player_distance, true, scene);
     rendering::hud::render_replay_info(f, chunks[2], state);
 }
 
-/// Render menu with title and options
-#[unsafe(no_mangle)]
-pub extern "C" fn ratatui_render_menu(
-    title: *const std::os::raw::c_char,
-    options: *const *const std::os::raw::c_char,
-    option_count: i32,
-    selected: i32,
-) -> bool {
-    let terminal = match io::terminal::get_terminal() {
-        Some(t) => t,
-        None => return false,
-    };
-    
-    let result = terminal.draw(|f| {
-        // Convert C strings to Rust strings
-        let title_str = unsafe { std::ffi::CStr::from_ptr(title).to_str().unwrap_or("MENU") };
-        
-        let mut menu_options = Vec::new();
-        for i in 0..option_count {
-            let option_ptr = unsafe { *options.offset(i as isize) };
-            let option_str = unsafe { std::ffi::CStr::from_ptr(option_ptr).to_str().unwrap_or("") };
-            menu_options.push(option_str.to_string());
-        }
-        
-        rendering::hud::render_menu(f, title_str, &menu_options, selected);
-    });
-    
-    result.is_ok()
-}
-
 // All rendering functions have been moved to dedicated modules
 // See: track.rs, hud.rs, objects.rs, effects.rs, utils.rs, input.rs, terminal.rs
@@ -0,0 +1,378 @@
+//! Asset registry - the one place the track, object and HUD renderers take
+//! their sprites, icons and colors from
+//! Built from the "classic" tables in `core::utils` or the "detailed" tables
+//! in `visual_assets`, optionally overlaid with an asset pack, and switchable
+//! at runtime like the key bindings
+
+use std::collections::BTreeMap;
+use std::sync::{Arc, LazyLock, PoisonError, RwLock};
+use ratatui::style::Color;
+use unicode_width::UnicodeWidthStr;
+use crate::core::error::{Error, ErrorCode, Result};
+use crate::core::types::{asset_styles, obstacle_types};
+use crate::core::utils::{get_building_style, get_car_design, get_road_char};
+use super::visual_assets::{
+    get_boost_animation, get_building, get_cactus, get_detailed_car, get_hud_style, get_mountain,
+    get_powerup_visual, get_road_marking, get_shield_animation, get_wheel_animation,
+};
+
+/// Built-in car types; asset packs may add more
+pub const CAR_TYPES: i32 = 10;
+
+/// Widest full-size car sprite, in columns
+pub const MAX_SPRITE_WIDTH: u16 = 9;
+
+/// Tallest full-size car sprite, in rows
+pub const MAX_SPRITE_HEIGHT: u16 = 4;
+
+/// Rows of text drawn as one picture
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Sprite {
+    pub rows: Vec<String>,
+    /// Widest row in terminal columns
+    pub width: u16,
+}
+
+impl Sprite {
+    pub fn new(rows: &[&str]) -> Self {
+        let width = rows.iter().map(|row| row.width()).max().unwrap_or(0) as u16;
+        Sprite { rows: rows.iter().map(|row| row.to_string()).collect(), width }
+    }
+
+    pub fn height(&self) -> u16 {
+        self.rows.len() as u16
+    }
+
+    /// Check the sprite fits in `max_width` x `max_height` cells
+    pub fn check(&self, max_width: u16, max_height: u16) -> std::result::Result<(), String> {
+        if self.rows.is_empty() || self.width == 0 {
+            return Err("sprite is empty".into());
+        }
+        if let Some(row) = self.rows.iter().find(|row| row.chars().any(char::is_control)) {
+            return Err(format!("row {:?} contains a control character", row));
+        }
+        if self.height() > max_height {
+            return Err(format!("sprite has {} rows, at most {} allowed", self.height(), max_height));
+        }
+        if self.width > max_width {
+            return Err(format!("sprite is {} columns wide, at most {} allowed", self.width, max_width));
+        }
+        Ok(())
+    }
+}
+
+/// A single symbol with its color, e.g. a cactus or a HUD title
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Glyph {
+    pub symbol: String,
+    pub color: Color,
+    /// Width of `symbol` in terminal columns
+    pub width: u16,
+}
+
+impl Glyph {
+    pub fn new(symbol: &str, color: Color) -> Self {
+        Glyph { symbol: symbol.to_string(), color, width: symbol.width() as u16 }
+    }
+
+    /// Check the symbol is between 1 and `max_width` columns wide
+    pub fn check(&self, max_width: u16) -> std::result::Result<(), String> {
+        if self.symbol.chars().any(char::is_control) {
+            return Err(format!("{:?} contains a control character", self.symbol));
+        }
+        if self.width == 0 || self.width > max_width {
+            return Err(format!("{:?} is {} columns wide, 1 to {} allowed", self.symbol, self.width, max_width));
+        }
+        Ok(())
+    }
+}
+
+/// Symbols cycled through on the renderer's frame clock
+#[derive(Clone, Debug, PartialEq)]
+pub struct Animation {
+    pub frames: Vec<String>,
+    /// Seconds each frame is shown for
+    pub step: f32,
+}
+
+impl Animation {
+    pub fn new(frames: &[&str], step: f32) -> Self {
+        Animation { frames: frames.iter().map(|f| f.to_string()).collect(), step }
+    }
+
+    /// Frame shown `elapsed` seconds into the animation
+    pub fn frame_at(&self, elapsed: f32) -> &str {
+        let index = (elapsed.max(0.0) / self.step) as usize % self.frames.len();
+        &self.frames[index]
+    }
+}
+
+/// A car at the three sprite sizes of the road projection
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CarAsset {
+    pub large: Sprite,
+    pub medium: Sprite,
+    pub small: Sprite,
+    pub color: Color,
+    pub label: String,
+}
+
+/// Powerup or obstacle icon
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PowerupAsset {
+    pub icon: Glyph,
+    pub name: String,
+}
+
+/// Wall pattern of a building; cut to the building's width
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BuildingAsset {
+    pub wall: String,
+    pub color: Color,
+}
+
+/// Titles and colors of the HUD panels
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct HudAssets {
+    /// Prefix of the score counter
+    pub score: Glyph,
+    pub speed: Glyph,
+    pub boost: Glyph,
+    pub shield: Glyph,
+    pub star: Glyph,
+    pub magnet: Glyph,
+}
+
+/// Effects drawn around the player car
+#[derive(Clone, Debug, PartialEq)]
+pub struct Animations {
+    pub boost: Animation,
+    pub shield: Animation,
+    /// Spinning wheels in the bottom corners of the car, if the style has them
+    pub wheel: Option<Animation>,
+}
+
+/// Everything the renderers draw that is not layout
+/// Fields are visible to `pack`, which overlays asset pack entries.
+#[derive(Clone, Debug, PartialEq)]
+pub struct AssetRegistry {
+    pub(super) style: i32,
+    /// By car type
+    pub(super) cars: BTreeMap<i32, CarAsset>,
+    /// Drawn for car types without an entry
+    pub(super) generic: CarAsset,
+    pub(super) boss: CarAsset,
+    /// By obstacle type
+    pub(super) powerups: BTreeMap<i32, PowerupAsset>,
+    /// Drawn for obstacle types without an entry
+    pub(super) unknown: PowerupAsset,
+    /// By building type; type 0 is the plain building
+    pub(super) buildings: BTreeMap<i32, BuildingAsset>,
+    /// Road surface character, indexed by weather
+    pub(super) road: [String; 4],
+    pub(super) cactus: Glyph,
+    pub(super) peak: Glyph,
+    pub(super) hud: HudAssets,
+    pub(super) animations: Animations,
+}
+
+impl AssetRegistry {
+    /// Registry for one of `asset_styles`
+    pub fn for_style(style: i32) -> Result<Self> {
+        match style {
+            asset_styles::CLASSIC => Ok(Self::classic()),
+            asset_styles::DETAILED => Ok(Self::detailed()),
+            _ => Err(Error::new(ErrorCode::InvalidArgument, format!("unknown asset style {}", style))),
+        }
+    }
+
+    /// 4-line car art with scaled-down variants, the default look
+    pub fn classic() -> Self {
+        let car = |car_type, is_boss| {
+            let design = get_car_design(car_type, is_boss);
+            CarAsset {
+                large: Sprite::new(&design.art),
+                medium: Sprite::new(&design.medium),
+                small: Sprite::new(&[design.small]),
+                color: design.color,
+                label: design.label.to_string(),
+            }
+        };
+        let buildings = (0..=3)
+            .map(|btype| {
+                let (wall, color) = get_building_style(btype);
+                (btype, BuildingAsset { wall: wall.to_string(), color })
+            })
+            .collect();
+
+        AssetRegistry {
+            style: asset_styles::CLASSIC,
+            cars: (0..CAR_TYPES).map(|t| (t, car(t, false))).collect(),
+            generic: car(-1, false),
+            boss: car(0, true),
+            powerups: powerups(),
+            unknown: powerup(-1),
+            buildings,
+            road: std::array::from_fn(|weather| get_road_char(weather as i32).to_string()),
+            cactus: Glyph::new("🌵", Color::Green),
+            peak: Glyph::new("▲", Color::Rgb(100, 100, 100)),
+            hud: HudAssets {
+                score: Glyph::new("⭐", Color::Yellow),
+                speed: Glyph::new("🏎SPD", Color::Cyan),
+                boost: Glyph::new("⚡BOOST", Color::Magenta),
+                shield: Glyph::new("🛡SHIELD", Color::Cyan),
+                star: Glyph::new("⭐STAR", Color::Yellow),
+                magnet: Glyph::new("🧲MAG", Color::Green),
+            },
+            animations: Animations {
+                boost: Animation::new(&["🔥"], 1.0),
+                shield: Animation::new(&["◯"], 1.0),
+                wheel: None,
+            },
+        }
+    }
+
+    /// 3-line boxed cars, environment emoji and framed HUD titles
+    pub fn detailed() -> Self {
+        let car = |car_type, is_boss| {
+            let design = get_detailed_car(car_type, is_boss);
+            CarAsset {
+                large: Sprite::new(&[design.top, design.middle, design.bottom]),
+                medium: Sprite::new(&[design.middle]),
+                small: Sprite::new(&["■"]),
+                color: design.color,
+                label: design.label.to_string(),
+            }
+        };
+        let buildings = (0..=3)
+            .map(|btype| {
+                let wall = get_building_style(btype).0.to_string();
+                (btype, BuildingAsset { wall, color: get_building(btype).color })
+            })
+            .collect();
+        let hud = |element: i32, color| {
+            let title = get_hud_style(element).border_mid.trim_matches(|c: char| c == '║' || c == ' ');
+            Glyph::new(title, color)
+        };
+        let gauge = |ptype| {
+            let visual = get_powerup_visual(ptype);
+            Glyph::new(visual.name, visual.color)
+        };
+        let (cactus, mountain) = (get_cactus(), get_mountain());
+
+        AssetRegistry {
+            style: asset_styles::DETAILED,
+            cars: (0..CAR_TYPES).map(|t| (t, car(t, false))).collect(),
+            generic: car(-1, false),
+            boss: car(0, true),
+            powerups: powerups(),
+            unknown: powerup(-1),
+            buildings,
+            road: std::array::from_fn(|weather| get_road_marking(weather as i32).solid.to_string()),
+            cactus: Glyph::new(cactus.symbol, cactus.color),
+            peak: Glyph::new(mountain.symbol, mountain.color),
+            hud: HudAssets {
+                score: hud(0, Color::Yellow),
+                speed: hud(2, Color::Cyan),
+                boost: gauge(obstacle_types::BOOST),
+                shield: Glyph::new("SHIELD", Color::Cyan),
+                star: gauge(obstacle_types::STAR),
+                magnet: gauge(obstacle_types::MAGNET),
+            },
+            animations: Animations {
+                boost: Animation::new(&(0..3).map(get_boost_animation).collect::<Vec<_>>(), 0.1),
+                shield: Animation::new(&(0..2).map(get_shield_animation).collect::<Vec<_>>(), 0.25),
+                wheel: Some(detailed_wheel()),
+            },
+        }
+    }
+
+    /// One of `asset_styles`
+    pub fn style(&self) -> i32 {
+        self.style
+    }
+
+    /// Car of `car_type`; unknown types get the generic car
+    pub fn car(&self, car_type: i32, is_boss: bool) -> &CarAsset {
+        if is_boss {
+            return &self.boss;
+        }
+        self.cars.get(&car_type).unwrap_or(&self.generic)
+    }
+
+    /// Car types with their own entry, in order
+    pub fn car_types(&self) -> impl Iterator<Item = i32> + '_ {
+        self.cars.keys().copied()
+    }
+
+    /// Icon of an obstacle or powerup; unknown types get a warning sign
+    pub fn powerup(&self, ptype: i32) -> &PowerupAsset {
+        self.powerups.get(&ptype).unwrap_or(&self.unknown)
+    }
+
+    /// Building of `btype`; unknown types get the plain building
+    pub fn building(&self, btype: i32) -> &BuildingAsset {
+        self.buildings.get(&btype).unwrap_or(&self.buildings[&0])
+    }
+
+    /// Road surface character for `weather`
+    pub fn road(&self, weather: i32) -> &str {
+        usize::try_from(weather).ok().and_then(|w| self.road.get(w)).unwrap_or(&self.road[0])
+    }
+
+    pub fn cactus(&self) -> &Glyph {
+        &self.cactus
+    }
+
+    pub fn peak(&self) -> &Glyph {
+        &self.peak
+    }
+
+    pub fn hud(&self) -> &HudAssets {
+        &self.hud
+    }
+
+    pub fn animations(&self) -> &Animations {
+        &self.animations
+    }
+}
+
+impl Default for AssetRegistry {
+    fn default() -> Self {
+        Self::classic()
+    }
+}
+
+/// Powerup icons shared by both styles
+fn powerups() -> BTreeMap<i32, PowerupAsset> {
+    (obstacle_types::CONE..=obstacle_types::CLOCK).map(|ptype| (ptype, powerup(ptype))).collect()
+}
+
+fn powerup(ptype: i32) -> PowerupAsset {
+    let visual = get_powerup_visual(ptype);
+    PowerupAsset { icon: Glyph::new(visual.icon, visual.color), name: visual.name.to_string() }
+}
+
+static ACTIVE: LazyLock<RwLock<Arc<AssetRegistry>>> = LazyLock::new(|| RwLock::new(Arc::new(AssetRegistry::default())));
+
+/// The registry frames are drawn from
+pub fn active() -> Arc<AssetRegistry> {
+    ACTIVE.read().unwrap_or_else(PoisonError::into_inner).clone()
+}
+
+/// Replace the active registry; frames already being drawn keep the old one
+pub fn set_active(registry: AssetRegistry) {
+    *ACTIVE.write().unwrap_or_else(PoisonError::into_inner) = Arc::new(registry);
+}
+
+/// Switch to one of the built-in `asset_styles`
+pub fn set_style(style: i32) -> Result<()> {
+    set_active(AssetRegistry::for_style(style)?);
+    Ok(())
+}
+
+/// Wheel animation of the detailed style, also the base for packs that add
+/// wheels to a style without them
+pub(super) fn detailed_wheel() -> Animation {
+    Animation::new(&(0..4).map(get_wheel_animation).collect::<Vec<_>>(), 0.08)
+}
@@ -0,0 +1,143 @@
+//! Colour depth detection and palette quantization
+//! Frames are drawn with RGB colours throughout. On terminals without 24-bit
+//! colour every RGB colour of the finished frame is replaced by the nearest
+//! entry of the xterm 256-colour palette, or of the 16 basic colours.
+
+use ratatui::{buffer::Buffer, style::Color};
+use crate::core::error::{Error, ErrorCode, Result};
+use crate::core::types::color_modes;
+
+/// Channel values of the 6x6x6 colour cube at palette indices 16-231
+const CUBE_LEVELS: [u8; 6] = [0, 95, 135, 175, 215, 255];
+
+/// First index of the 24-step grey ramp (8, 18, ... 238)
+const GREY_RAMP: u8 = 232;
+
+/// The 16 basic colours with their xterm default RGB values
+const BASIC: [(Color, [u8; 3]); 16] = [
+    (Color::Black, [0, 0, 0]),
+    (Color::Red, [205, 0, 0]),
+    (Color::Green, [0, 205, 0]),
+    (Color::Yellow, [205, 205, 0]),
+    (Color::Blue, [0, 0, 238]),
+    (Color::Magenta, [205, 0, 205]),
+    (Color::Cyan, [0, 205, 205]),
+    (Color::Gray, [229, 229, 229]),
+    (Color::DarkGray, [127, 127, 127]),
+    (Color::LightRed, [255, 0, 0]),
+    (Color::LightGreen, [0, 255, 0]),
+    (Color::LightYellow, [255, 255, 0]),
+    (Color::LightBlue, [92, 92, 255]),
+    (Color::LightMagenta, [255, 0, 255]),
+    (Color::LightCyan, [0, 255, 255]),
+    (Color::White, [255, 255, 255]),
+];
+
+/// Colour depth from the terminal's `COLORTERM` and `TERM` values
+/// `COLORTERM=truecolor` or `24bit`, or a `*-direct` / `*truecolor` TERM,
+/// give TRUECOLOR; a `*256color` TERM gives ANSI_256, and anything else
+/// ANSI_16. Windows consoles set neither variable but have taken 24-bit
+/// colour since Windows 10, so no TERM at all means TRUECOLOR there.
+pub fn detect(colorterm: Option<&str>, term: Option<&str>) -> i32 {
+    if colorterm.is_some_and(|c| matches!(c.to_ascii_lowercase().as_str(), "truecolor" | "24bit")) {
+        return color_modes::TRUECOLOR;
+    }
+    match term.map(str::to_ascii_lowercase) {
+        Some(term) if term.ends_with("-direct") || term.contains("truecolor") || term.contains("24bit") => {
+            color_modes::TRUECOLOR
+        },
+        Some(term) if term.contains("256color") => color_modes::ANSI_256,
+        None if cfg!(windows) => color_modes::TRUECOLOR,
+        _ => color_modes::ANSI_16,
+    }
+}
+
+/// Colour depth of the terminal the process runs in
+pub fn detect_terminal() -> i32 {
+    let colorterm = std::env::var("COLORTERM").ok();
+    let term = std::env::var("TERM").ok();
+    detect(colorterm.as_deref(), term.as_deref())
+}
+
+/// Resolve one of `color_modes` to the depth frames are drawn at
+/// `AUTO` is detected for the real terminal and TRUECOLOR off-screen, so
+/// snapshots never depend on the environment. Fails on an unknown mode.
+pub fn resolve(mode: i32, is_tty: bool) -> Result<i32> {
+    match mode {
+        color_modes::AUTO if is_tty => Ok(detect_terminal()),
+        color_modes::AUTO => Ok(color_modes::TRUECOLOR),
+        color_modes::TRUECOLOR | color_modes::ANSI_256 | color_modes::ANSI_16 => Ok(mode),
+        _ => Err(Error::new(ErrorCode::InvalidArgument, format!("unknown color mode {}", mode))),
+    }
+}
+
+/// `color` as drawn at depth `mode`
+/// Named colours are left to the terminal's own theme; at ANSI_16, 256-colour
+/// palette entries are mapped down as well.
+pub fn quantize(color: Color, mode: i32) -> Color {
+    match (color, mode) {
+        (Color::Rgb(r, g, b), color_modes::ANSI_256) => Color::Indexed(nearest_256([r, g, b])),
+        (Color::Rgb(r, g, b), color_modes::ANSI_16) => nearest_16([r, g, b]),
+        (Color::Indexed(i), color_modes::ANSI_16) => match BASIC.get(i as usize) {
+            Some(&(basic, _)) => basic,
+            None => nearest_16(palette_rgb(i)),
+        },
+        _ => color,
+    }
+}
+
+/// Quantize the foreground and background of every cell in `buffer`
+pub fn quantize_buffer(buffer: &mut Buffer, mode: i32) {
+    if mode == color_modes::TRUECOLOR {
+        return;
+    }
+    for cell in &mut buffer.content {
+        cell.fg = quantize(cell.fg, mode);
+        cell.bg = quantize(cell.bg, mode);
+    }
+}
+
+/// Index of the nearest colour cube or grey ramp entry
+/// Indices 0-15 are left out, since terminal themes redefine them.
+pub fn nearest_256(rgb: [u8; 3]) -> u8 {
+    let level = |v: u8| match v {
+        0..48 => 0,
+        48..115 => 1,
+        _ => (v - 35) / 40,
+    };
+    let [r, g, b] = rgb.map(level);
+    let cube = 16 + 36 * r + 6 * g + b;
+
+    let mean = (rgb.iter().map(|&v| v as u32).sum::<u32>() / 3) as u8;
+    let grey = GREY_RAMP + (mean.saturating_sub(3) / 10).min(23);
+
+    if distance(rgb, palette_rgb(grey)) < distance(rgb, palette_rgb(cube)) {
+        grey
+    } else {
+        cube
+    }
+}
+
+/// The nearest of the 16 basic colours
+pub fn nearest_16(rgb: [u8; 3]) -> Color {
+    BASIC.iter().min_by_key(|(_, basic)| distance(rgb, *basic)).map_or(Color::Reset, |&(color, _)| color)
+}
+
+/// RGB value of a 256-colour palette index
+fn palette_rgb(index: u8) -> [u8; 3] {
+    match index {
+        0..16 => BASIC[index as usize].1,
+        16..GREY_RAMP => {
+            let i = index - 16;
+            [CUBE_LEVELS[(i / 36) as usize], CUBE_LEVELS[(i / 6 % 6) as usize], CUBE_LEVELS[(i % 6) as usize]]
+        },
+        _ => [8 + 10 * (index - GREY_RAMP); 3],
+    }
+}
+
+/// Squared distance between two colours, weighted towards green, to which
+/// the eye is most sensitive, and away from blue
+fn distance(a: [u8; 3], b: [u8; 3]) -> u32 {
+    let d = |i: usize| (a[i] as i32 - b[i] as i32).pow(2) as u32;
+    2 * d(0) + 4 * d(1) + 3 * d(2)
+}
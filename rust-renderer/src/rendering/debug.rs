@@ -0,0 +1,138 @@
+//! Debug performance overlay
+//! Shows what game frames cost, how much of each reached the terminal and
+//! how busy the scene is, for diagnosing stutter on slow terminals and over
+//! SSH. Off by default; toggled with `ratatui_set_debug_overlay` or F3, for
+//! every renderer at once.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use ratatui::{
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    text::Line,
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+use crate::core::types::{FrameStats, GameState};
+use super::pacing::smooth;
+
+/// Columns and rows of the overlay, borders included
+const OVERLAY_WIDTH: u16 = 26;
+const OVERLAY_HEIGHT: u16 = 9;
+
+/// Names of `quality_levels`, by level
+const QUALITY_NAMES: [&str; 4] = ["HIGH", "MEDIUM", "LOW", "MINIMAL"];
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+/// Whether game frames carry the overlay
+pub fn enabled() -> bool {
+    ENABLED.load(Ordering::SeqCst)
+}
+
+pub fn set_enabled(enabled: bool) {
+    ENABLED.store(enabled, Ordering::SeqCst);
+}
+
+/// Flip the overlay on or off, returning whether it is now on
+pub fn toggle() -> bool {
+    !ENABLED.fetch_xor(true, Ordering::SeqCst)
+}
+
+/// Frame rate and terminal output of the frames a renderer drew
+#[derive(Clone, Copy, Debug, Default)]
+pub struct FrameMeter {
+    /// Running average of the time between frames, in seconds
+    interval: Option<f32>,
+    cells: usize,
+}
+
+impl FrameMeter {
+    /// Record a frame drawn `delta` seconds after the previous one that
+    /// wrote `cells` cells to the terminal
+    /// The first frame has no delta and only counts its cells.
+    pub fn record(&mut self, delta: f32, cells: usize) {
+        if delta > 0.0 {
+            self.interval = Some(smooth(self.interval, delta));
+        }
+        self.cells = cells;
+    }
+
+    /// Frames per second, 0 before the second frame
+    pub fn fps(&self) -> f32 {
+        self.interval.map_or(0.0, |interval| 1.0 / interval)
+    }
+
+    /// Average time between frames in milliseconds
+    pub fn frame_ms(&self) -> f32 {
+        self.interval.unwrap_or(0.0) * 1000.0
+    }
+
+    /// Cells the last frame changed on the terminal
+    pub fn cells(&self) -> usize {
+        self.cells
+    }
+}
+
+/// Everything the overlay shows besides the terminal size
+#[derive(Clone, Copy, Debug, Default)]
+pub struct DebugStats {
+    pub fps: f32,
+    pub frame_ms: f32,
+    /// Average cost of drawing and flushing a game frame
+    pub draw_ms: f32,
+    pub cells: usize,
+    pub quality: i32,
+    pub adaptive: bool,
+    pub cars: i32,
+    pub obstacles: i32,
+    pub buildings: i32,
+}
+
+impl DebugStats {
+    /// Figures for the frame about to be drawn of `state`
+    /// Timings and cells are those of the frames already drawn.
+    pub fn new(meter: &FrameMeter, pacing: &FrameStats, state: &GameState) -> Self {
+        DebugStats {
+            fps: meter.fps(),
+            frame_ms: meter.frame_ms(),
+            draw_ms: pacing.average_ms,
+            cells: meter.cells(),
+            quality: pacing.quality,
+            adaptive: pacing.adaptive,
+            cars: state.car_count.max(0),
+            obstacles: state.obstacle_count.max(0),
+            buildings: state.building_count.max(0),
+        }
+    }
+}
+
+/// Draw the overlay in the top right corner of the frame
+pub fn render_overlay(f: &mut Frame, stats: &DebugStats) {
+    let area = f.area();
+    let width = OVERLAY_WIDTH.min(area.width);
+    let height = OVERLAY_HEIGHT.min(area.height);
+    let overlay = Rect::new(area.right() - width, area.y, width, height);
+
+    let quality = QUALITY_NAMES.get(stats.quality as usize).copied().unwrap_or("?");
+    let lines = vec![
+        Line::raw(format!("FPS     {:>6.1}", stats.fps)),
+        Line::raw(format!("Frame   {:>6.1} ms", stats.frame_ms)),
+        Line::raw(format!("Draw    {:>6.1} ms", stats.draw_ms)),
+        Line::raw(format!("Cells   {:>6}", stats.cells)),
+        Line::raw(format!("Objects {} / {} / {}", stats.cars, stats.obstacles, stats.buildings)),
+        Line::raw(format!("Size    {}x{}", area.width, area.height)),
+        Line::raw(format!("Quality {}{}", quality, if stats.adaptive { " auto" } else { "" })),
+    ];
+
+    f.render_widget(Clear, overlay);
+    f.render_widget(
+        Paragraph::new(lines).style(Style::default().fg(Color::White)).block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(Color::DarkGray))
+                .title("Debug")
+                .title_style(Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)),
+        ),
+        overlay,
+    );
+}
@@ -0,0 +1,82 @@
+//! Low-level drawing straight into the frame buffer
+//! The track, object and effect renderers write thousands of single glyphs a
+//! frame; setting cells directly avoids building a `Paragraph` (and often a
+//! `String`) for each one. Every call is clipped to a rectangle, so callers
+//! may pass positions that fall off its edges.
+//!
+//! Cells are written the way a one-line `Paragraph` writes them: the style is
+//! patched onto what is there. A wide glyph also clears the cells it hides, as
+//! `Buffer::set_stringn` does, so nothing drawn earlier resurfaces when it is
+//! overwritten, and gives them its style.
+
+use ratatui::{buffer::Buffer, layout::Rect, style::Style};
+use unicode_segmentation::UnicodeSegmentation;
+use unicode_width::UnicodeWidthStr;
+
+/// Write one glyph with its left edge at (`x`, `y`)
+/// Left out if any column of it falls outside `clip`.
+pub fn put(buf: &mut Buffer, clip: Rect, x: u16, y: u16, symbol: &str, style: Style) {
+    let clip = clip.intersection(buf.area);
+    let width = symbol.width() as u16;
+    if width == 0 || y < clip.top() || y >= clip.bottom() || x < clip.left() || x + width > clip.right() {
+        return;
+    }
+    set(buf, x, y, symbol, width, style);
+}
+
+/// Write `text` from (`x`, `y`) towards the right, one glyph after another
+/// Glyphs left of `clip` are skipped and the text stops at its right edge.
+/// Returns the column after the last glyph written.
+pub fn text(buf: &mut Buffer, clip: Rect, x: u16, y: u16, text: &str, style: Style) -> u16 {
+    let clip = clip.intersection(buf.area);
+    if y < clip.top() || y >= clip.bottom() {
+        return x;
+    }
+
+    let mut x = x;
+    for symbol in text.graphemes(true) {
+        let width = symbol.width() as u16;
+        if width == 0 {
+            continue;
+        }
+        if x + width > clip.right() {
+            break;
+        }
+        if x >= clip.left() {
+            set(buf, x, y, symbol, width, style);
+        }
+        x += width;
+    }
+    x
+}
+
+/// Repeat `symbol` across `count` glyphs from (`x`, `y`), clipped like `text`
+/// Returns the column after the last glyph written.
+pub fn fill(buf: &mut Buffer, clip: Rect, x: u16, y: u16, count: u16, symbol: &str, style: Style) -> u16 {
+    let clip = clip.intersection(buf.area);
+    let width = symbol.width() as u16;
+    if width == 0 || y < clip.top() || y >= clip.bottom() {
+        return x;
+    }
+
+    let mut x = x;
+    for _ in 0..count {
+        if x + width > clip.right() {
+            break;
+        }
+        if x >= clip.left() {
+            set(buf, x, y, symbol, width, style);
+        }
+        x += width;
+    }
+    x
+}
+
+/// Write a glyph already known to fit
+fn set(buf: &mut Buffer, x: u16, y: u16, symbol: &str, width: u16, style: Style) {
+    buf[(x, y)].set_symbol(symbol).set_style(style);
+    for hidden in x + 1..x + width {
+        buf[(hidden, y)].reset();
+        buf[(hidden, y)].set_style(style);
+    }
+}
@@ -1,83 +1,82 @@
-/// Visual effects module for weather, lane markers, and special effects
-/// Handles rain, fog, slowmo effects, and lane rendering
+//! Visual effects module for weather, lane markers, and special effects
+//! Handles rain, fog, slowmo effects, and lane rendering
 
 use ratatui::{
     layout::Rect,
-    style::{Color, Modifier, Style},
-    widgets::Paragraph,
+    style::{Color, Style},
     Frame,
 };
 use crate::core::types::GameState;
+use super::draw;
+use super::projection::Projection;
+use super::scene::{FrameTime, Scene};
 
-/// Render lane dividers with animation
-pub fn render_lane_markers(
-    f: &mut Frame,
-    area: Rect,
-    state: &GameState,
-    lane_width: u16,
-    height: u16,
-    curve: i16,
-) {
-    let offset = (state.player_distance as u16 * 2) % 6;
-    
-    for y in (0..height).step_by(3) {
-        let actual_y = (y + offset) % height;
-        
-        for lane in 1..3 {
-            let base_x = area.x + lane * lane_width;
-            let curved_x = (base_x as i16 + curve)
-                .max(area.x as i16)
-                .min((area.x + area.width) as i16) as u16;
-            
-            if actual_y + area.y < area.bottom() && curved_x < area.x + area.width {
-                f.render_widget(
-                    Paragraph::new("┃").style(Style::default().fg(Color::White)),
-                    Rect::new(curved_x, area.y + actual_y, 1, 2),
-                );
+/// Track distance covered by one lane dash and the gap after it
+const DASH_LENGTH: f32 = 3.0;
+
+/// Rows per second rain falls, whether or not the car is moving
+const RAIN_SPEED: f32 = 12.0;
+
+/// Render lane dividers converging towards the horizon in the track's
+/// marker style. Dashes are placed by track distance, so they shorten with
+/// depth and scroll as the player moves
+pub fn render_lane_markers(f: &mut Frame, projection: &Projection, player_dist: f32) {
+    let area = projection.area();
+    let marker = Style::default().fg(Color::White);
+    let buf = f.buffer_mut();
+
+    for y in projection.horizon()..area.bottom() {
+        let (Some(scale), Some(distance)) = (projection.row_scale(y), projection.distance_at(y)) else {
+            continue;
+        };
+        if ((distance + player_dist) / DASH_LENGTH).floor() as i64 % 2 != 0 {
+            continue;
+        }
+
+        for lane in 1..projection.lanes() {
+            if let Some(x) = projection.column(projection.lane_boundary(lane, scale)) {
+                draw::put(buf, area, x, y, projection.marker(), marker);
             }
         }
     }
 }
 
 /// Render weather effects (rain, fog) and special effects (slowmo)
-pub fn render_weather_overlay(f: &mut Frame, area: Rect, state: &GameState) {
+/// Rain falls on the scene clock and is left out below full quality
+pub fn render_weather_overlay(f: &mut Frame, area: Rect, state: &GameState, scene: &Scene) {
     match state.weather {
-        1 => render_rain(f, area, state),
+        1 if scene.effects().rain => render_rain(f, area, scene.time()),
         2 => {
             // Fog is mainly handled by background color
         },
         _ => {},
     }
-    
+
     if state.slowmo_active {
         render_slowmo_effect(f, area);
     }
 }
 
-/// Render rain effect with animated drops
-fn render_rain(f: &mut Frame, area: Rect, state: &GameState) {
+/// Render rain effect with drops falling on the frame clock
+fn render_rain(f: &mut Frame, area: Rect, time: FrameTime) {
     let spacing = 7;
-    let offset = (state.player_distance as u16) % spacing;
-    
+    let offset = ((time.elapsed * RAIN_SPEED) as u64 % area.height.max(1) as u64) as u16;
+    let drop = Style::default().fg(Color::Rgb(100, 150, 200));
+    let buf = f.buffer_mut();
+
     for x in (0..area.width).step_by(spacing as usize) {
         for y in (0..area.height).step_by(3) {
             let drop_y = (y + offset) % area.height;
-            if drop_y + area.y < area.bottom() {
-                f.render_widget(
-                    Paragraph::new("·").style(Style::default().fg(Color::Rgb(100, 150, 200))),
-                    Rect::new(area.x + x, area.y + drop_y, 1, 1),
-                );
-            }
+            draw::put(buf, area, area.x + x, area.y + drop_y, "·", drop);
         }
     }
 }
 
 /// Render slowmo motion blur effect
 fn render_slowmo_effect(f: &mut Frame, area: Rect) {
+    let blur = Style::default().fg(Color::Rgb(80, 80, 150));
+    let buf = f.buffer_mut();
     for y in (0..area.height).step_by(4) {
-        f.render_widget(
-            Paragraph::new("━").style(Style::default().fg(Color::Rgb(80, 80, 150))),
-            Rect::new(area.x + 2, area.y + y, 3, 1),
-        );
+        draw::put(buf, area, area.x + 2, area.y + y, "━", blur);
     }
 }
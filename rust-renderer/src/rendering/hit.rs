@@ -0,0 +1,71 @@
+//! Hit-testing module for mouse input
+//! Records where clickable elements were drawn so terminal cells can be
+//! mapped back to menu options and the replay timeline
+
+use ratatui::layout::{Position, Rect};
+use crate::core::types::{game_modes, GameState};
+use super::{hud, size};
+
+/// Element under a mouse position
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum HitTarget {
+    /// Menu option by index
+    MenuItem(usize),
+    /// Point on the replay timeline, 0.0 at the start to 1.0 at the end
+    ReplayTimeline(f32),
+}
+
+/// Clickable areas of the last drawn frame
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct HitMap {
+    /// Rows of the menu option list and the number of options
+    menu_items: Option<(Rect, usize)>,
+    /// Bar of the replay timeline
+    timeline: Option<Rect>,
+}
+
+impl HitMap {
+    /// Clickable areas of a menu drawn by `hud::render_menu`
+    pub fn for_menu(area: Rect, option_count: usize) -> Self {
+        let list = hud::menu_block().inner(hud::menu_layout(area)[1]);
+        HitMap { menu_items: Some((list, option_count)), timeline: None }
+    }
+
+    /// Clickable areas of a game frame drawn by `render_game`
+    /// Only replay mode has any: its timeline
+    pub fn for_game(area: Rect, state: &GameState) -> Self {
+        if state.game_mode != game_modes::REPLAY || !size::fits(area, state) {
+            return HitMap::default();
+        }
+        let timeline = hud::replay_info_block(state).inner(hud::replay_layout(area)[2]);
+        HitMap { menu_items: None, timeline: Some(timeline) }
+    }
+
+    /// Element at a terminal cell
+    pub fn hit(&self, column: u16, row: u16) -> Option<HitTarget> {
+        let position = Position::new(column, row);
+
+        if let Some((list, count)) = self.menu_items
+            && list.contains(position)
+        {
+            let index = (row - list.y) as usize;
+            return (index < count).then_some(HitTarget::MenuItem(index));
+        }
+
+        if let Some(bar) = self.timeline
+            && bar.contains(position)
+        {
+            return self.timeline_position(column).map(HitTarget::ReplayTimeline);
+        }
+
+        None
+    }
+
+    /// Timeline position of `column`, even outside the bar
+    /// Lets a drag that leaves the bar keep seeking to the nearest end
+    pub fn timeline_position(&self, column: u16) -> Option<f32> {
+        let bar = self.timeline?;
+        let span = bar.width.saturating_sub(1).max(1) as f32;
+        Some((column.saturating_sub(bar.x) as f32 / span).min(1.0))
+    }
+}
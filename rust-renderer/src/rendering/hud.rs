@@ -1,6 +1,7 @@
-/// HUD (Heads-Up Display) module for UI elements
-/// Handles rendering of gauges, stats, and game information
+//! HUD (Heads-Up Display) module for UI elements
+//! Handles rendering of gauges, stats, and game information
 
+use std::rc::Rc;
 use ratatui::{
     layout::{Alignment, Constraint, Direction, Layout, Rect},
     style::{Color, Modifier, Style},
@@ -9,9 +10,15 @@ use ratatui::{
 };
 use crate::core::types::GameState;
 use crate::core::utils::{get_combo_color, get_health_color};
+use crate::io::bindings::{self, Action, Bindings};
+use super::assets::{self, Glyph};
+use super::scene::FrameTime;
+
+/// Pulses per second of an active powerup gauge
+const GAUGE_PULSE: f32 = 2.0;
 
 /// Render single-player HUD with full stats
-pub fn render_singleplayer_hud(f: &mut Frame, area: Rect, state: &GameState) {
+pub fn render_singleplayer_hud(f: &mut Frame, area: Rect, state: &GameState, time: FrameTime) {
     let chunks = Layout::default()
         .direction(Direction::Vertical)
         .constraints([
@@ -21,9 +28,9 @@ pub fn render_singleplayer_hud(f: &mut Frame, area: Rect, state: &GameState) {
         ])
         .split(area);
     
-    render_enhanced_hud(f, chunks[0], state);
+    render_enhanced_hud(f, chunks[0], state, time);
     // Track rendering handled elsewhere
-    render_controls(f, chunks[2], state.game_mode);
+    render_controls(f, chunks[2], state.game_mode, &bindings::active());
 }
 
 /// Render split-screen multiplayer HUD
@@ -51,11 +58,11 @@ pub fn render_splitscreen_hud(f: &mut Frame, area: Rect, state: &GameState) {
     render_player_hud(f, p2_chunks[0], state, 2);
     // Track rendering handled elsewhere
     
-    render_controls(f, p1_chunks[2], 1);
+    render_controls(f, p1_chunks[2], 1, &bindings::active());
 }
 
 /// Render career mode HUD with progress tracking
-pub fn render_career_hud(f: &mut Frame, area: Rect, state: &GameState) {
+pub fn render_career_hud(f: &mut Frame, area: Rect, state: &GameState, time: FrameTime) {
     let chunks = Layout::default()
         .direction(Direction::Vertical)
         .constraints([
@@ -65,29 +72,62 @@ pub fn render_career_hud(f: &mut Frame, area: Rect, state: &GameState) {
         ])
         .split(area);
     
-    render_enhanced_hud(f, chunks[0], state);
+    render_enhanced_hud(f, chunks[0], state, time);
     // Track rendering handled elsewhere
     render_career_info(f, chunks[2], state);
 }
 
 /// Render replay mode HUD with playback controls
 pub fn render_replay_hud(f: &mut Frame, area: Rect, state: &GameState) {
-    let chunks = Layout::default()
+    let chunks = replay_layout(area);
+    
+    render_replay_controls(f, chunks[0], &bindings::active());
+    // Track rendering handled elsewhere
+    render_replay_info(f, chunks[2], state);
+}
+
+/// Split the replay screen into controls, game area and timeline
+/// Shared with mouse hit-testing, so clicks line up with what was drawn
+pub fn replay_layout(area: Rect) -> Rc<[Rect]> {
+    Layout::default()
         .direction(Direction::Vertical)
         .constraints([
             Constraint::Length(3),   // Replay controls
             Constraint::Min(0),      // Game area
             Constraint::Length(2),   // Replay info
         ])
-        .split(area);
-    
-    render_replay_controls(f, chunks[0]);
-    // Track rendering handled elsewhere
-    render_replay_info(f, chunks[2], state);
+        .split(area)
+}
+
+/// Split the menu screen into title, options and controls
+pub fn menu_layout(area: Rect) -> Rc<[Rect]> {
+    Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(5),
+            Constraint::Min(0),
+            Constraint::Length(3),
+        ])
+        .split(area)
+}
+
+/// Block around the replay timeline; its inner row is the seekable bar
+pub fn replay_info_block(state: &GameState) -> Block<'static> {
+    Block::default()
+        .borders(Borders::TOP)
+        .title(format!("Time: {:.2}s | Best: Ghost Car", state.lap_time))
+}
+
+/// Block around the menu options; each inner row is one option
+pub fn menu_block() -> Block<'static> {
+    Block::default().borders(Borders::ALL).title("Select Option")
 }
 
 /// Render enhanced HUD with all stats and gauges
-pub fn render_enhanced_hud(f: &mut Frame, area: Rect, state: &GameState) {
+/// Active powerup gauges pulse on the frame clock
+pub fn render_enhanced_hud(f: &mut Frame, area: Rect, state: &GameState, time: FrameTime) {
+    let assets = assets::active();
+    let hud = assets.hud();
     let rows = Layout::default()
         .direction(Direction::Vertical)
         .constraints([Constraint::Length(2), Constraint::Length(4)])
@@ -105,8 +145,8 @@ pub fn render_enhanced_hud(f: &mut Frame, area: Rect, state: &GameState) {
         .split(rows[0]);
     
     // Score
-    let score_text = Paragraph::new(format!("⭐ {:08}", state.player_score))
-        .style(Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD));
+    let score_text = Paragraph::new(format!("{} {:08}", hud.score.symbol, state.player_score))
+        .style(Style::default().fg(hud.score.color).add_modifier(Modifier::BOLD));
     f.render_widget(score_text, top[0]);
     
     // Combo
@@ -141,11 +181,11 @@ pub fn render_enhanced_hud(f: &mut Frame, area: Rect, state: &GameState) {
         ])
         .split(rows[1]);
     
-    render_speed_gauge(f, bottom[0], state.player_speed, state.boost_active);
-    render_powerup_gauge(f, bottom[1], "⚡BOOST", state.boost_remaining, state.boost_active, Color::Magenta);
-    render_powerup_gauge(f, bottom[2], "🛡SHIELD", state.shield_remaining, state.shield_active, Color::Cyan);
-    render_powerup_gauge(f, bottom[3], "⭐STAR", state.invincibility_remaining, state.invincibility_active, Color::Yellow);
-    render_powerup_gauge(f, bottom[4], "🧲MAG", state.magnet_remaining, state.magnet_active, Color::Green);
+    render_speed_gauge(f, bottom[0], &hud.speed.symbol, state.player_speed, state.boost_active);
+    render_powerup_gauge(f, bottom[1], &hud.boost, state.boost_remaining, state.boost_active, time);
+    render_powerup_gauge(f, bottom[2], &hud.shield, state.shield_remaining, state.shield_active, time);
+    render_powerup_gauge(f, bottom[3], &hud.star, state.invincibility_remaining, state.invincibility_active, time);
+    render_powerup_gauge(f, bottom[4], &hud.magnet, state.magnet_remaining, state.magnet_active, time);
 }
 
 /// Render individual player HUD for split-screen
@@ -185,7 +225,7 @@ pub fn render_player_hud(f: &mut Frame, area: Rect, state: &GameState, player: i
 }
 
 /// Render speed gauge with dynamic coloring
-fn render_speed_gauge(f: &mut Frame, area: Rect, speed: f32, boosting: bool) {
+fn render_speed_gauge(f: &mut Frame, area: Rect, title: &str, speed: f32, boosting: bool) {
     let max_speed = if boosting { 250.0 } else { 200.0 };
     let percent = (speed / max_speed * 100.0).min(100.0) as u16;
     let color = if boosting {
@@ -199,31 +239,27 @@ fn render_speed_gauge(f: &mut Frame, area: Rect, speed: f32, boosting: bool) {
     };
     
     let gauge = Gauge::default()
-        .block(Block::default().borders(Borders::ALL).title("🏎SPD"))
+        .block(Block::default().borders(Borders::ALL).title(title))
         .gauge_style(Style::default().fg(color))
         .percent(percent)
         .label(format!("{:.0}", speed));
     f.render_widget(gauge, area);
 }
 
-/// Render powerup duration gauge
-fn render_powerup_gauge(
-    f: &mut Frame,
-    area: Rect,
-    title: &str,
-    remaining: f32,
-    active: bool,
-    color: Color,
-) {
-    let percent = remaining.max(0.0).min(100.0) as u16;
-    let style = if active {
-        Style::default().fg(color).add_modifier(Modifier::RAPID_BLINK)
+/// Render powerup duration gauge, titled and colored by `title`
+fn render_powerup_gauge(f: &mut Frame, area: Rect, title: &Glyph, remaining: f32, active: bool, time: FrameTime) {
+    let color = title.color;
+    let percent = remaining.clamp(0.0, 100.0) as u16;
+    let style = if active && time.blink(GAUGE_PULSE) {
+        Style::default().fg(color).add_modifier(Modifier::BOLD)
+    } else if active {
+        Style::default().fg(color)
     } else {
         Style::default().fg(if percent > 0u16 { color } else { Color::DarkGray })
     };
     
     let gauge = Gauge::default()
-        .block(Block::default().borders(Borders::ALL).title(title))
+        .block(Block::default().borders(Borders::ALL).title(title.symbol.as_str()))
         .gauge_style(style)
         .percent(percent);
     f.render_widget(gauge, area);
@@ -256,8 +292,13 @@ pub fn render_career_info(f: &mut Frame, area: Rect, state: &GameState) {
 }
 
 /// Render replay mode playback controls
-pub fn render_replay_controls(f: &mut Frame, area: Rect) {
-    let text = Paragraph::new("⏮ [←] Rewind | [SPACE] Pause | [→] Fast Forward ⏭")
+pub fn render_replay_controls(f: &mut Frame, area: Rect, bindings: &Bindings) {
+    let text = Paragraph::new(format!(
+        "⏮ [{}] Rewind | [{}] Pause | [{}] Fast Forward ⏭",
+        bindings.label(Action::P1Left),
+        bindings.label(Action::P1Boost),
+        bindings.label(Action::P1Right)
+    ))
         .block(Block::default().borders(Borders::ALL).title("REPLAY MODE"))
         .style(Style::default().fg(Color::Magenta).add_modifier(Modifier::BOLD))
         .alignment(Alignment::Center);
@@ -265,24 +306,61 @@ pub fn render_replay_controls(f: &mut Frame, area: Rect) {
 }
 
 /// Render replay mode information
+/// The timeline shows how far through the replay playback is
 pub fn render_replay_info(f: &mut Frame, area: Rect, state: &GameState) {
-    let text = format!("Time: {:.2}s | Best: Ghost Car", state.lap_time);
-    let info = Paragraph::new(text)
-        .block(Block::default().borders(Borders::ALL))
-        .style(Style::default().fg(Color::Cyan));
-    f.render_widget(info, area);
+    let progress = if state.replay_progress.is_finite() { state.replay_progress.clamp(0.0, 1.0) } else { 0.0 };
+    let timeline = Gauge::default()
+        .block(replay_info_block(state))
+        .gauge_style(Style::default().fg(Color::Cyan).bg(Color::DarkGray))
+        .ratio(progress as f64)
+        .label(format!("{:.0}%", progress * 100.0));
+    f.render_widget(timeline, area);
 }
 
 /// Render control instructions based on game mode
-fn render_controls(f: &mut Frame, area: Rect, mode: i32) {
+/// Key names come from the active bindings so rebinding keeps them accurate
+pub fn render_controls(f: &mut Frame, area: Rect, mode: i32, bindings: &Bindings) {
     let text = match mode {
-        1 => "P1: WASD+SPACE | P2: IJKL+U | Q=Quit",
-        2 => "← → Move | ↑ Accel | ↓ Brake | SPACE Boost | M Menu | Q Quit",
-        _ => "← → Move | ↑ Accel | ↓ Brake | SPACE Boost | P Pause | Q Quit",
+        1 => format!(
+            "P1: {} | P2: {} | {} Quit",
+            bindings.driving_hints(1),
+            bindings.driving_hints(2),
+            bindings.label(Action::Quit)
+        ),
+        2 => format!(
+            "{} | {} Menu | {} Quit",
+            bindings.driving_hints(1),
+            bindings.label(Action::Menu),
+            bindings.label(Action::Quit)
+        ),
+        _ => format!(
+            "{} | {} Pause | {} Quit",
+            bindings.driving_hints(1),
+            bindings.label(Action::Pause),
+            bindings.label(Action::Quit)
+        ),
     };
-    
+    render_hint_line(f, area, text);
+}
+
+/// Render one player's controls in split-screen
+/// Player 2's side also lists the system keys
+pub fn render_player_controls(f: &mut Frame, area: Rect, player: i32, bindings: &Bindings) {
+    let mut text = format!("P{}: {}", player, bindings.driving_hints(player));
+    if player == 2 {
+        text.push_str(&format!(
+            " | {} Pause | {} Quit",
+            bindings.label(Action::Pause),
+            bindings.label(Action::Quit)
+        ));
+    }
+    render_hint_line(f, area, text);
+}
+
+/// Dimmed hint text under a top rule
+fn render_hint_line(f: &mut Frame, area: Rect, text: String) {
     let controls = Paragraph::new(text)
-        .block(Block::default().borders(Borders::ALL))
+        .block(Block::default().borders(Borders::TOP))
         .style(Style::default().fg(Color::DarkGray));
     f.render_widget(controls, area);
 }
@@ -294,16 +372,7 @@ pub fn render_menu(
     options: &[String],
     selected: i32,
 ) {
-    let size = f.area();
-    
-    let chunks = Layout::default()
-        .direction(Direction::Vertical)
-        .constraints([
-            Constraint::Length(5),
-            Constraint::Min(0),
-            Constraint::Length(3),
-        ])
-        .split(size);
+    let chunks = menu_layout(f.area());
     
     // Title
     let title_widget = Paragraph::new(title_str)
@@ -325,12 +394,18 @@ pub fn render_menu(
         items.push(ListItem::new(format!("{}{}", prefix, option_str)).style(style));
     }
     
-    let list = List::new(items)
-        .block(Block::default().borders(Borders::ALL).title("Select Option"));
+    let list = List::new(items).block(menu_block());
     f.render_widget(list, chunks[1]);
     
     // Controls
-    let controls = Paragraph::new("↑↓ Navigate | ENTER Select | Q Quit")
+    let bindings = bindings::active();
+    let controls = Paragraph::new(format!(
+        "{}{} Navigate | {} Select | {} Back",
+        bindings.label(Action::MenuUp),
+        bindings.label(Action::MenuDown),
+        bindings.label(Action::MenuConfirm),
+        bindings.label(Action::MenuBack)
+    ))
         .block(Block::default().borders(Borders::ALL))
         .style(Style::default().fg(Color::DarkGray));
     f.render_widget(controls, chunks[2]);
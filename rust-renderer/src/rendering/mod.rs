@@ -1,19 +1,35 @@
-/// Rendering module - All visual output and UI rendering
-/// Organized into specialized submodules for different rendering concerns
+//! Rendering module - All visual output and UI rendering
+//! Organized into specialized submodules for different rendering concerns
 
+pub mod assets;
+pub mod color;
+pub mod debug;
+pub mod draw;
 pub mod track;
 pub mod objects;
+pub mod pacing;
+pub mod pack;
+pub mod particles;
 pub mod effects;
+pub mod hit;
 pub mod hud;
+pub mod projection;
+pub mod reload;
+pub mod scene;
+pub mod size;
 pub mod visual_assets;
 
 pub use track::render_track;
 pub use objects::{render_objects, render_player, render_ghost};
 pub use effects::{render_lane_markers, render_weather_overlay};
+pub use pacing::{Effects, FramePacer};
+pub use particles::{render_particles, ParticleSystem};
+pub use scene::{FrameClock, FrameTime, Scene};
 pub use hud::{
     render_enhanced_hud, render_player_hud, render_career_info,
     render_replay_controls, render_replay_info, render_menu
 };
+pub use assets::{AssetRegistry, CarAsset, PowerupAsset, Sprite};
 pub use visual_assets::{
     get_detailed_car, get_powerup_visual, get_tree, get_building,
     get_cactus, get_mountain, get_road_marking, get_hud_style,
@@ -1,224 +1,208 @@
-/// Objects rendering module for cars, obstacles, and powerups
-/// Handles rendering of player cars, AI cars, and interactive objects
+//! Objects rendering module for cars, obstacles, and powerups
+//! Handles rendering of player cars, AI cars, and interactive objects
 
 use ratatui::{
-    layout::Rect,
     style::{Color, Modifier, Style},
-    widgets::Paragraph,
     Frame,
 };
 use crate::core::types::GameState;
-use crate::core::utils::{get_car_design, get_powerup_icon};
+use super::assets::{self, AssetRegistry, CarAsset, Sprite};
+use super::draw;
+use super::projection::{Projected, Projection, SpriteSize};
+use super::scene::FrameTime;
+
+/// Blinks per second of boss cars and the invincibility stars
+const BLINK_RATE: f32 = 3.0;
+
+/// Car sprite of the given size
+fn car_sprite(car: &CarAsset, size: SpriteSize) -> &Sprite {
+    match size {
+        SpriteSize::Large => &car.large,
+        SpriteSize::Medium => &car.medium,
+        SpriteSize::Small => &car.small,
+    }
+}
+
+/// Draw a sprite so its last row sits on row `bottom`
+/// Rows above the top of the area are skipped.
+fn draw_sprite(f: &mut Frame, projection: &Projection, sprite: &Sprite, x: u16, bottom: u16, style: Style) {
+    let area = projection.area();
+    let buf = f.buffer_mut();
+    for (i, line) in sprite.rows.iter().enumerate() {
+        if let Some(y) = (bottom + 1 + i as u16).checked_sub(sprite.height()) {
+            draw::text(buf, area, x, y, line, style);
+        }
+    }
+}
 
 /// Render all dynamic objects (AI cars and obstacles)
-pub fn render_objects(
-    f: &mut Frame,
-    area: Rect,
-    state: &GameState,
-    lane_width: u16,
-    height: u16,
-    curve: i16,
-    player_dist: f32,
-) {
-    render_ai_cars(f, area, state, lane_width, height, curve, player_dist);
-    render_obstacles(f, area, state, lane_width, height, curve, player_dist);
+/// Drawn far to near so closer objects cover the ones behind them
+pub fn render_objects(f: &mut Frame, projection: &Projection, state: &GameState, player_dist: f32, time: FrameTime) {
+    let assets = assets::active();
+    render_obstacles(f, projection, &assets, state, player_dist);
+    render_ai_cars(f, projection, &assets, state, player_dist, time);
 }
 
 /// Render all AI cars on the track
 fn render_ai_cars(
     f: &mut Frame,
-    area: Rect,
+    projection: &Projection,
+    assets: &AssetRegistry,
     state: &GameState,
-    lane_width: u16,
-    height: u16,
-    curve: i16,
     player_dist: f32,
+    time: FrameTime,
 ) {
     if state.car_count == 0 || state.ai_positions.is_null() {
         return;
     }
-    
+
     let positions = unsafe { std::slice::from_raw_parts(state.ai_positions, state.car_count as usize) };
     let distances = unsafe { std::slice::from_raw_parts(state.ai_distances, state.car_count as usize) };
     let types = unsafe { std::slice::from_raw_parts(state.ai_types, state.car_count as usize) };
     let is_boss = unsafe { std::slice::from_raw_parts(state.ai_is_boss, state.car_count as usize) };
-    
-    for i in 0..state.car_count as usize {
-        let rel_dist = distances[i] - player_dist;
-        if rel_dist > -10.0 && rel_dist < 50.0 {
-            let screen_y = area.y + height - ((rel_dist + 10.0) * height as f32 / 60.0) as u16;
-            let base_x = area.x + (positions[i] as u16 * lane_width) + lane_width / 2 - 3;
-            let x = (base_x as i16 + curve).max(area.x as i16) as u16;
-            
-            if screen_y < area.bottom() - 1 {
-                render_car(f, x, screen_y, types[i], is_boss[i]);
-            }
-        }
+
+    let mut visible: Vec<(usize, Projected)> = (0..state.car_count as usize)
+        .filter_map(|i| projection.project(distances[i] - player_dist).map(|p| (i, p)))
+        .collect();
+    visible.sort_by_key(|(_, at)| at.y);
+
+    for (i, at) in visible {
+        let blinking = is_boss[i] && !time.blink(BLINK_RATE);
+        render_car(f, projection, assets.car(types[i], is_boss[i]), at, positions[i], blinking);
     }
 }
 
 /// Render all obstacles and powerups on the track
-fn render_obstacles(
-    f: &mut Frame,
-    area: Rect,
-    state: &GameState,
-    lane_width: u16,
-    height: u16,
-    curve: i16,
-    player_dist: f32,
-) {
+fn render_obstacles(f: &mut Frame, projection: &Projection, assets: &AssetRegistry, state: &GameState, player_dist: f32) {
     if state.obstacle_count == 0 || state.obstacle_positions.is_null() {
         return;
     }
-    
+
     let positions = unsafe { std::slice::from_raw_parts(state.obstacle_positions, state.obstacle_count as usize) };
     let distances = unsafe { std::slice::from_raw_parts(state.obstacle_distances, state.obstacle_count as usize) };
     let types = unsafe { std::slice::from_raw_parts(state.obstacle_types, state.obstacle_count as usize) };
-    
+
     for i in 0..state.obstacle_count as usize {
-        let rel_dist = distances[i] - player_dist;
-        if rel_dist > -5.0 && rel_dist < 60.0 {
-            let screen_y = area.y + height - ((rel_dist + 5.0) * height as f32 / 65.0) as u16;
-            let base_x = area.x + (positions[i] as u16 * lane_width) + lane_width / 2 - 1;
-            let x = (base_x as i16 + curve).max(area.x as i16) as u16;
-            
-            if screen_y < area.bottom() - 1 {
-                render_powerup(f, x, screen_y, types[i]);
-            }
+        if let Some(at) = projection.project(distances[i] - player_dist) {
+            render_powerup(f, projection, assets, at, positions[i], types[i]);
         }
     }
 }
 
-/// Render an AI car with visual effects for boss cars
-fn render_car(f: &mut Frame, x: u16, y: u16, car_type: i32, is_boss: bool) {
-    let design = get_car_design(car_type, is_boss);
-    
-    for (i, line) in design.art.iter().enumerate() {
-        let modifier = if is_boss {
-            Modifier::BOLD | Modifier::RAPID_BLINK
-        } else {
-            Modifier::BOLD
-        };
-        
-        f.render_widget(
-            Paragraph::new(*line).style(Style::default().fg(design.color).add_modifier(modifier)),
-            Rect::new(x, y + i as u16, 7, 1),
-        );
-    }
-    
-    if !design.label.is_empty() {
-        f.render_widget(
-            Paragraph::new(design.label)
-                .style(Style::default().fg(Color::Black).bg(design.color).add_modifier(Modifier::BOLD)),
-            Rect::new(x + 1, y + 3, 5, 1),
-        );
-    }
+/// Render an AI car scaled by distance
+/// Boss cars blink by dimming on alternate beats of the frame clock
+fn render_car(f: &mut Frame, projection: &Projection, car: &CarAsset, at: Projected, lane: i32, dimmed: bool) {
+    let sprite = car_sprite(car, SpriteSize::from_scale(at.scale));
+    let x = projection.sprite_x(projection.lane_center(lane, at.scale), sprite.width);
+
+    let modifier = if dimmed {
+        Modifier::BOLD | Modifier::DIM
+    } else {
+        Modifier::BOLD
+    };
+    draw_sprite(f, projection, sprite, x, at.y, Style::default().fg(car.color).add_modifier(modifier));
 }
 
 /// Render the player car with powerup visual effects
+/// The player always drives at distance 0, so the car keeps its full size.
+/// Wheels, flames and the shield animate on the frame clock, so they keep
+/// moving whatever the speed.
 pub fn render_player(
     f: &mut Frame,
-    area: Rect,
+    projection: &Projection,
     state: &GameState,
     position: i32,
-    lane_width: u16,
-    curve: i16,
     car_type: i32,
     is_p1: bool,
+    time: FrameTime,
 ) {
-    let base_x = area.x + (position as u16 * lane_width) + lane_width / 2 - 3;
-    let x = (base_x as i16 + curve).max(area.x as i16) as u16;
-    let y = area.bottom() - 7;
-    
-    let mut design = get_car_design(car_type, false);
-    
+    let Some(at) = projection.project(0.0) else {
+        return;
+    };
+    let assets = assets::active();
+    let car = assets.car(car_type, false);
+    let x = projection.sprite_x(projection.lane_center(position, at.scale), car.large.width);
+    let y = (at.y + 1).saturating_sub(car.large.height()).max(projection.area().y);
+
     // Color modifiers for powerups
-    if state.invincibility_active {
-        design.color = Color::Yellow;
+    let color = if state.invincibility_active {
+        Color::Yellow
     } else if state.boost_active {
-        design.color = Color::Magenta;
+        Color::Magenta
     } else if state.shield_active {
-        design.color = Color::Cyan;
+        Color::Cyan
     } else if is_p1 {
-        design.color = Color::Green;
-    }
-    
+        Color::Green
+    } else {
+        car.color
+    };
+
     // Render car
-    for (i, line) in design.art.iter().enumerate() {
-        f.render_widget(
-            Paragraph::new(*line).style(Style::default().fg(design.color).add_modifier(Modifier::BOLD)),
-            Rect::new(x, y + i as u16, 7, 1),
-        );
+    let style = Style::default().fg(color).add_modifier(Modifier::BOLD);
+    draw_sprite(f, projection, &car.large, x, at.y, style);
+
+    let area = projection.area();
+    let buf = f.buffer_mut();
+
+    // Wheels spin in the bottom corners, for styles that animate them
+    if let Some(wheel) = &assets.animations().wheel {
+        let wheel = wheel.frame_at(time.elapsed);
+        for wx in [x, x + car.large.width.saturating_sub(1)] {
+            draw::put(buf, area, wx, at.y, wheel, style);
+        }
     }
-    
+
+    let boost = assets.animations().boost.frame_at(time.elapsed);
+    let shield = assets.animations().shield.frame_at(time.elapsed);
+    let above = y.saturating_sub(1);
+
     // Boost effect
     if state.boost_active {
-        f.render_widget(
-            Paragraph::new("🔥🔥").style(Style::default().fg(Color::Red).add_modifier(Modifier::RAPID_BLINK)),
-            Rect::new(x + 1, y + 4, 4, 1),
-        );
+        draw::fill(buf, area, x + 1, at.y + 1, 2, boost, Style::default().fg(Color::Red));
     }
-    
+
     // Shield effect
     if state.shield_active {
-        f.render_widget(
-            Paragraph::new(" ◯◯◯ ").style(Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)),
-            Rect::new(x.saturating_sub(1), y.saturating_sub(1), 9, 1),
-        );
+        let style = Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD);
+        let left = draw::text(buf, area, x.saturating_sub(1), above, " ", style);
+        let right = draw::fill(buf, area, left, above, 3, shield, style);
+        draw::text(buf, area, right, above, " ", style);
     }
-    
+
     // Invincibility effect
-    if state.invincibility_active {
-        f.render_widget(
-            Paragraph::new("✨⭐✨").style(Style::default().fg(Color::Yellow).add_modifier(Modifier::RAPID_BLINK)),
-            Rect::new(x.saturating_sub(1), y.saturating_sub(1), 9, 1),
-        );
+    if state.invincibility_active && time.blink(BLINK_RATE) {
+        draw::text(buf, area, x.saturating_sub(1), above, "✨⭐✨", Style::default().fg(Color::Yellow));
     }
-    
+
     // Magnet effect
     if state.magnet_active {
-        f.render_widget(
-            Paragraph::new("🧲").style(Style::default().fg(Color::Red)),
-            Rect::new(x + 6, y + 1, 2, 1),
-        );
+        draw::put(buf, area, x + car.large.width.saturating_sub(1), y + 1, "🧲", Style::default().fg(Color::Red));
     }
 }
 
 /// Render ghost car from replay mode
-pub fn render_ghost(
-    f: &mut Frame,
-    area: Rect,
-    state: &GameState,
-    lane_width: u16,
-    height: u16,
-    curve: i16,
-    player_dist: f32,
-) {
-    let rel_dist = state.ghost_distance - player_dist;
-    if rel_dist > -10.0 && rel_dist < 50.0 {
-        let screen_y = area.y + height - ((rel_dist + 10.0) * height as f32 / 60.0) as u16;
-        let base_x = area.x + (state.ghost_position as u16 * lane_width) + lane_width / 2 - 3;
-        let x = (base_x as i16 + curve).max(area.x as i16) as u16;
-        
-        if screen_y < area.bottom() - 1 {
-            let design = get_car_design(state.player_car_type, false);
-            for (i, line) in design.art.iter().enumerate() {
-                f.render_widget(
-                    Paragraph::new(*line)
-                        .style(Style::default().fg(Color::Rgb(150, 150, 200)).add_modifier(Modifier::DIM)),
-                    Rect::new(x, screen_y + i as u16, 7, 1),
-                );
-            }
-        }
-    }
+pub fn render_ghost(f: &mut Frame, projection: &Projection, state: &GameState, player_dist: f32) {
+    let Some(at) = projection.project(state.ghost_distance - player_dist) else {
+        return;
+    };
+    let assets = assets::active();
+    let sprite = car_sprite(assets.car(state.player_car_type, false), SpriteSize::from_scale(at.scale));
+    let x = projection.sprite_x(projection.lane_center(state.ghost_position, at.scale), sprite.width);
+
+    let style = Style::default().fg(Color::Rgb(150, 150, 200)).add_modifier(Modifier::DIM);
+    draw_sprite(f, projection, sprite, x, at.y, style);
 }
 
 /// Render a single powerup or obstacle icon
-fn render_powerup(f: &mut Frame, x: u16, y: u16, ptype: i32) {
-    let (icon, color) = get_powerup_icon(ptype);
-    
-    f.render_widget(
-        Paragraph::new(icon).style(Style::default().fg(color).add_modifier(Modifier::BOLD)),
-        Rect::new(x, y, 2, 1),
-    );
+/// Far away icons shrink to a dot of the same color
+fn render_powerup(f: &mut Frame, projection: &Projection, assets: &AssetRegistry, at: Projected, lane: i32, ptype: i32) {
+    let glyph = &assets.powerup(ptype).icon;
+    let color = glyph.color;
+    let (icon, width) = match SpriteSize::from_scale(at.scale) {
+        SpriteSize::Small => ("•", 1),
+        _ => (glyph.symbol.as_str(), glyph.width.max(1)),
+    };
+    let x = projection.sprite_x(projection.lane_center(lane, at.scale), width);
+
+    draw::text(f.buffer_mut(), projection.area(), x, at.y, icon, Style::default().fg(color).add_modifier(Modifier::BOLD));
 }
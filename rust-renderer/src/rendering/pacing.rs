@@ -0,0 +1,176 @@
+//! Frame pacing and adaptive effect quality
+//! The pacer measures what each game frame costs to draw and flush, tells
+//! the renderer how long to wait for the next frame at the target rate, and
+//! in adaptive mode trades effects for time on slow terminals and SSH
+//! sessions, where flushing a busy frame can take longer than the frame.
+
+use std::time::{Duration, Instant};
+use crate::core::error::{Error, ErrorCode, Result};
+use crate::core::types::{quality_levels, FrameStats};
+
+/// Rate adaptive quality budgets for when no target is set
+pub const DEFAULT_FPS: u32 = 60;
+
+/// Weight of the newest sample in a running average
+const SMOOTHING: f32 = 0.1;
+
+/// Frames to wait after a quality change before judging the next one
+/// Gives the running average time to settle on the new cost.
+const SETTLE_FRAMES: u32 = 30;
+
+/// Share of the budget the average must fall below before quality is raised
+/// Well under the budget, so a level that only just fits is kept.
+const RAISE_BELOW: f32 = 0.5;
+
+/// Effects drawn at a quality level
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Effects {
+    pub rain: bool,
+    pub particles: bool,
+    pub windows: bool,
+}
+
+impl Effects {
+    /// Effects of one of `quality_levels`
+    pub fn at(level: i32) -> Self {
+        Effects {
+            rain: level < quality_levels::MEDIUM,
+            particles: level < quality_levels::LOW,
+            windows: level < quality_levels::MINIMAL,
+        }
+    }
+}
+
+/// Target rate, measured frame cost and current quality of one renderer
+#[derive(Clone, Debug)]
+pub struct FramePacer {
+    target_fps: u32,
+    /// When the last paced frame was due
+    deadline: Option<Instant>,
+    /// Cost of the last game frame and its running average, in seconds
+    last: f32,
+    average: Option<f32>,
+    /// Best quality allowed; adaptive mode only ever lowers from it
+    ceiling: i32,
+    quality: i32,
+    adaptive: bool,
+    settle: u32,
+}
+
+impl Default for FramePacer {
+    fn default() -> Self {
+        FramePacer {
+            target_fps: 0,
+            deadline: None,
+            last: 0.0,
+            average: None,
+            ceiling: quality_levels::HIGH,
+            quality: quality_levels::HIGH,
+            adaptive: false,
+            settle: 0,
+        }
+    }
+}
+
+impl FramePacer {
+    /// Pace frames at `fps`, or not at all for 0
+    pub fn set_target_fps(&mut self, fps: u32) {
+        self.target_fps = fps;
+        self.deadline = None;
+    }
+
+    pub fn target_fps(&self) -> u32 {
+        self.target_fps
+    }
+
+    /// Seconds one frame may take at the target rate
+    pub fn budget(&self) -> f32 {
+        let fps = if self.target_fps > 0 { self.target_fps } else { DEFAULT_FPS };
+        1.0 / fps as f32
+    }
+
+    /// Draw at `level`, or at most at `level` while `adaptive`
+    /// Fails on an unknown level, leaving the quality unchanged.
+    pub fn set_quality(&mut self, level: i32, adaptive: bool) -> Result<()> {
+        if !(quality_levels::HIGH..=quality_levels::MINIMAL).contains(&level) {
+            return Err(Error::new(ErrorCode::InvalidArgument, format!("unknown quality level {}", level)));
+        }
+        self.ceiling = level;
+        self.quality = level;
+        self.adaptive = adaptive;
+        self.settle = SETTLE_FRAMES;
+        Ok(())
+    }
+
+    /// Current quality, one of `quality_levels`
+    pub fn quality(&self) -> i32 {
+        self.quality
+    }
+
+    pub fn adaptive(&self) -> bool {
+        self.adaptive
+    }
+
+    pub fn effects(&self) -> Effects {
+        Effects::at(self.quality)
+    }
+
+    /// Record what a game frame cost to draw and flush
+    /// In adaptive mode, a running average over the budget drops one quality
+    /// level, and one well under it raises a level back towards the ceiling.
+    pub fn record(&mut self, cost: Duration) {
+        let cost = cost.as_secs_f32();
+        self.last = cost;
+        let average = smooth(self.average, cost);
+        self.average = Some(average);
+
+        if !self.adaptive {
+            return;
+        }
+        if self.settle > 0 {
+            self.settle -= 1;
+            return;
+        }
+
+        let budget = self.budget();
+        if average > budget && self.quality < quality_levels::MINIMAL {
+            self.quality += 1;
+            self.settle = SETTLE_FRAMES;
+        } else if average < budget * RAISE_BELOW && self.quality > self.ceiling {
+            self.quality -= 1;
+            self.settle = SETTLE_FRAMES;
+        }
+    }
+
+    /// How long to wait from `now` until the next frame is due
+    /// Frames are due one period apart; a host running late starts the
+    /// count again from `now` rather than rushing to catch up. Always zero
+    /// while frames are not paced.
+    pub fn until_next_frame(&mut self, now: Instant) -> Duration {
+        if self.target_fps == 0 {
+            return Duration::ZERO;
+        }
+        let period = Duration::from_secs_f64(1.0 / self.target_fps as f64);
+        let next = self.deadline.map(|due| due + period).filter(|&next| next > now).unwrap_or(now);
+        self.deadline = Some(next);
+        next - now
+    }
+
+    /// Pacing figures for the FFI, at frame number `frame`
+    pub fn stats(&self, frame: u64) -> FrameStats {
+        FrameStats {
+            frame,
+            render_ms: self.last * 1000.0,
+            average_ms: self.average.unwrap_or(0.0) * 1000.0,
+            target_fps: self.target_fps,
+            quality: self.quality,
+            adaptive: self.adaptive,
+        }
+    }
+}
+
+/// Fold `sample` into a running average, which starts at the first sample
+/// Frame costs here and frame intervals in the debug overlay share it.
+pub fn smooth(average: Option<f32>, sample: f32) -> f32 {
+    average.map_or(sample, |average| average + (sample - average) * SMOOTHING)
+}
@@ -0,0 +1,303 @@
+//! Asset pack files
+//! A pack overlays sprites, icons, colors, labels and animation frames on one
+//! of the built-in styles, so new art needs no rebuild of the renderer.
+//! Every sprite is checked against the space the renderers give it.
+
+use std::path::Path;
+use std::str::FromStr;
+use ratatui::style::Color;
+use serde_json::{Map, Value};
+use unicode_width::UnicodeWidthStr;
+use crate::core::error::{Error, ErrorCode, Result};
+use super::assets::{
+    detailed_wheel, Animation, AssetRegistry, BuildingAsset, CarAsset, Glyph, PowerupAsset, Sprite,
+    MAX_SPRITE_HEIGHT, MAX_SPRITE_WIDTH,
+};
+
+/// Widest powerup, scenery or animation frame symbol
+const MAX_ICON_WIDTH: u16 = 2;
+
+/// Widest HUD title; gauges are at least this wide on the smallest layout
+const MAX_TITLE_WIDTH: u16 = 12;
+
+/// Weather names of the `road` table, in `weather` order
+const WEATHER: [&str; 4] = ["clear", "rain", "fog", "night"];
+
+fn invalid(message: impl std::fmt::Display) -> Error {
+    Error::new(ErrorCode::InvalidAssets, format!("invalid asset pack: {}", message))
+}
+
+/// One table of the pack, with its dotted path for error messages
+struct Table<'a> {
+    path: String,
+    entries: &'a Map<String, Value>,
+}
+
+impl<'a> Table<'a> {
+    fn new(path: String, value: &'a Value) -> Result<Self> {
+        match value {
+            Value::Object(entries) => Ok(Table { path, entries }),
+            _ => Err(invalid(format!("`{}` must be a table", path))),
+        }
+    }
+
+    fn path(&self, key: &str) -> String {
+        if self.path.is_empty() { key.to_string() } else { format!("{}.{}", self.path, key) }
+    }
+
+    /// Fail on keys outside `known`, which are most likely typos
+    fn only(&self, known: &[&str]) -> Result<()> {
+        match self.entries.keys().find(|key| !known.contains(&key.as_str())) {
+            Some(key) => Err(invalid(format!("unknown key `{}`", self.path(key)))),
+            None => Ok(()),
+        }
+    }
+
+    fn table(&self, key: &str) -> Result<Option<Table<'a>>> {
+        self.entries.get(key).map(|value| Table::new(self.path(key), value)).transpose()
+    }
+
+    fn str(&self, key: &str) -> Result<Option<&'a str>> {
+        match self.entries.get(key) {
+            None => Ok(None),
+            Some(Value::String(s)) => Ok(Some(s)),
+            Some(_) => Err(invalid(format!("`{}` must be a string", self.path(key)))),
+        }
+    }
+
+    /// A string or a list of strings, e.g. the rows of a sprite
+    fn rows(&self, key: &str) -> Result<Option<Vec<&'a str>>> {
+        let not_rows = || invalid(format!("`{}` must be a string or a list of strings", self.path(key)));
+        match self.entries.get(key) {
+            None => Ok(None),
+            Some(Value::String(s)) => Ok(Some(vec![s])),
+            Some(Value::Array(items)) => items.iter().map(|item| item.as_str().ok_or_else(not_rows)).collect::<Result<_>>().map(Some),
+            Some(_) => Err(not_rows()),
+        }
+    }
+
+    fn number(&self, key: &str) -> Result<Option<f32>> {
+        match self.entries.get(key) {
+            None => Ok(None),
+            Some(Value::Number(n)) => Ok(n.as_f64().map(|n| n as f32)),
+            Some(_) => Err(invalid(format!("`{}` must be a number", self.path(key)))),
+        }
+    }
+
+    /// A color name (`red`, `light-blue`, `gray`), `#rrggbb` or an ANSI index
+    fn color(&self, key: &str) -> Result<Option<Color>> {
+        self.str(key)?
+            .map(|s| Color::from_str(s).map_err(|_| invalid(format!("`{}`: unknown color {:?}", self.path(key), s))))
+            .transpose()
+    }
+
+    /// Sub-tables keyed by a non-negative type number
+    fn by_type(&self) -> Result<Vec<(i32, Table<'a>)>> {
+        self.entries
+            .iter()
+            .map(|(key, value)| {
+                let number = key.parse::<i32>().ok().filter(|n| *n >= 0);
+                let number = number.ok_or_else(|| invalid(format!("`{}`: expected a type number", self.path(key))))?;
+                Ok((number, Table::new(self.path(key), value)?))
+            })
+            .collect()
+    }
+}
+
+/// Car entry over `base`; fields left out keep the base art
+fn car(entry: &Table, mut base: CarAsset) -> Result<CarAsset> {
+    entry.only(&["large", "medium", "small", "color", "label"])?;
+    if let Some(rows) = entry.rows("large")? {
+        base.large = Sprite::new(&rows);
+    }
+    if let Some(rows) = entry.rows("medium")? {
+        base.medium = Sprite::new(&rows);
+    }
+    if let Some(rows) = entry.rows("small")? {
+        base.small = Sprite::new(&rows);
+    }
+    base.color = entry.color("color")?.unwrap_or(base.color);
+    if let Some(label) = entry.str("label")? {
+        base.label = label.to_string();
+    }
+    Ok(base)
+}
+
+/// `{ symbol, color }` entry over `base`
+fn glyph(entry: &Table, base: &Glyph) -> Result<Glyph> {
+    entry.only(&["symbol", "color"])?;
+    let symbol = entry.str("symbol")?.unwrap_or(&base.symbol);
+    Ok(Glyph::new(symbol, entry.color("color")?.unwrap_or(base.color)))
+}
+
+/// Overlay the `{ symbol, color }` entries of `table` named in `slots`
+fn glyphs(table: &Table, slots: &mut [(&str, &mut Glyph)]) -> Result<()> {
+    table.only(&slots.iter().map(|(name, _)| *name).collect::<Vec<_>>())?;
+    for (name, slot) in slots.iter_mut() {
+        if let Some(entry) = table.table(name)? {
+            **slot = glyph(&entry, slot)?;
+        }
+    }
+    Ok(())
+}
+
+/// `{ frames, step }` entry over `base`
+fn animation(entry: &Table, base: &Animation) -> Result<Animation> {
+    entry.only(&["frames", "step"])?;
+    let frames = entry.rows("frames")?.map_or_else(|| base.frames.clone(), |f| f.iter().map(|f| f.to_string()).collect());
+    Ok(Animation { frames, step: entry.number("step")?.unwrap_or(base.step) })
+}
+
+impl AssetRegistry {
+    /// Parse an asset pack from JSON (an object) or TOML
+    /// `base` names the style (`classic` or `detailed`) that everything the
+    /// pack leaves out comes from. Car, powerup and building tables are keyed
+    /// by type number, and new numbers add types.
+    pub fn parse_pack(text: &str) -> Result<Self> {
+        let value: Value = if text.trim_start().starts_with('{') {
+            serde_json::from_str(text).map_err(invalid)?
+        } else {
+            let table: toml::Table = toml::from_str(text).map_err(invalid)?;
+            serde_json::to_value(table).map_err(invalid)?
+        };
+        let pack = Table::new(String::new(), &value)?;
+        pack.only(&["base", "cars", "boss", "powerups", "buildings", "road", "scenery", "hud", "animations"])?;
+
+        let mut registry = match pack.str("base")? {
+            None | Some("classic") => AssetRegistry::classic(),
+            Some("detailed") => AssetRegistry::detailed(),
+            Some(other) => return Err(invalid(format!("unknown base style `{}`", other))),
+        };
+
+        if let Some(cars) = pack.table("cars")? {
+            for (car_type, entry) in cars.by_type()? {
+                let car = car(&entry, registry.car(car_type, false).clone())?;
+                registry.cars.insert(car_type, car);
+            }
+        }
+        if let Some(entry) = pack.table("boss")? {
+            registry.boss = car(&entry, registry.boss.clone())?;
+        }
+        if let Some(powerups) = pack.table("powerups")? {
+            for (ptype, entry) in powerups.by_type()? {
+                entry.only(&["icon", "color", "name"])?;
+                let base = registry.powerup(ptype).clone();
+                let icon = entry.str("icon")?.unwrap_or(&base.icon.symbol);
+                let powerup = PowerupAsset {
+                    icon: Glyph::new(icon, entry.color("color")?.unwrap_or(base.icon.color)),
+                    name: entry.str("name")?.map_or(base.name, str::to_string),
+                };
+                registry.powerups.insert(ptype, powerup);
+            }
+        }
+        if let Some(buildings) = pack.table("buildings")? {
+            for (btype, entry) in buildings.by_type()? {
+                entry.only(&["wall", "color"])?;
+                let base = registry.building(btype).clone();
+                let building = BuildingAsset {
+                    wall: entry.str("wall")?.map_or(base.wall, str::to_string),
+                    color: entry.color("color")?.unwrap_or(base.color),
+                };
+                registry.buildings.insert(btype, building);
+            }
+        }
+        if let Some(road) = pack.table("road")? {
+            road.only(&WEATHER)?;
+            for (slot, weather) in registry.road.iter_mut().zip(WEATHER) {
+                if let Some(surface) = road.str(weather)? {
+                    *slot = surface.to_string();
+                }
+            }
+        }
+        if let Some(scenery) = pack.table("scenery")? {
+            glyphs(&scenery, &mut [("cactus", &mut registry.cactus), ("peak", &mut registry.peak)])?;
+        }
+        if let Some(hud) = pack.table("hud")? {
+            let h = &mut registry.hud;
+            glyphs(&hud, &mut [
+                ("score", &mut h.score),
+                ("speed", &mut h.speed),
+                ("boost", &mut h.boost),
+                ("shield", &mut h.shield),
+                ("star", &mut h.star),
+                ("magnet", &mut h.magnet),
+            ])?;
+        }
+        if let Some(animations) = pack.table("animations")? {
+            animations.only(&["boost", "shield", "wheel"])?;
+            let a = &mut registry.animations;
+            if let Some(entry) = animations.table("boost")? {
+                a.boost = animation(&entry, &a.boost)?;
+            }
+            if let Some(entry) = animations.table("shield")? {
+                a.shield = animation(&entry, &a.shield)?;
+            }
+            if let Some(entry) = animations.table("wheel")? {
+                let base = a.wheel.take().unwrap_or_else(detailed_wheel);
+                a.wheel = Some(animation(&entry, &base)?);
+            }
+        }
+
+        registry.validate()?;
+        Ok(registry)
+    }
+
+    /// Load an asset pack file (`.toml` or `.json`)
+    pub fn load_pack(path: &Path) -> Result<Self> {
+        let text = std::fs::read_to_string(path)
+            .map_err(|e| Error::io(&format!("failed to read {}", path.display()), e))?;
+        AssetRegistry::parse_pack(&text)
+    }
+
+    /// Check every sprite and symbol fits the space the renderers give it
+    /// Cars shrink with distance, so each size must be no wider than the
+    /// one before it; road and wall characters fill single cells.
+    pub fn validate(&self) -> Result<()> {
+        let at = |path: String| move |message: String| invalid(format!("`{}`: {}", path, message));
+
+        let cars = self.cars.iter().map(|(t, car)| (t.to_string(), car));
+        for (name, car) in cars.chain([("boss".to_string(), &self.boss)]) {
+            car.large.check(MAX_SPRITE_WIDTH, MAX_SPRITE_HEIGHT).map_err(at(format!("cars.{}.large", name)))?;
+            car.medium.check(car.large.width, 2).map_err(at(format!("cars.{}.medium", name)))?;
+            car.small.check(car.medium.width, 1).map_err(at(format!("cars.{}.small", name)))?;
+        }
+        for (ptype, powerup) in &self.powerups {
+            powerup.icon.check(MAX_ICON_WIDTH).map_err(at(format!("powerups.{}.icon", ptype)))?;
+        }
+        for (btype, building) in &self.buildings {
+            if building.wall.is_empty() || building.wall.chars().any(|c| c.to_string().width() != 1) {
+                return Err(at(format!("buildings.{}.wall", btype))("every character must be one column wide".into()));
+            }
+        }
+        for (surface, weather) in self.road.iter().zip(WEATHER) {
+            if surface.chars().count() != 1 || surface.width() != 1 {
+                return Err(at(format!("road.{}", weather))("must be a single one-column character".into()));
+            }
+        }
+        self.cactus.check(MAX_ICON_WIDTH).map_err(at("scenery.cactus".into()))?;
+        self.peak.check(MAX_ICON_WIDTH).map_err(at("scenery.peak".into()))?;
+
+        let h = &self.hud;
+        for (name, title) in [("score", &h.score), ("speed", &h.speed), ("boost", &h.boost), ("shield", &h.shield), ("star", &h.star), ("magnet", &h.magnet)] {
+            title.check(MAX_TITLE_WIDTH).map_err(at(format!("hud.{}", name)))?;
+        }
+
+        let a = &self.animations;
+        // Wheels replace single cells of the car sprite
+        let wheel = a.wheel.iter().map(|wheel| ("wheel", wheel, 1));
+        let effects = [("boost", &a.boost, MAX_ICON_WIDTH), ("shield", &a.shield, MAX_ICON_WIDTH)];
+        for (name, animation, width) in effects.into_iter().chain(wheel) {
+            let at = at(format!("animations.{}", name));
+            if animation.frames.is_empty() {
+                return Err(at("needs at least one frame".into()));
+            }
+            if !(animation.step.is_finite() && animation.step > 0.0) {
+                return Err(at("`step` must be a positive number".into()));
+            }
+            for frame in &animation.frames {
+                Glyph::new(frame, Color::Reset).check(width).map_err(&at)?;
+            }
+        }
+        Ok(())
+    }
+}
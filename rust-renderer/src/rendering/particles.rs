@@ -0,0 +1,247 @@
+//! Particle effects - exhaust, crash sparks, desert dust and rain spray
+//! Particles live in track space next to the car that emitted them and are
+//! simulated between frames; each renderer keeps its system in its `Scene`
+//! and advances it by the frame delta.
+
+use ratatui::{
+    style::{Modifier, Style},
+    Frame,
+};
+use crate::core::types::{track_types, weather, GameState};
+use super::draw;
+use super::projection::{lane_count, lane_fraction, Projection};
+use super::visual_assets::{
+    get_boost_particle, get_crash_particle, get_dust_particle, get_spray_particle, ParticleEffect,
+};
+
+/// Most particles alive at once; emitters skip spawns beyond it
+pub const MAX_PARTICLES: usize = 256;
+
+/// Longest step simulated at once, so a stalled host does not flush every
+/// particle in one frame
+pub const MAX_STEP: f32 = 0.1;
+
+/// `ParticleEffect` lifetimes are counted in frames at this rate
+const EFFECT_FPS: f32 = 30.0;
+
+/// Sparks thrown up when the player loses health
+const SPARK_BURST: usize = 12;
+
+/// Rows per second squared pulling sparks back onto the road
+const GRAVITY: f32 = 24.0;
+
+/// What a particle was emitted as, picking its look and motion
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ParticleKind {
+    /// Trail behind a boosting car
+    Exhaust,
+    /// Burst from a car that just took damage
+    Spark,
+    /// Kicked up from the desert shoulder
+    Dust,
+    /// Thrown up behind the wheels in rain
+    Spray,
+}
+
+impl ParticleKind {
+    fn effect(self) -> ParticleEffect {
+        match self {
+            ParticleKind::Exhaust => get_boost_particle(),
+            ParticleKind::Spark => get_crash_particle(),
+            ParticleKind::Dust => get_dust_particle(),
+            ParticleKind::Spray => get_spray_particle(),
+        }
+    }
+
+    /// Seconds the particle stays alive
+    fn lifetime(self) -> f32 {
+        self.effect().lifetime as f32 / EFFECT_FPS
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+struct Particle {
+    kind: ParticleKind,
+    /// 0 for player 1, 1 for player 2
+    owner: usize,
+    /// Fraction of the way across the road
+    x: f32,
+    /// Track distance ahead of the owner's car
+    ahead: f32,
+    /// Rows above the road at full scale
+    height: f32,
+    vx: f32,
+    va: f32,
+    vh: f32,
+    age: f32,
+}
+
+/// Spawns still owed to one player, carried over between updates so low
+/// rates emit steadily at any frame rate
+#[derive(Clone, Copy, Debug, Default)]
+struct Emitter {
+    health: Option<i32>,
+    exhaust: f32,
+    dust: f32,
+    spray: f32,
+}
+
+/// Live particles and the emitters feeding them
+#[derive(Clone, Debug)]
+pub struct ParticleSystem {
+    particles: Vec<Particle>,
+    emitters: [Emitter; 2],
+    seed: u32,
+}
+
+impl Default for ParticleSystem {
+    fn default() -> Self {
+        ParticleSystem { particles: Vec::new(), emitters: [Emitter::default(); 2], seed: 0x9E37_79B9 }
+    }
+}
+
+impl ParticleSystem {
+    /// Number of live particles
+    pub fn len(&self) -> usize {
+        self.particles.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.particles.is_empty()
+    }
+
+    /// Number of live particles of one kind
+    pub fn count(&self, kind: ParticleKind) -> usize {
+        self.particles.iter().filter(|p| p.kind == kind).count()
+    }
+
+    /// Drop every particle and forget what the emitters have seen
+    /// A later update starts over, so health lost meanwhile throws no sparks.
+    pub fn clear(&mut self) {
+        self.particles.clear();
+        self.emitters = [Emitter::default(); 2];
+    }
+
+    /// Advance every particle by `dt` seconds and run the emitters for `state`
+    /// Steps longer than `MAX_STEP` are shortened to it.
+    pub fn update(&mut self, state: &GameState, dt: f32) {
+        let dt = dt.clamp(0.0, MAX_STEP);
+
+        for p in &mut self.particles {
+            p.age += dt;
+            p.x += p.vx * dt;
+            p.ahead += p.va * dt;
+            p.height = (p.height + p.vh * dt).max(0.0);
+            if p.kind == ParticleKind::Spark {
+                p.vh -= GRAVITY * dt;
+            }
+        }
+        self.particles.retain(|p| p.age < p.kind.lifetime());
+
+        let lanes = lane_count(state);
+        self.emit(state, 0, state.player_position, state.player_speed, state.player_health, lanes, dt);
+        if state.player2_active {
+            self.emit(state, 1, state.player2_position, state.player2_speed, state.player2_health, lanes, dt);
+        } else {
+            self.emitters[1] = Emitter::default();
+        }
+    }
+
+    /// Run one player's emitters
+    #[allow(clippy::too_many_arguments)]
+    fn emit(&mut self, state: &GameState, owner: usize, lane: i32, speed: f32, health: i32, lanes: u16, dt: f32) {
+        let x = lane_fraction(lane, lanes);
+        let moving = speed.max(0.0);
+        let mut emitter = self.emitters[owner];
+
+        // Sparks when health drops, not on the first sight of the car
+        if emitter.health.is_some_and(|last| health < last) {
+            for _ in 0..SPARK_BURST {
+                let (vx, va, vh) = (self.range(-0.6, 0.6), self.range(-4.0, 4.0), self.range(4.0, 9.0));
+                self.spawn(ParticleKind::Spark, owner, x, 0.0, 1.0, vx, va, vh);
+            }
+        }
+        emitter.health = Some(health);
+
+        if state.boost_active {
+            for _ in 0..owed(&mut emitter.exhaust, 40.0 * dt) {
+                let (dx, va) = (self.range(-0.03, 0.03), self.range(-9.0, -6.0));
+                self.spawn(ParticleKind::Exhaust, owner, x + dx, -0.3, 0.0, 0.0, va, 0.5);
+            }
+        } else {
+            emitter.exhaust = 0.0;
+        }
+
+        if state.track_type == track_types::DESERT && moving > 0.0 {
+            for _ in 0..owed(&mut emitter.dust, (moving / 8.0).min(30.0) * dt) {
+                let side = if self.range(0.0, 1.0) < 0.5 { -0.06 } else { 1.06 };
+                let (ahead, vh) = (self.range(0.0, 3.0), self.range(1.0, 3.0));
+                let vx = (side - 0.5) * 0.2;
+                self.spawn(ParticleKind::Dust, owner, side, ahead, 0.0, vx, -moving / 20.0, vh);
+            }
+        } else {
+            emitter.dust = 0.0;
+        }
+
+        if state.weather == weather::RAIN && moving > 0.0 {
+            for _ in 0..owed(&mut emitter.spray, (moving / 5.0).min(40.0) * dt) {
+                let (dx, vh) = (self.range(-0.06, 0.06), self.range(1.5, 3.5));
+                self.spawn(ParticleKind::Spray, owner, x + dx, -0.3, 0.0, dx, -4.0, vh);
+            }
+        } else {
+            emitter.spray = 0.0;
+        }
+
+        self.emitters[owner] = emitter;
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn spawn(&mut self, kind: ParticleKind, owner: usize, x: f32, ahead: f32, height: f32, vx: f32, va: f32, vh: f32) {
+        if self.particles.len() < MAX_PARTICLES {
+            self.particles.push(Particle { kind, owner, x, ahead, height, vx, va, vh, age: 0.0 });
+        }
+    }
+
+    /// Uniform value in `lo..hi` from a xorshift generator
+    /// Seeded the same way every time, so runs are reproducible
+    fn range(&mut self, lo: f32, hi: f32) -> f32 {
+        self.seed ^= self.seed << 13;
+        self.seed ^= self.seed >> 17;
+        self.seed ^= self.seed << 5;
+        lo + (hi - lo) * (self.seed >> 8) as f32 / (1u32 << 24) as f32
+    }
+}
+
+/// Add `amount` to what an emitter owes and take the whole spawns out of it
+fn owed(pending: &mut f32, amount: f32) -> usize {
+    *pending += amount;
+    let whole = pending.floor();
+    *pending -= whole;
+    whole as usize
+}
+
+/// Render the particles seen from a car at `player_dist`
+/// Particles fade for the last third of their life.
+pub fn render_particles(f: &mut Frame, projection: &Projection, particles: &ParticleSystem, state: &GameState, player_dist: f32) {
+    let area = projection.area();
+    let owners = [state.player_distance, state.player2_distance];
+
+    for p in &particles.particles {
+        let Some(at) = projection.project(owners[p.owner] + p.ahead - player_dist) else {
+            continue;
+        };
+        let Some(x) = projection.column(projection.road_x(p.x, at.scale)) else {
+            continue;
+        };
+        let Some(y) = at.y.checked_sub((p.height * at.scale).round() as u16).filter(|&y| y >= area.y) else {
+            continue;
+        };
+
+        let effect = p.kind.effect();
+        let mut style = Style::default().fg(effect.color);
+        if p.age > p.kind.lifetime() * 2.0 / 3.0 {
+            style = style.add_modifier(Modifier::DIM);
+        }
+        draw::put(f.buffer_mut(), area, x, y, effect.symbol, style);
+    }
+}
@@ -0,0 +1,216 @@
+//! Pseudo-3D road projection
+//! Maps track distances to screen rows with perspective, so the road narrows
+//! towards a horizon, bends progressively with depth and objects shrink as
+//! they get further away
+
+use ratatui::layout::Rect;
+use crate::core::types::GameState;
+use super::visual_assets::get_lane_config;
+
+/// Distance ahead of the player beyond which nothing is drawn
+pub const DRAW_DISTANCE: f32 = 60.0;
+
+/// Distance behind the player that is still visible at the bottom edge
+pub const REAR_DISTANCE: f32 = 2.0;
+
+/// Focal length in track units: a larger value flattens the perspective
+const FOCAL_LENGTH: f32 = 10.0;
+
+/// Horizon height above the bottom edge, as a fraction of the area
+const HORIZON: f32 = 0.7;
+
+/// Road width at the bottom edge, as a fraction of the area width, for a
+/// road of `STANDARD_ROAD` columns of lanes
+const ROAD_WIDTH: f32 = 0.95;
+
+/// Total `LaneConfig` lane width of the built-in tracks (3 x 8, 4 x 6, 2 x 12)
+const STANDARD_ROAD: f32 = 24.0;
+
+/// Most lanes a `GameState::lane_count` override may ask for
+pub const MAX_LANES: u16 = 8;
+
+/// Sprite size picked from the projected scale of an object
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SpriteSize {
+    Small,
+    Medium,
+    Large,
+}
+
+impl SpriteSize {
+    /// Size of a sprite drawn at `scale`
+    pub fn from_scale(scale: f32) -> Self {
+        if scale >= 0.6 {
+            SpriteSize::Large
+        } else if scale >= 0.3 {
+            SpriteSize::Medium
+        } else {
+            SpriteSize::Small
+        }
+    }
+}
+
+/// Object position on screen
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Projected {
+    /// Row the object touches the road on
+    pub y: u16,
+    /// 1.0 at the bottom edge, approaching 0.0 at the horizon
+    pub scale: f32,
+}
+
+/// Perspective camera over one track area
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Projection {
+    area: Rect,
+    horizon: u16,
+    lanes: u16,
+    /// Road width at the bottom edge in columns
+    width: f32,
+    /// Columns the road centre moves at the horizon
+    bend: f32,
+    marker: &'static str,
+}
+
+impl Projection {
+    /// Camera behind the player for `area`
+    /// `elevation` raises or lowers the horizon; the road curve is scaled by
+    /// `curve_factor`, which each track type sets for its own character.
+    /// Lanes follow the track's `LaneConfig`, with `lane_count` overriding
+    /// the count when set; the road grows or shrinks with it.
+    pub fn new(area: Rect, state: &GameState, curve_factor: f32) -> Self {
+        let height = area.height as f32;
+        let elevation = state.elevation.clamp(-1.0, 1.0);
+        let above = (height * (1.0 - HORIZON) - elevation * height * 0.15).round();
+        let horizon = area.y + (above.max(0.0) as u16).min(area.height.saturating_sub(1));
+        let bend = state.curve_offset * curve_factor * area.width as f32 / 40.0;
+
+        let config = get_lane_config(state.track_type);
+        let lanes = lane_count(state);
+        let road = lanes as f32 * config.lane_width as f32 / STANDARD_ROAD;
+        let width = (area.width as f32 * ROAD_WIDTH * road).min(area.width as f32);
+
+        Projection { area, horizon, lanes, width, bend, marker: config.marker_style }
+    }
+
+    /// Track area this camera draws into
+    pub fn area(&self) -> Rect {
+        self.area
+    }
+
+    /// First row below the horizon
+    pub fn horizon(&self) -> u16 {
+        self.horizon
+    }
+
+    /// Number of lanes on the road
+    pub fn lanes(&self) -> u16 {
+        self.lanes
+    }
+
+    /// Lane divider symbol of the track
+    pub fn marker(&self) -> &'static str {
+        self.marker
+    }
+
+    /// Rows between the horizon and the bottom edge
+    fn ground_rows(&self) -> f32 {
+        self.area.bottom().saturating_sub(self.horizon).max(1) as f32
+    }
+
+    /// Scale of a distance ahead of the player, 1.0 at `-REAR_DISTANCE`
+    pub fn scale_at(rel_dist: f32) -> f32 {
+        FOCAL_LENGTH / (FOCAL_LENGTH + rel_dist + REAR_DISTANCE)
+    }
+
+    /// Distance ahead of the player shown on a road row
+    /// None above the horizon
+    pub fn distance_at(&self, y: u16) -> Option<f32> {
+        let scale = self.row_scale(y)?;
+        Some(FOCAL_LENGTH / scale - FOCAL_LENGTH - REAR_DISTANCE)
+    }
+
+    /// Scale of a road row; None above the horizon
+    pub fn row_scale(&self, y: u16) -> Option<f32> {
+        if y < self.horizon || y >= self.area.bottom() {
+            return None;
+        }
+        Some((y - self.horizon + 1) as f32 / self.ground_rows())
+    }
+
+    /// Screen position of an object `rel_dist` ahead of the player
+    /// None when it is behind the camera or past the draw distance
+    pub fn project(&self, rel_dist: f32) -> Option<Projected> {
+        if !(-REAR_DISTANCE..DRAW_DISTANCE).contains(&rel_dist) {
+            return None;
+        }
+        let scale = Self::scale_at(rel_dist);
+        let row = (scale * self.ground_rows()).round() as u16;
+        let y = (self.horizon + row.max(1) - 1).min(self.area.bottom() - 1);
+        Some(Projected { y, scale })
+    }
+
+    /// Road centre column at `scale`, bent by the curve with depth
+    pub fn center(&self, scale: f32) -> f32 {
+        let depth = 1.0 - scale;
+        self.area.x as f32 + self.area.width as f32 / 2.0 + self.bend * depth * depth
+    }
+
+    /// Road width in columns at `scale`
+    pub fn road_width(&self, scale: f32) -> f32 {
+        (self.width * scale).max(1.0)
+    }
+
+    /// Left and right road edge at `scale`
+    pub fn road_edges(&self, scale: f32) -> (f32, f32) {
+        let half = self.road_width(scale) / 2.0;
+        let center = self.center(scale);
+        (center - half, center + half)
+    }
+
+    /// Column `fraction` of the way across the road at `scale`
+    /// 0.0 is the left edge and 1.0 the right; values outside fall beside it
+    pub fn road_x(&self, fraction: f32, scale: f32) -> f32 {
+        let (left, _) = self.road_edges(scale);
+        left + self.road_width(scale) * fraction
+    }
+
+    /// Column of the boundary left of `lane` (0 = left road edge)
+    pub fn lane_boundary(&self, lane: u16, scale: f32) -> f32 {
+        self.road_x(lane as f32 / self.lanes as f32, scale)
+    }
+
+    /// Centre column of `lane`; out-of-range lanes are clamped to the road
+    pub fn lane_center(&self, lane: i32, scale: f32) -> f32 {
+        self.road_x(lane_fraction(lane, self.lanes), scale)
+    }
+
+    /// Left column of a sprite `width` cells wide centred on `x`
+    /// Clamped so the sprite stays inside the area
+    pub fn sprite_x(&self, x: f32, width: u16) -> u16 {
+        let left = (x - width as f32 / 2.0).round() as i32;
+        let max_x = (self.area.right() as i32 - width as i32).max(self.area.x as i32);
+        left.clamp(self.area.x as i32, max_x) as u16
+    }
+
+    /// Column inside the area, or None if `x` falls outside it
+    pub fn column(&self, x: f32) -> Option<u16> {
+        let x = x.floor();
+        (x >= self.area.x as f32 && x < self.area.right() as f32).then_some(x as u16)
+    }
+}
+
+/// Lanes on the road: the track's `LaneConfig`, unless `lane_count` is set
+pub fn lane_count(state: &GameState) -> u16 {
+    match state.lane_count {
+        count if count > 0 => count.min(MAX_LANES as i32) as u16,
+        _ => get_lane_config(state.track_type).lane_count as u16,
+    }
+}
+
+/// Fraction of the road width at the centre of `lane` out of `lanes`
+/// Out-of-range lanes are clamped to the road
+pub fn lane_fraction(lane: i32, lanes: u16) -> f32 {
+    let lane = lane.clamp(0, lanes as i32 - 1) as f32;
+    (lane + 0.5) / lanes as f32
+}
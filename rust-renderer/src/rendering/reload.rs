@@ -0,0 +1,118 @@
+//! Hot reload of the asset pack during development
+//! The watched file is polled by modification time before each frame; a
+//! changed pack replaces the active registry between frames, and a pack that
+//! fails to load is reported in an overlay while the last good assets stay
+//! on screen
+
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, MutexGuard, PoisonError};
+use std::time::SystemTime;
+use ratatui::{
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    widgets::{Block, Borders, Clear, Paragraph, Wrap},
+    Frame,
+};
+use crate::core::error::Result;
+use super::assets::{self, AssetRegistry};
+
+/// Rows of the error overlay, borders included
+const OVERLAY_HEIGHT: u16 = 5;
+
+/// What the file looked like when it was last loaded
+/// The length catches quick edits on filesystems with coarse timestamps.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct Stamp {
+    modified: Option<SystemTime>,
+    len: u64,
+}
+
+impl Stamp {
+    fn of(path: &Path) -> Option<Self> {
+        let meta = std::fs::metadata(path).ok()?;
+        Some(Stamp { modified: meta.modified().ok(), len: meta.len() })
+    }
+}
+
+struct Watch {
+    path: PathBuf,
+    stamp: Option<Stamp>,
+    /// Why the current version of the file did not load
+    error: Option<String>,
+}
+
+impl Watch {
+    /// Load the file as it is now and remember its stamp
+    fn load(&mut self) -> Result<()> {
+        self.stamp = Stamp::of(&self.path);
+        let loaded = AssetRegistry::load_pack(&self.path);
+        self.error = loaded.as_ref().err().map(|e| e.message.clone());
+        assets::set_active(loaded?);
+        Ok(())
+    }
+}
+
+static WATCH: Mutex<Option<Watch>> = Mutex::new(None);
+
+fn lock() -> MutexGuard<'static, Option<Watch>> {
+    WATCH.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+/// Load the asset pack at `path` and keep reloading it whenever it changes
+/// Watching continues even if this first load fails, so fixing the file
+/// recovers without a restart. Replaces any earlier watch.
+pub fn watch(path: &Path) -> Result<()> {
+    let mut watch = Watch { path: path.to_path_buf(), stamp: None, error: None };
+    let loaded = watch.load();
+    *lock() = Some(watch);
+    loaded
+}
+
+/// Stop watching; the assets last loaded stay active
+pub fn unwatch() {
+    *lock() = None;
+}
+
+/// Reload the watched pack if it changed since the last look
+/// Returns whether a new registry was swapped in. A missing file counts as
+/// unchanged, since editors often replace files by deleting and renaming.
+pub fn poll() -> bool {
+    let mut guard = lock();
+    let Some(watch) = guard.as_mut() else {
+        return false;
+    };
+    match Stamp::of(&watch.path) {
+        Some(stamp) if watch.stamp != Some(stamp) => watch.load().is_ok(),
+        _ => false,
+    }
+}
+
+/// Why the watched pack last failed to load, if its current version is bad
+pub fn error() -> Option<String> {
+    lock().as_ref().and_then(|watch| watch.error.clone())
+}
+
+/// Draw the load error, if any, over the bottom of the frame
+pub fn render_overlay(f: &mut Frame) {
+    let Some(message) = error() else {
+        return;
+    };
+    let area = f.area();
+    let height = OVERLAY_HEIGHT.min(area.height);
+    let overlay = Rect::new(area.x, area.bottom() - height, area.width, height);
+
+    f.render_widget(Clear, overlay);
+    f.render_widget(
+        Paragraph::new(message)
+            .wrap(Wrap { trim: true })
+            .style(Style::default().fg(Color::White))
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .border_style(Style::default().fg(Color::Red))
+                    .title("Asset pack not reloaded")
+                    .title_style(Style::default().fg(Color::Red).add_modifier(Modifier::BOLD)),
+            ),
+        overlay,
+    );
+}
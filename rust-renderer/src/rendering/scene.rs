@@ -0,0 +1,121 @@
+//! Renderer state carried from frame to frame
+//! The game state says where everything is; the scene adds what only the
+//! renderer keeps: its own clock, so animations run on time rather than
+//! track distance, the live particles and the frame pacer deciding which
+//! effects are drawn.
+
+use std::time::Instant;
+use crate::core::types::GameState;
+use super::pacing::{Effects, FramePacer};
+use super::particles::ParticleSystem;
+
+/// When a frame is drawn, as seen by the renderer
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct FrameTime {
+    /// Frames drawn so far, this one included; 0 outside a renderer
+    pub frame: u64,
+    /// Seconds since the previous frame
+    pub delta: f32,
+    /// Seconds since the first frame
+    pub elapsed: f32,
+}
+
+impl FrameTime {
+    /// Whether a blink at `per_second` blinks is in its visible half
+    /// Starts visible, so still frames show everything.
+    pub fn blink(&self, per_second: f32) -> bool {
+        ((self.elapsed * per_second * 2.0) as u64).is_multiple_of(2)
+    }
+}
+
+/// Monotonic frame counter and wall-clock delta between frames
+#[derive(Clone, Copy, Debug, Default)]
+pub struct FrameClock {
+    last: Option<Instant>,
+    time: FrameTime,
+}
+
+impl FrameClock {
+    /// Start a frame now
+    /// The first frame has no delta, since nothing was drawn before it.
+    pub fn tick(&mut self) -> FrameTime {
+        let now = Instant::now();
+        let delta = self.last.map_or(0.0, |last| now.duration_since(last).as_secs_f32());
+        self.last = Some(now);
+        self.step(delta)
+    }
+
+    /// Start a frame `delta` seconds after the previous one
+    pub fn step(&mut self, delta: f32) -> FrameTime {
+        let delta = delta.max(0.0);
+        self.time = FrameTime { frame: self.time.frame + 1, delta, elapsed: self.time.elapsed + delta };
+        self.time
+    }
+
+    /// The frame being drawn
+    pub fn time(&self) -> FrameTime {
+        self.time
+    }
+}
+
+/// Everything a renderer keeps between frames
+/// The default scene is a still frame at full quality: no time passed and
+/// no particles.
+#[derive(Clone, Debug, Default)]
+pub struct Scene {
+    clock: FrameClock,
+    particles: ParticleSystem,
+    pacer: FramePacer,
+}
+
+impl Scene {
+    /// Start a game frame now, advancing the particles by the frame delta
+    pub fn tick(&mut self, state: &GameState) -> FrameTime {
+        let time = self.clock.tick();
+        self.advance(state, time);
+        time
+    }
+
+    /// Start a game frame `delta` seconds after the previous one
+    pub fn step(&mut self, state: &GameState, delta: f32) -> FrameTime {
+        let time = self.clock.step(delta);
+        self.advance(state, time);
+        time
+    }
+
+    /// Particles are only kept while the quality draws them
+    fn advance(&mut self, state: &GameState, time: FrameTime) {
+        if self.effects().particles {
+            self.particles.update(state, time.delta);
+        } else {
+            self.particles.clear();
+        }
+    }
+
+    /// Start a frame with no game on screen; particles are left as they are
+    pub fn tick_idle(&mut self) -> FrameTime {
+        self.clock.tick()
+    }
+
+    /// The frame being drawn
+    pub fn time(&self) -> FrameTime {
+        self.clock.time()
+    }
+
+    pub fn particles(&self) -> &ParticleSystem {
+        &self.particles
+    }
+
+    /// Effects drawn at the pacer's current quality
+    pub fn effects(&self) -> Effects {
+        self.pacer.effects()
+    }
+
+    pub fn pacer(&self) -> &FramePacer {
+        &self.pacer
+    }
+
+    pub fn pacer_mut(&mut self) -> &mut FramePacer {
+        &mut self.pacer
+    }
+}
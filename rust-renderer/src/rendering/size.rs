@@ -0,0 +1,47 @@
+//! Terminal size guard for the game layouts
+//! Below a per-mode minimum the game is replaced by a screen asking for a
+//! larger terminal, instead of a squashed or clipped frame
+
+use ratatui::{
+    layout::{Alignment, Rect},
+    style::{Color, Modifier, Style},
+    text::Line,
+    widgets::{Paragraph, Wrap},
+    Frame,
+};
+use crate::core::types::{game_modes, GameState};
+
+/// Smallest terminal (columns, rows) each game mode lays out properly in
+/// Leaves the track at least 10 rows tall and the control hints unclipped
+pub fn min_size(state: &GameState) -> (u16, u16) {
+    match state.game_mode {
+        game_modes::SPLIT_SCREEN if state.player2_active => (100, 18),
+        game_modes::CAREER => (80, 22),
+        game_modes::REPLAY => (80, 17),
+        _ => (80, 20),
+    }
+}
+
+/// Whether `area` is large enough for the layout of `state`
+pub fn fits(area: Rect, state: &GameState) -> bool {
+    let (width, height) = min_size(state);
+    area.width >= width && area.height >= height
+}
+
+/// Draw the "please enlarge your terminal" screen
+pub fn render_too_small(f: &mut Frame, area: Rect, state: &GameState) {
+    let (width, height) = min_size(state);
+    let lines = vec![
+        Line::styled("Terminal too small", Style::default().fg(Color::Red).add_modifier(Modifier::BOLD)),
+        Line::raw(format!("please enlarge your terminal (needs {}x{})", width, height)),
+        Line::styled(format!("current size: {}x{}", area.width, area.height), Style::default().fg(Color::DarkGray)),
+    ];
+
+    // Roughly centred; wrapping may take more rows on narrow terminals
+    let top = area.height.saturating_sub(lines.len() as u16) / 2;
+    let text_area = Rect::new(area.x, area.y + top, area.width, area.height - top);
+    f.render_widget(
+        Paragraph::new(lines).alignment(Alignment::Center).wrap(Wrap { trim: true }),
+        text_area,
+    );
+}
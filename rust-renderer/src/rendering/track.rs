@@ -1,18 +1,24 @@
-/// Track rendering module for different track types
-/// Handles highway, city, mountain, desert, and tunnel rendering
+//! Track rendering module for different track types
+//! Handles highway, city, mountain, desert, and tunnel rendering
 
 use ratatui::{
     layout::Rect,
-    style::{Color, Modifier, Style},
-    widgets::Paragraph,
+    style::{Color, Style},
     Frame,
 };
-use crate::core::types::{GameState, track_types};
-use crate::core::utils::{get_road_char, get_track_style, get_track_name, get_weather_icon, get_building_style};
+use crate::core::types::GameState;
+use crate::core::utils::{get_track_style, get_track_name, get_weather_icon};
+use super::assets;
+use super::draw;
 use super::objects::{render_objects, render_player, render_ghost};
 use super::effects::{render_weather_overlay, render_lane_markers};
+use super::particles::render_particles;
+use super::scene::Scene;
+use super::projection::{Projection, DRAW_DISTANCE, REAR_DISTANCE};
 
 /// Render the appropriate track based on track type
+/// Animations run on the `scene` clock and its particles are drawn over the
+/// cars, seen from the car at `player_dist`
 pub fn render_track(
     f: &mut Frame,
     area: Rect,
@@ -20,6 +26,7 @@ pub fn render_track(
     player_pos: i32,
     player_dist: f32,
     _is_primary: bool,
+    scene: &Scene,
 ) {
     let track_name = get_track_name(state.track_type);
     let weather_icon = get_weather_icon(state.weather);
@@ -37,14 +44,17 @@ pub fn render_track(
     
     let inner = block.inner(area);
     f.render_widget(block, area);
+    if inner.is_empty() {
+        return;
+    }
     
     // Track-specific rendering
     match state.track_type {
-        1 => render_city_track(f, inner, state, player_pos, player_dist),
-        2 => render_mountain_track(f, inner, state, player_pos, player_dist),
-        3 => render_desert_track(f, inner, state, player_pos, player_dist),
-        4 => render_tunnel_track(f, inner, state, player_pos, player_dist),
-        _ => render_highway_track(f, inner, state, player_pos, player_dist),
+        1 => render_city_track(f, inner, state, player_pos, player_dist, scene),
+        2 => render_mountain_track(f, inner, state, player_pos, player_dist, scene),
+        3 => render_desert_track(f, inner, state, player_pos, player_dist, scene),
+        4 => render_tunnel_track(f, inner, state, player_pos, player_dist, scene),
+        _ => render_highway_track(f, inner, state, player_pos, player_dist, scene),
     }
 }
 
@@ -55,21 +65,21 @@ fn render_highway_track(
     state: &GameState,
     player_pos: i32,
     player_dist: f32,
+    scene: &Scene,
 ) {
-    let lane_width = area.width / 3;
-    let road_height = area.height;
-    let curve = (state.curve_offset * 3.0) as i16;
+    let projection = Projection::new(area, state, 3.0);
     
-    render_road_base(f, area, state);
-    render_lane_markers(f, area, state, lane_width, road_height, curve);
-    render_objects(f, area, state, lane_width, road_height, curve, player_dist);
-    render_player(f, area, state, player_pos, lane_width, curve, state.player_car_type, true);
+    render_road_base(f, &projection, state, player_dist);
+    render_lane_markers(f, &projection, player_dist);
+    render_objects(f, &projection, state, player_dist, scene.time());
+    render_player(f, &projection, state, player_pos, state.player_car_type, true, scene.time());
+    render_particles(f, &projection, scene.particles(), state, player_dist);
     
     if state.replay_mode && state.ghost_distance > 0.0 {
-        render_ghost(f, area, state, lane_width, road_height, curve, player_dist);
+        render_ghost(f, &projection, state, player_dist);
     }
     
-    render_weather_overlay(f, area, state);
+    render_weather_overlay(f, area, state, scene);
 }
 
 /// Render city track with buildings and urban environment
@@ -79,17 +89,17 @@ fn render_city_track(
     state: &GameState,
     player_pos: i32,
     player_dist: f32,
+    scene: &Scene,
 ) {
-    let lane_width = area.width / 3;
-    let road_height = area.height;
-    let curve = (state.curve_offset * 2.0) as i16;
+    let projection = Projection::new(area, state, 2.0);
     
-    render_road_base(f, area, state);
-    render_buildings(f, area, state, player_dist);
-    render_lane_markers(f, area, state, lane_width, road_height, curve);
-    render_objects(f, area, state, lane_width, road_height, curve, player_dist);
-    render_player(f, area, state, player_pos, lane_width, curve, state.player_car_type, true);
-    render_weather_overlay(f, area, state);
+    render_road_base(f, &projection, state, player_dist);
+    render_buildings(f, &projection, state, player_dist, scene.effects().windows);
+    render_lane_markers(f, &projection, player_dist);
+    render_objects(f, &projection, state, player_dist, scene.time());
+    render_player(f, &projection, state, player_pos, state.player_car_type, true, scene.time());
+    render_particles(f, &projection, scene.particles(), state, player_dist);
+    render_weather_overlay(f, area, state, scene);
 }
 
 /// Render mountain track with elevation and peaks
@@ -99,18 +109,17 @@ fn render_mountain_track(
     state: &GameState,
     player_pos: i32,
     player_dist: f32,
+    scene: &Scene,
 ) {
-    let lane_width = area.width / 3;
-    let road_height = area.height;
-    let curve = (state.curve_offset * 4.0) as i16;
-    let elevation = (state.elevation * 5.0) as i16;
+    let projection = Projection::new(area, state, 4.0);
     
-    render_mountain_bg(f, area, elevation);
-    render_road_base(f, area, state);
-    render_lane_markers(f, area, state, lane_width, road_height, curve);
-    render_objects(f, area, state, lane_width, road_height, curve, player_dist);
-    render_player(f, area, state, player_pos, lane_width, curve, state.player_car_type, true);
-    render_weather_overlay(f, area, state);
+    render_mountain_bg(f, &projection);
+    render_road_base(f, &projection, state, player_dist);
+    render_lane_markers(f, &projection, player_dist);
+    render_objects(f, &projection, state, player_dist, scene.time());
+    render_player(f, &projection, state, player_pos, state.player_car_type, true, scene.time());
+    render_particles(f, &projection, scene.particles(), state, player_dist);
+    render_weather_overlay(f, area, state, scene);
 }
 
 /// Render desert track with sparse vegetation
@@ -120,17 +129,17 @@ fn render_desert_track(
     state: &GameState,
     player_pos: i32,
     player_dist: f32,
+    scene: &Scene,
 ) {
-    let lane_width = area.width / 3;
-    let road_height = area.height;
-    let curve = (state.curve_offset * 2.5) as i16;
+    let projection = Projection::new(area, state, 2.5);
     
-    render_desert_bg(f, area, player_dist);
-    render_road_base(f, area, state);
-    render_lane_markers(f, area, state, lane_width, road_height, curve);
-    render_objects(f, area, state, lane_width, road_height, curve, player_dist);
-    render_player(f, area, state, player_pos, lane_width, curve, state.player_car_type, true);
-    render_weather_overlay(f, area, state);
+    render_road_base(f, &projection, state, player_dist);
+    render_desert_bg(f, &projection, player_dist);
+    render_lane_markers(f, &projection, player_dist);
+    render_objects(f, &projection, state, player_dist, scene.time());
+    render_player(f, &projection, state, player_pos, state.player_car_type, true, scene.time());
+    render_particles(f, &projection, scene.particles(), state, player_dist);
+    render_weather_overlay(f, area, state, scene);
 }
 
 /// Render tunnel track with walls and lighting
@@ -140,37 +149,60 @@ fn render_tunnel_track(
     state: &GameState,
     player_pos: i32,
     player_dist: f32,
+    scene: &Scene,
 ) {
-    let lane_width = area.width / 3;
-    let road_height = area.height;
-    let curve = (state.curve_offset * 1.5) as i16;
+    let projection = Projection::new(area, state, 1.5);
     
-    render_tunnel_walls(f, area, state, player_dist);
-    render_road_base(f, area, state);
-    render_lane_markers(f, area, state, lane_width, road_height, curve);
-    render_objects(f, area, state, lane_width, road_height, curve, player_dist);
-    render_player(f, area, state, player_pos, lane_width, curve, state.player_car_type, true);
+    render_road_base(f, &projection, state, player_dist);
+    render_tunnel_walls(f, &projection, state, player_dist);
+    render_lane_markers(f, &projection, player_dist);
+    render_objects(f, &projection, state, player_dist, scene.time());
+    render_player(f, &projection, state, player_pos, state.player_car_type, true, scene.time());
+    render_particles(f, &projection, scene.particles(), state, player_dist);
+}
+
+/// Track distance covered by one light or dark band of the road surface
+const ROAD_BAND_LENGTH: f32 = 4.0;
+
+/// Whether a road row at `distance` ahead falls on an even band
+/// Bands are fixed to the track, so they shorten towards the horizon and
+/// scroll past as the player moves
+fn even_band(distance: f32, player_dist: f32, length: f32) -> bool {
+    ((distance + player_dist) / length).floor() as i64 % 2 == 0
 }
 
-/// Render the road base with animated pattern
-fn render_road_base(f: &mut Frame, area: Rect, state: &GameState) {
-    let road_char = get_road_char(state.weather);
-    let offset = (state.player_distance as u16) % 2;
+/// Render the road surface between its projected edges, with rumble strips
+fn render_road_base(f: &mut Frame, projection: &Projection, state: &GameState, player_dist: f32) {
+    let assets = assets::active();
+    let road_char = assets.road(state.weather);
+    let area = projection.area();
+    let buf = f.buffer_mut();
     
-    for y in (0..area.height).step_by(2) {
-        let actual_y = (y + offset) % area.height;
-        if actual_y + area.y < area.bottom() {
-            let road_line = road_char.repeat(area.width as usize);
-            f.render_widget(
-                Paragraph::new(road_line).style(Style::default().fg(Color::DarkGray)),
-                Rect::new(area.x, area.y + actual_y, area.width, 1),
-            );
+    for y in projection.horizon()..area.bottom() {
+        let (Some(scale), Some(distance)) = (projection.row_scale(y), projection.distance_at(y)) else {
+            continue;
+        };
+        let (left, right) = projection.road_edges(scale);
+        let left = left.round().max(area.x as f32) as u16;
+        let right = (right.round() as u16).min(area.right());
+        if left >= right {
+            continue;
         }
+        
+        let even = even_band(distance, player_dist, ROAD_BAND_LENGTH);
+        let surface = if even { Color::DarkGray } else { Color::Rgb(70, 70, 70) };
+        draw::fill(buf, area, left, y, right - left, road_char, Style::default().fg(surface));
+        
+        // Red and white kerbs on both edges
+        let kerb = Style::default().fg(if even { Color::Red } else { Color::White });
+        draw::put(buf, area, left, y, "▌", kerb);
+        draw::put(buf, area, right - 1, y, "▐", kerb);
     }
 }
 
-/// Render city buildings on both sides of the road
-fn render_buildings(f: &mut Frame, area: Rect, state: &GameState, player_dist: f32) {
+/// Render city buildings on both sides of the road, scaled with distance
+/// Lit windows are left out at the lowest quality
+fn render_buildings(f: &mut Frame, projection: &Projection, state: &GameState, player_dist: f32, windows: bool) {
     if state.building_count == 0 || state.building_positions.is_null() {
         return;
     }
@@ -179,103 +211,129 @@ fn render_buildings(f: &mut Frame, area: Rect, state: &GameState, player_dist: f
     let distances = unsafe { std::slice::from_raw_parts(state.building_distances, state.building_count as usize) };
     let heights = unsafe { std::slice::from_raw_parts(state.building_heights, state.building_count as usize) };
     let types = unsafe { std::slice::from_raw_parts(state.building_types, state.building_count as usize) };
+    let area = projection.area();
     
-    for i in 0..state.building_count as usize {
-        let rel_dist = distances[i] - player_dist;
-        if rel_dist > -20.0 && rel_dist < 60.0 {
-            let screen_y = area.y + area.height - ((rel_dist + 20.0) * area.height as f32 / 80.0) as u16;
-            let building_height = heights[i].min(15) as u16;
-            
-            let x = if positions[i] < 0 {
-                area.x
-            } else {
-                area.x + area.width - 8
-            };
-            
-            if screen_y < area.bottom() {
-                render_building(f, x, screen_y, building_height, types[i]);
-            }
-        }
+    // Far to near, so closer buildings cover the ones behind them
+    let mut order: Vec<usize> = (0..state.building_count as usize).collect();
+    order.sort_by(|&a, &b| distances[b].total_cmp(&distances[a]));
+    
+    for i in order {
+        let Some(at) = projection.project(distances[i] - player_dist) else {
+            continue;
+        };
+        let width = ((6.0 * at.scale).round() as u16).clamp(1, 6);
+        let height = ((heights[i].clamp(0, 15) as f32 * at.scale).round() as u16).clamp(1, 10);
+        let (left, right) = projection.road_edges(at.scale);
+        
+        let x = if positions[i] < 0 {
+            left - 1.0 - width as f32
+        } else {
+            right + 1.0
+        };
+        let x = x.round().clamp(area.x as f32, area.right().saturating_sub(width) as f32) as u16;
+        
+        render_building(f, projection, x, at.y, width, height, types[i], windows);
     }
 }
 
-/// Render individual building with windows
-fn render_building(f: &mut Frame, x: u16, y: u16, height: u16, btype: i32) {
-    let (char_set, color) = get_building_style(btype);
+/// Render individual building with windows, standing on row `y`
+#[allow(clippy::too_many_arguments)]
+fn render_building(f: &mut Frame, projection: &Projection, x: u16, y: u16, width: u16, height: u16, btype: i32, windows: bool) {
+    let assets = assets::active();
+    let building = assets.building(btype);
+    let color = building.color;
+    let wall: String = building.wall.chars().cycle().take(width as usize).collect();
+    let area = projection.area();
+    let buf = f.buffer_mut();
     
-    for i in 0..height.min(10) {
-        if y >= i {
-            f.render_widget(
-                Paragraph::new(char_set).style(Style::default().fg(color)),
-                Rect::new(x, y - i, 6, 1),
-            );
-            
-            // Windows for glass buildings
-            if i % 2 == 0 && btype == 1 {
-                f.render_widget(
-                    Paragraph::new("▫▫").style(Style::default().fg(Color::Yellow)),
-                    Rect::new(x + 2, y - i, 2, 1),
-                );
-            }
+    for i in 0..height {
+        let Some(row) = y.checked_sub(i).filter(|&row| row >= area.y) else {
+            break;
+        };
+        draw::text(buf, area, x, row, &wall, Style::default().fg(color));
+        
+        // Windows for glass buildings
+        if windows && i % 2 == 0 && btype == 1 && width >= 4 {
+            draw::text(buf, area, x + width / 2 - 1, row, "▫▫", Style::default().fg(Color::Yellow));
         }
     }
 }
 
-/// Render mountain peaks in background
-fn render_mountain_bg(f: &mut Frame, area: Rect, elevation: i16) {
-    for x in (0..area.width).step_by(5) {
-        let peak_y = (area.height / 3) + (elevation.abs() % 10) as u16;
-        f.render_widget(
-            Paragraph::new("▲").style(Style::default().fg(Color::Rgb(100, 100, 100))),
-            Rect::new(area.x + x, area.y + peak_y, 1, 1),
-        );
+/// Render mountain peaks along the horizon
+/// The horizon itself follows `elevation`, so the range rises and sinks with
+/// the road
+fn render_mountain_bg(f: &mut Frame, projection: &Projection) {
+    let area = projection.area();
+    let assets = assets::active();
+    let peak_glyph = assets.peak();
+    let style = Style::default().fg(peak_glyph.color);
+    let buf = f.buffer_mut();
+    
+    for (i, x) in (0..area.width).step_by(5).enumerate() {
+        let peak = 1 + (i % 3) as u16;
+        let Some(y) = projection.horizon().checked_sub(peak).filter(|&y| y >= area.y) else {
+            continue;
+        };
+        draw::text(buf, area, area.x + x, y, &peak_glyph.symbol, style);
     }
 }
 
-/// Render desert background with cacti
-fn render_desert_bg(f: &mut Frame, area: Rect, distance: f32) {
-    let cactus_spacing = 15;
-    for x in (0..area.width).step_by(cactus_spacing) {
-        let offset = (distance as u16 + x) % (cactus_spacing as u16);
-        if offset < 3 {
-            f.render_widget(
-                Paragraph::new("🌵").style(Style::default().fg(Color::Green)),
-                Rect::new(area.x + x, area.bottom() - 8, 2, 1),
-            );
+/// Render desert cacti along the roadside, alternating sides
+fn render_desert_bg(f: &mut Frame, projection: &Projection, distance: f32) {
+    let cactus_spacing = 15.0;
+    let first = (distance / cactus_spacing).floor() as i64;
+    let area = projection.area();
+    let assets = assets::active();
+    let cactus = assets.cactus();
+    let width = cactus.width.max(1) as f32;
+    let style = Style::default().fg(cactus.color);
+    let buf = f.buffer_mut();
+    
+    for k in first..first + (DRAW_DISTANCE / cactus_spacing) as i64 + 2 {
+        let Some(at) = projection.project(k as f32 * cactus_spacing - distance) else {
+            continue;
+        };
+        let (left, right) = projection.road_edges(at.scale);
+        let x = if k % 2 == 0 { left - 1.0 - width } else { right + 1.0 };
+        if x < area.x as f32 || x + width > area.right() as f32 {
+            continue;
         }
+        draw::text(buf, area, x as u16, at.y, &cactus.symbol, style);
     }
 }
 
-/// Render tunnel walls with lighting effects
-fn render_tunnel_walls(f: &mut Frame, area: Rect, state: &GameState, distance: f32) {
-    let light_spacing = 10;
-    let light_offset = (distance as u16) % light_spacing;
+/// Render tunnel walls along the road edges with ceiling lights
+fn render_tunnel_walls(f: &mut Frame, projection: &Projection, state: &GameState, distance: f32) {
+    let light_spacing = 10.0;
+    let area = projection.area();
+    let wall = Style::default().fg(Color::Rgb(40, 40, 40));
+    let buf = f.buffer_mut();
     
-    for y in 0..area.height {
-        // Left wall
-        f.render_widget(
-            Paragraph::new("▌").style(Style::default().fg(Color::Rgb(40, 40, 40))),
-            Rect::new(area.x, area.y + y, 1, 1),
-        );
+    for y in projection.horizon()..area.bottom() {
+        let (Some(scale), Some(ahead)) = (projection.row_scale(y), projection.distance_at(y)) else {
+            continue;
+        };
+        let (left, right) = projection.road_edges(scale);
         
-        // Right wall
-        f.render_widget(
-            Paragraph::new("▐").style(Style::default().fg(Color::Rgb(40, 40, 40))),
-            Rect::new(area.x + area.width - 1, area.y + y, 1, 1),
-        );
+        // Left and right wall
+        if let Some(x) = projection.column(left - 1.0) {
+            draw::put(buf, area, x, y, "▌", wall);
+        }
+        if let Some(x) = projection.column(right) {
+            draw::put(buf, area, x, y, "▐", wall);
+        }
         
-        // Ceiling lights
-        if y % light_spacing == light_offset {
+        // Ceiling lights, reflected on the road centre
+        let next = projection.distance_at(y + 1).unwrap_or(-REAR_DISTANCE);
+        let passes_light = ((ahead + distance) / light_spacing).floor() != ((next + distance) / light_spacing).floor();
+        if passes_light && let Some(x) = projection.column(projection.center(scale)) {
             let brightness = 1.0 - state.tunnel_darkness;
             let light_color = Color::Rgb(
                 (255.0 * brightness) as u8,
                 (255.0 * brightness) as u8,
                 (200.0 * brightness) as u8,
             );
-            f.render_widget(
-                Paragraph::new("•").style(Style::default().fg(light_color)),
-                Rect::new(area.x + area.width / 2, area.y + y, 1, 1),
-            );
+            draw::put(buf, area, x, y, "•", Style::default().fg(light_color));
         }
     }
 }
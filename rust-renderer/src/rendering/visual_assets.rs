@@ -1,6 +1,6 @@
-/// Visual Assets Module - Enhanced graphics and styling
-/// Provides detailed car designs, powerup visuals, and environmental assets
-/// Inspired by modern mobile racing games with colorful, detailed graphics
+//! Visual Assets Module - Enhanced graphics and styling
+//! Provides detailed car designs, powerup visuals, and environmental assets
+//! Inspired by modern mobile racing games with colorful, detailed graphics
 
 use ratatui::style::Color;
 
@@ -382,6 +382,14 @@ pub fn get_dust_particle() -> ParticleEffect {
     }
 }
 
+pub fn get_spray_particle() -> ParticleEffect {
+    ParticleEffect {
+        symbol: "'",
+        color: Color::Rgb(140, 170, 210),
+        lifetime: 6,
+    }
+}
+
 /// Color palette for consistent theming
 pub struct ColorPalette {
     pub primary: Color,
@@ -0,0 +1,199 @@
+//! Asset registry - classic and detailed styles, fallbacks, asset packs and
+//! switching what the renderers draw with
+
+mod common;
+
+use common::*;
+use rust_renderer::core::types::{asset_styles, game_modes, track_types, weather};
+use rust_renderer::rendering::assets::{self, AssetRegistry, CAR_TYPES};
+use rust_renderer::{
+    ratatui_asset_style, ratatui_last_error_code, ratatui_load_asset_pack, ratatui_set_asset_pack,
+    ratatui_set_asset_style, ErrorCode,
+};
+
+fn scene(track_type: i32) -> String {
+    let objects = Objects::standard();
+    let mut state = base_state(game_modes::SINGLE_PLAYER, track_type, weather::CLEAR);
+    objects.attach(&mut state);
+    render_to_text(100, 40, |f| rust_renderer::render_game(f, &state))
+}
+
+#[test]
+fn classic_sprites_keep_their_sizes() {
+    let registry = AssetRegistry::classic();
+
+    for car_type in 0..CAR_TYPES {
+        let car = registry.car(car_type, false);
+        assert_eq!((car.large.width, car.large.height()), (7, 4), "car {}", car_type);
+        assert_eq!((car.medium.width, car.medium.height()), (5, 2), "car {}", car_type);
+        assert_eq!((car.small.width, car.small.height()), (3, 1), "car {}", car_type);
+    }
+    assert_eq!(registry.car(3, true).label, "BOSS!");
+}
+
+#[test]
+fn detailed_cars_have_three_rows() {
+    let registry = AssetRegistry::detailed();
+
+    assert_eq!(registry.style(), asset_styles::DETAILED);
+    for car_type in 0..CAR_TYPES {
+        assert_eq!(registry.car(car_type, false).large.height(), 3, "car {}", car_type);
+    }
+    assert_eq!(registry.car(8, false).large.width, 7);
+    assert_eq!(registry.car(0, true).label, "BOSS");
+}
+
+#[test]
+fn unknown_types_fall_back() {
+    let registry = AssetRegistry::classic();
+
+    assert_eq!(registry.car(42, false), registry.car(CAR_TYPES - 1, false));
+    assert_eq!(registry.car(-1, false).label, "CAR");
+    assert_eq!(registry.powerup(99).icon.symbol, "⚠");
+    assert_eq!(registry.powerup(-3).name, "UNKNOWN");
+    assert_eq!(registry.building(7), registry.building(0));
+    assert_eq!(registry.road(9), registry.road(weather::CLEAR));
+}
+
+#[test]
+fn style_switches_what_is_drawn() {
+    let _serial = serial();
+    let _assets = RestoreAssets;
+    assert_eq!(ratatui_asset_style(), asset_styles::CLASSIC);
+    let classic = scene(track_types::DESERT);
+
+    assert!(ratatui_set_asset_style(asset_styles::DETAILED));
+    assert_eq!(ratatui_asset_style(), asset_styles::DETAILED);
+    let detailed = scene(track_types::DESERT);
+
+    assert!(classic.contains("🏎SPD") && classic.contains(" YOU "));
+    assert!(detailed.contains("SPEED") && detailed.contains("MAGNET"));
+    assert!(detailed.contains("│●│"), "player drawn with the detailed car");
+    assert!(!detailed.contains(" YOU "));
+}
+
+#[test]
+fn unknown_style_is_rejected() {
+    let _serial = serial();
+
+    assert!(!ratatui_set_asset_style(7));
+    assert_eq!(ratatui_last_error_code(), ErrorCode::InvalidArgument);
+    assert_eq!(ratatui_asset_style(), asset_styles::CLASSIC);
+}
+
+const PACK: &str = r##"
+base = "classic"
+
+[cars.12]
+large = [" ▄▀▀▄ ", "▐████▌", " ▀  ▀ ", " UFO  "]
+medium = ["▄▀▀▄", "▀  ▀"]
+small = "▄▀▄"
+color = "#ff8800"
+label = "UFO"
+
+[powerups.2]
+icon = "»"
+color = "light-magenta"
+
+[hud]
+boost = { symbol = "TURBO" }
+
+[animations]
+boost = { frames = ["*", "+"], step = 1.5 }
+"##;
+
+#[test]
+fn built_in_styles_pass_validation() {
+    AssetRegistry::classic().validate().unwrap();
+    AssetRegistry::detailed().validate().unwrap();
+    assert_eq!(AssetRegistry::parse_pack("").unwrap(), AssetRegistry::classic());
+    assert_eq!(AssetRegistry::parse_pack("{\"base\": \"detailed\"}").unwrap(), AssetRegistry::detailed());
+}
+
+#[test]
+fn packs_add_car_types_and_override_entries() {
+    let registry = AssetRegistry::parse_pack(PACK).unwrap();
+
+    let ufo = registry.car(12, false);
+    assert_eq!((ufo.large.width, ufo.medium.width, ufo.small.width), (6, 4, 3));
+    assert_eq!(ufo.color, ratatui::style::Color::Rgb(255, 136, 0));
+    assert_eq!(registry.car_types().collect::<Vec<_>>(), (0..CAR_TYPES).chain([12]).collect::<Vec<_>>());
+    assert_eq!(registry.car(11, false).label, "CAR");
+
+    // Fields left out keep the base entry
+    let boost = registry.powerup(2);
+    assert_eq!((boost.icon.symbol.as_str(), boost.name.as_str()), ("»", "BOOST"));
+    assert_eq!(registry.hud().boost.symbol, "TURBO");
+    assert_eq!(registry.hud().boost.color, AssetRegistry::classic().hud().boost.color);
+    assert_eq!(registry.animations().boost.frame_at(2.0), "+");
+    assert_eq!(registry.animations().wheel, None, "classic cars have no wheels");
+    assert_eq!(registry.car(3, false), AssetRegistry::classic().car(3, false));
+}
+
+#[test]
+fn packs_can_add_wheels() {
+    let registry = AssetRegistry::parse_pack("[animations]\nwheel = { frames = [\"o\", \"O\"] }").unwrap();
+    let wheel = registry.animations().wheel.as_ref().unwrap();
+    assert_eq!(wheel.frames, ["o", "O"]);
+    assert_eq!(wheel.step, AssetRegistry::detailed().animations().wheel.as_ref().unwrap().step);
+}
+
+#[test]
+fn json_and_toml_packs_agree() {
+    let json = r##"{
+        "cars": { "12": { "large": [" ▄▀▀▄ ", "▐████▌", " ▀  ▀ ", " UFO  "], "medium": ["▄▀▀▄", "▀  ▀"],
+                          "small": "▄▀▄", "color": "#ff8800", "label": "UFO" } },
+        "powerups": { "2": { "icon": "»", "color": "light-magenta" } },
+        "hud": { "boost": { "symbol": "TURBO" } },
+        "animations": { "boost": { "frames": ["*", "+"], "step": 1.5 } }
+    }"##;
+    assert_eq!(AssetRegistry::parse_pack(json).unwrap(), AssetRegistry::parse_pack(PACK).unwrap());
+}
+
+#[test]
+fn sprites_that_do_not_fit_are_rejected() {
+    let bad = [
+        ("[cars.10]\nlarge = [\"██████████████\"]", "cars.10.large"),
+        ("[cars.1]\nmedium = [\"▄████████▄\"]", "cars.1.medium"),
+        ("[boss]\nlarge = [\"a\", \"b\", \"c\", \"d\", \"e\"]", "cars.boss.large"),
+        ("[powerups.0]\nicon = \"CONE\"", "powerups.0.icon"),
+        ("[road]\nrain = \"~~\"", "road.rain"),
+        ("[buildings.1]\nwall = \"🏢🏢\"", "buildings.1.wall"),
+        ("[animations]\nshield = { frames = [] }", "animations.shield"),
+        ("[animations]\nwheel = { frames = [\"◐◐\"] }", "animations.wheel"),
+        ("[cars.2]\ncolour = \"red\"", "cars.2.colour"),
+        ("[cars.2]\ncolor = \"reddish\"", "cars.2.color"),
+        ("[cars.fast]\nlabel = \"X\"", "cars.fast"),
+        ("base = \"retro\"", "retro"),
+        ("cars = 3", "cars"),
+    ];
+
+    for (pack, path) in bad {
+        let err = AssetRegistry::parse_pack(pack).unwrap_err();
+        assert_eq!(err.code, ErrorCode::InvalidAssets, "{}", pack);
+        assert!(err.message.contains(path), "{} -> {}", pack, err.message);
+    }
+}
+
+#[test]
+fn packs_are_loaded_over_ffi() {
+    let _serial = serial();
+    let _assets = RestoreAssets;
+    let path = std::env::temp_dir().join(format!("assets-{}.toml", std::process::id()));
+    std::fs::write(&path, PACK).unwrap();
+    let c_path = std::ffi::CString::new(path.to_str().unwrap()).unwrap();
+
+    assert!(unsafe { ratatui_load_asset_pack(c_path.as_ptr()) });
+    std::fs::remove_file(&path).unwrap();
+    assert_eq!(assets::active().hud().boost.symbol, "TURBO");
+    assert!(scene(track_types::HIGHWAY).contains("TURBO"));
+
+    // A bad pack leaves the loaded one active
+    let bad = std::ffi::CString::new("[cars.1]\nsmall = \"▄▄▄▄▄▄▄▄▄▄\"").unwrap();
+    assert!(!unsafe { ratatui_set_asset_pack(bad.as_ptr()) });
+    assert_eq!(ratatui_last_error_code(), ErrorCode::InvalidAssets);
+    assert_eq!(assets::active().hud().boost.symbol, "TURBO");
+
+    assert!(!unsafe { ratatui_load_asset_pack(c_path.as_ptr()) });
+    assert_eq!(ratatui_last_error_code(), ErrorCode::Io);
+}
@@ -0,0 +1,109 @@
+//! Generated host bindings - checked against the Rust definitions they mirror
+
+use std::process::Command;
+use rust_renderer::core::layout::{ABI_VERSION, GAME_STATE_FIELDS, INPUT_STATE_FIELDS};
+use rust_renderer::core::types::{game_modes, obstacle_types, INPUT_TEXT_CAPACITY};
+
+const HEADER: &str = include_str!("../bindings/rust_renderer.h");
+const CSHARP: &str = include_str!("../bindings/RatatuiFFI.g.cs");
+
+/// Body of `name` in the generated source, from its opening brace to the closing one
+fn body<'a>(source: &'a str, start: &str) -> &'a str {
+    let from = source.find(start).unwrap_or_else(|| panic!("{} not generated", start));
+    let rest = &source[from..];
+    &rest[..rest.find("\n}").expect("unterminated block")]
+}
+
+fn pascal_case(snake: &str) -> String {
+    snake
+        .split('_')
+        .map(|w| w[..1].to_uppercase() + &w[1..])
+        .collect()
+}
+
+#[test]
+fn checked_in_bindings_are_current() {
+    let generated = [
+        (HEADER, include_str!(concat!(env!("OUT_DIR"), "/rust_renderer.h"))),
+        (CSHARP, include_str!(concat!(env!("OUT_DIR"), "/RatatuiFFI.g.cs"))),
+    ];
+    for (checked_in, generated) in generated {
+        assert!(checked_in == generated, "bindings/ is stale, rebuild with UPDATE_BINDINGS=1");
+    }
+}
+
+#[test]
+fn structs_list_every_field_in_order() {
+    for (name, fields) in [("GameState", &GAME_STATE_FIELDS[..]), ("InputState", &INPUT_STATE_FIELDS[..])] {
+        let c = body(HEADER, &format!("typedef struct {} {{", name));
+        let cs = body(CSHARP, &format!("public struct {}\n", name));
+
+        let c_names: Vec<&str> = c
+            .lines()
+            .skip(1)
+            .filter_map(|l| l.trim().strip_suffix(';'))
+            .map(|decl| decl.rsplit([' ', '*']).next().unwrap())
+            .map(|name| name.split('[').next().unwrap())
+            .collect();
+        let cs_names: Vec<&str> = cs
+            .lines()
+            .filter_map(|l| l.trim().strip_suffix(';'))
+            .map(|decl| decl.rsplit(' ').next().unwrap())
+            .collect();
+
+        let rust: Vec<&str> = fields.iter().map(|&(f, _)| f).collect();
+        let rust_pascal: Vec<String> = rust.iter().map(|f| pascal_case(f)).collect();
+        assert_eq!(c_names, rust, "C {} out of sync", name);
+        assert_eq!(cs_names, rust_pascal, "C# {} out of sync", name);
+    }
+}
+
+#[test]
+fn constants_are_exported() {
+    assert!(HEADER.contains(&format!("#define RATATUI_ABI_VERSION {}\n", ABI_VERSION)));
+    assert!(CSHARP.contains(&format!("public const uint AbiVersion = {};", ABI_VERSION)));
+
+    assert!(HEADER.contains(&format!("uint32_t text[{}];", INPUT_TEXT_CAPACITY)));
+    assert!(CSHARP.contains(&format!("SizeConst = {})] public uint[] Text;", INPUT_TEXT_CAPACITY)));
+
+    assert!(HEADER.contains(&format!("#define GAME_MODES_CAREER {}\n", game_modes::CAREER)));
+    assert!(HEADER.contains(&format!("#define OBSTACLE_TYPES_BOOST {}\n", obstacle_types::BOOST)));
+    assert!(body(CSHARP, "public static class GameModes").contains(&format!("Career = {};", game_modes::CAREER)));
+}
+
+#[test]
+fn every_export_is_declared() {
+    let lib = include_str!("../src/lib.rs");
+    let exports: Vec<&str> = lib
+        .split("extern \"C\" fn ")
+        .skip(1)
+        .map(|rest| &rest[..rest.find('(').unwrap()])
+        .collect();
+    assert!(!exports.is_empty());
+
+    for name in exports {
+        assert!(HEADER.contains(&format!(" {}(", name)) || HEADER.contains(&format!("*{}(", name)), "{} missing from header", name);
+        assert!(CSHARP.contains(&format!(" {}(", name)), "{} missing from C# bindings", name);
+    }
+}
+
+#[test]
+fn strings_are_marshalled_as_utf8() {
+    let menu = CSHARP.lines().find(|l| l.contains(" ratatui_render_menu(")).unwrap();
+    assert!(menu.contains("[MarshalAs(UnmanagedType.LPUTF8Str)] string title"));
+    assert!(menu.contains("ArraySubType = UnmanagedType.LPUTF8Str)] string[] options"));
+    assert!(!CSHARP.contains("UnmanagedType.LPStr)"), "LPStr is the ANSI code page on Windows");
+}
+
+#[test]
+fn header_compiles_as_c() {
+    let header = concat!(env!("CARGO_MANIFEST_DIR"), "/bindings/rust_renderer.h");
+    let status = Command::new("cc")
+        .args(["-std=c99", "-Wall", "-Wextra", "-pedantic", "-Werror", "-fsyntax-only", "-x", "c", header])
+        .status();
+
+    match status {
+        Ok(status) => assert!(status.success(), "generated header does not compile"),
+        Err(e) => eprintln!("skipping header compile check, no C compiler: {}", e),
+    }
+}
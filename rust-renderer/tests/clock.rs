@@ -0,0 +1,91 @@
+//! Frame clock - frame counting, delta time and animations that run on time
+//! rather than track distance
+
+mod common;
+
+use common::*;
+use rust_renderer::core::types::{game_modes, track_types, weather, GameState};
+use rust_renderer::rendering::assets::{self, AssetRegistry};
+use rust_renderer::rendering::{FrameClock, FrameTime, Scene};
+use rust_renderer::{render_game_with, Renderer, RendererConfig};
+
+fn at(state: &GameState, scene: &Scene) -> String {
+    render_to_text(100, 40, |f| render_game_with(f, state, scene))
+}
+
+#[test]
+fn clock_counts_frames_and_time() {
+    let mut clock = FrameClock::default();
+    assert_eq!(clock.time(), FrameTime::default());
+
+    assert_eq!(clock.step(0.5), FrameTime { frame: 1, delta: 0.5, elapsed: 0.5 });
+    assert_eq!(clock.step(-1.0), FrameTime { frame: 2, delta: 0.0, elapsed: 0.5 });
+
+    let first = clock.tick();
+    assert_eq!((first.frame, first.delta), (3, 0.0), "no delta before the first real frame");
+    let second = clock.tick();
+    assert_eq!(second.frame, 4);
+    assert!(second.delta >= 0.0 && second.elapsed >= first.elapsed);
+}
+
+#[test]
+fn blinks_alternate_on_elapsed_time() {
+    let time = |elapsed| FrameTime { frame: 1, delta: 0.0, elapsed };
+
+    assert!(time(0.0).blink(2.0), "still frames show everything");
+    assert!(!time(0.3).blink(2.0));
+    assert!(time(0.5).blink(2.0));
+    assert!(!time(0.06).blink(10.0));
+}
+
+#[test]
+fn renderers_count_game_and_menu_frames() {
+    let state = base_state(game_modes::SINGLE_PLAYER, track_types::HIGHWAY, weather::CLEAR);
+    let renderer = Renderer::new(&RendererConfig { headless: true, width: 100, height: 40, ..Default::default() }).unwrap();
+    assert_eq!(renderer.frame_time().frame, 0);
+
+    renderer.render(&state).unwrap();
+    let first = renderer.frame_time();
+    assert_eq!((first.frame, first.delta), (1, 0.0));
+
+    renderer.render_menu("TERMINAL RACER", &["Race".to_string()], 0).unwrap();
+    renderer.render(&state).unwrap();
+    let third = renderer.frame_time();
+    assert_eq!(third.frame, 3);
+    assert!(third.elapsed >= first.elapsed);
+}
+
+#[test]
+fn animations_run_on_time_not_distance() {
+    let _serial = serial();
+    let _assets = RestoreAssets;
+    assets::set_active(AssetRegistry::detailed());
+    let mut state = base_state(game_modes::SINGLE_PLAYER, track_types::HIGHWAY, weather::CLEAR);
+    state.shield_active = true;
+    let mut scene = Scene::default();
+
+    let still = at(&state, &scene);
+    assert!(still.contains("◯◯◯") && still.contains('◐'));
+
+    // A moving car on a stopped clock keeps the same frames
+    state.player_distance += 7.0;
+    assert!(at(&state, &scene).contains("◯◯◯"));
+
+    // A stopped car on a running clock does not
+    state.player_speed = 0.0;
+    scene.step(&state, 0.3);
+    let later = at(&state, &scene);
+    assert!(later.contains("◉◉◉"), "shield pulses");
+    assert!(!later.contains('◐') && later.contains('◒'), "wheels spin");
+}
+
+#[test]
+fn rain_falls_while_the_car_stands_still() {
+    let mut state = base_state(game_modes::SINGLE_PLAYER, track_types::HIGHWAY, weather::RAIN);
+    state.player_speed = 0.0;
+    let mut scene = Scene::default();
+
+    let before = at(&state, &scene);
+    scene.step(&state, 0.1);
+    assert_ne!(at(&state, &scene), before);
+}
@@ -0,0 +1,108 @@
+//! Colour depth - detection from COLORTERM / TERM, quantization to the
+//! 256- and 16-colour palettes, and frames drawn at each depth
+
+mod common;
+
+use common::*;
+use ratatui::style::Color;
+use rust_renderer::core::types::{color_modes, game_modes, track_types, weather};
+use rust_renderer::rendering::color::{detect, nearest_16, nearest_256, quantize, resolve};
+use rust_renderer::{
+    ratatui_create, ratatui_destroy, ratatui_renderer_color_mode, ratatui_renderer_set_color_mode, Renderer,
+    RendererConfig,
+};
+
+/// Kind byte of a packed `CellStyle` colour
+const RGB: u32 = 0x01;
+const INDEXED: u32 = 0x02;
+
+#[test]
+fn depth_is_detected_from_the_environment() {
+    assert_eq!(detect(Some("truecolor"), Some("xterm")), color_modes::TRUECOLOR);
+    assert_eq!(detect(Some("24bit"), Some("screen-256color")), color_modes::TRUECOLOR);
+    assert_eq!(detect(None, Some("xterm-direct")), color_modes::TRUECOLOR);
+    assert_eq!(detect(Some("yes"), Some("tmux-256color")), color_modes::ANSI_256);
+    assert_eq!(detect(None, Some("xterm-256color")), color_modes::ANSI_256);
+    assert_eq!(detect(None, Some("xterm")), color_modes::ANSI_16);
+    assert_eq!(detect(None, Some("linux")), color_modes::ANSI_16);
+    let no_term = if cfg!(windows) { color_modes::TRUECOLOR } else { color_modes::ANSI_16 };
+    assert_eq!(detect(None, None), no_term);
+
+    assert_eq!(resolve(color_modes::AUTO, false).unwrap(), color_modes::TRUECOLOR, "off-screen");
+    assert_eq!(resolve(color_modes::ANSI_16, false).unwrap(), color_modes::ANSI_16, "forced");
+    assert!(resolve(color_modes::ANSI_16 + 1, true).is_err());
+    assert!(resolve(-1, false).is_err());
+}
+
+#[test]
+fn rgb_maps_to_the_nearest_palette_entry() {
+    // Colour cube, grey ramp, and whichever of the two is closer
+    assert_eq!(nearest_256([255, 0, 0]), 196);
+    assert_eq!(nearest_256([95, 135, 175]), 67);
+    assert_eq!(nearest_256([0, 0, 0]), 16);
+    assert_eq!(nearest_256([128, 128, 128]), 244);
+    assert_eq!(nearest_256([70, 70, 70]), 238);
+    assert_eq!(nearest_256([100, 100, 120]), 242);
+    assert_eq!(nearest_256([90, 100, 170]), 61);
+
+    assert_eq!(nearest_16([250, 10, 10]), Color::LightRed);
+    assert_eq!(nearest_16([180, 20, 20]), Color::Red);
+    assert_eq!(nearest_16([40, 40, 40]), Color::Black);
+    assert_eq!(nearest_16([110, 110, 110]), Color::DarkGray);
+    assert_eq!(nearest_16([30, 144, 255]), Color::LightBlue);
+
+    assert_eq!(quantize(Color::Rgb(255, 0, 0), color_modes::TRUECOLOR), Color::Rgb(255, 0, 0));
+    assert_eq!(quantize(Color::Rgb(255, 0, 0), color_modes::ANSI_256), Color::Indexed(196));
+    assert_eq!(quantize(Color::Indexed(196), color_modes::ANSI_16), Color::LightRed);
+    assert_eq!(quantize(Color::Indexed(4), color_modes::ANSI_16), Color::Blue);
+    for mode in [color_modes::ANSI_256, color_modes::ANSI_16] {
+        assert_eq!(quantize(Color::Cyan, mode), Color::Cyan, "named colours follow the theme");
+        assert_eq!(quantize(Color::Reset, mode), Color::Reset);
+    }
+}
+
+#[test]
+fn frames_use_only_colours_of_the_depth() {
+    let objects = Objects::crowded();
+    let mut state = base_state(game_modes::SINGLE_PLAYER, track_types::CITY, weather::RAIN);
+    objects.attach(&mut state);
+    state.tunnel_darkness = 0.5;
+
+    let kinds = |mode: i32| {
+        let config = RendererConfig { headless: true, width: 100, height: 40, color_mode: mode };
+        let renderer = Renderer::new(&config).unwrap();
+        renderer.render(&state).unwrap();
+        let text = renderer.snapshot_text().unwrap();
+        let colors: Vec<u32> = renderer.snapshot_styles().unwrap().iter().flat_map(|s| [s.fg, s.bg]).collect();
+        (renderer.color_mode(), text, colors)
+    };
+
+    let (mode, text, truecolor) = kinds(color_modes::AUTO);
+    assert_eq!(mode, color_modes::TRUECOLOR);
+    assert!(truecolor.iter().any(|c| c >> 24 == RGB), "the scene is drawn in RGB");
+
+    let (mode, text_256, ansi_256) = kinds(color_modes::ANSI_256);
+    assert_eq!((mode, &text_256), (color_modes::ANSI_256, &text), "only colours change");
+    assert!(ansi_256.iter().all(|c| c >> 24 != RGB));
+
+    let (_, text_16, ansi_16) = kinds(color_modes::ANSI_16);
+    assert_eq!(text_16, text);
+    assert!(ansi_16.iter().all(|&c| c == 0 || (c >> 24 == INDEXED && c & 0xff < 16)));
+}
+
+#[test]
+fn hosts_can_force_a_depth() {
+    let bad = RendererConfig { headless: true, width: 40, height: 20, color_mode: 9 };
+    assert!(unsafe { ratatui_create(&bad) }.is_null());
+
+    let config = RendererConfig { headless: true, width: 40, height: 20, ..Default::default() };
+    let handle = unsafe { ratatui_create(&config) };
+    assert_eq!(unsafe { ratatui_renderer_color_mode(handle) }, color_modes::TRUECOLOR);
+    assert!(unsafe { ratatui_renderer_set_color_mode(handle, color_modes::ANSI_256) });
+    assert!(!unsafe { ratatui_renderer_set_color_mode(handle, -3) });
+    assert_eq!(unsafe { ratatui_renderer_color_mode(handle) }, color_modes::ANSI_256, "left unchanged");
+    assert!(unsafe { ratatui_renderer_set_color_mode(handle, color_modes::AUTO) });
+    assert_eq!(unsafe { ratatui_renderer_color_mode(handle) }, color_modes::TRUECOLOR);
+    assert_eq!(unsafe { ratatui_renderer_color_mode(std::ptr::null()) }, -1);
+    unsafe { ratatui_destroy(handle) };
+}
@@ -0,0 +1,236 @@
+//! Shared test harness - GameState builders and golden snapshot helpers
+
+#![allow(dead_code)]
+
+use std::path::PathBuf;
+use std::sync::{Mutex, MutexGuard, PoisonError};
+use ratatui::{backend::TestBackend, Frame, Terminal};
+use rust_renderer::core::types::{game_modes, track_types, weather, GameState};
+use rust_renderer::io::snapshot::buffer_to_text;
+use rust_renderer::rendering::assets::{self, AssetRegistry};
+use rust_renderer::rendering::projection::DRAW_DISTANCE;
+
+/// The global renderer, active asset registry, watched asset pack and debug
+/// overlay switch are shared by every test in a binary, so tests that touch
+/// them run one at a time
+static SERIAL: Mutex<()> = Mutex::new(());
+
+pub fn serial() -> MutexGuard<'static, ()> {
+    SERIAL.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+/// Puts the default asset registry back when dropped, even if the test failed
+/// Take it after `serial()`, so it is dropped before the lock is released.
+pub struct RestoreAssets;
+
+impl Drop for RestoreAssets {
+    fn drop(&mut self) {
+        assets::set_active(AssetRegistry::default());
+    }
+}
+
+/// Owned object arrays that a `GameState` can point into
+/// Must outlive every state it has been attached to
+pub struct Objects {
+    pub ai_positions: Vec<i32>,
+    pub ai_distances: Vec<f32>,
+    pub ai_types: Vec<i32>,
+    pub ai_is_boss: Vec<bool>,
+
+    pub obstacle_positions: Vec<i32>,
+    pub obstacle_distances: Vec<f32>,
+    pub obstacle_types: Vec<i32>,
+
+    pub building_positions: Vec<i32>,
+    pub building_distances: Vec<f32>,
+    pub building_heights: Vec<i32>,
+    pub building_types: Vec<i32>,
+}
+
+impl Objects {
+    /// No AI cars, obstacles or buildings
+    pub fn empty() -> Self {
+        Self::with_counts(0, 0, 0)
+    }
+
+    /// A fixed, representative scene around a player at `PLAYER_DISTANCE`
+    pub fn standard() -> Self {
+        Self::with_counts(4, 6, 6)
+    }
+
+    /// A busy race: 30 AI cars, 50 obstacles and 40 buildings, all within
+    /// draw distance of the player
+    pub fn crowded() -> Self {
+        let mut objects = Self::with_counts(30, 50, 40);
+        let ahead = DRAW_DISTANCE - 3.0;
+        for distances in [&mut objects.ai_distances, &mut objects.obstacle_distances, &mut objects.building_distances] {
+            let count = distances.len() as f32;
+            for (i, distance) in distances.iter_mut().enumerate() {
+                *distance = PLAYER_DISTANCE + 2.0 + ahead * i as f32 / count;
+            }
+        }
+        objects
+    }
+
+    /// Deterministic objects spread over the visible range
+    pub fn with_counts(cars: usize, obstacles: usize, buildings: usize) -> Self {
+        Objects {
+            ai_positions: (0..cars).map(|i| (i % 3) as i32).collect(),
+            ai_distances: (0..cars).map(|i| PLAYER_DISTANCE + 8.0 + i as f32 * 11.0).collect(),
+            ai_types: (0..cars).map(|i| (i % 10) as i32).collect(),
+            ai_is_boss: (0..cars).map(|i| i == 3).collect(),
+
+            obstacle_positions: (0..obstacles).map(|i| ((i + 1) % 3) as i32).collect(),
+            obstacle_distances: (0..obstacles).map(|i| PLAYER_DISTANCE + 5.0 + i as f32 * 9.0).collect(),
+            obstacle_types: (0..obstacles).map(|i| (i % 6) as i32).collect(),
+
+            building_positions: (0..buildings).map(|i| if i % 2 == 0 { -1 } else { 1 }).collect(),
+            building_distances: (0..buildings).map(|i| PLAYER_DISTANCE - 10.0 + i as f32 * 12.0).collect(),
+            building_heights: (0..buildings).map(|i| 4 + (i % 5) as i32 * 2).collect(),
+            building_types: (0..buildings).map(|i| (i % 4) as i32).collect(),
+        }
+    }
+
+    /// Point the object arrays of `state` at this set
+    pub fn attach(&self, state: &mut GameState) {
+        state.car_count = self.ai_positions.len() as i32;
+        state.ai_positions = self.ai_positions.as_ptr();
+        state.ai_distances = self.ai_distances.as_ptr();
+        state.ai_types = self.ai_types.as_ptr();
+        state.ai_is_boss = self.ai_is_boss.as_ptr();
+
+        state.obstacle_count = self.obstacle_positions.len() as i32;
+        state.obstacle_positions = self.obstacle_positions.as_ptr();
+        state.obstacle_distances = self.obstacle_distances.as_ptr();
+        state.obstacle_types = self.obstacle_types.as_ptr();
+
+        state.building_count = self.building_positions.len() as i32;
+        state.building_positions = self.building_positions.as_ptr();
+        state.building_distances = self.building_distances.as_ptr();
+        state.building_heights = self.building_heights.as_ptr();
+        state.building_types = self.building_types.as_ptr();
+    }
+}
+
+pub const PLAYER_DISTANCE: f32 = 1234.5;
+
+/// A mid-race state with no objects attached and no powerups active
+pub fn base_state(game_mode: i32, track_type: i32, weather_type: i32) -> GameState {
+    GameState {
+        player_position: 1,
+        player_speed: 142.0,
+        player_distance: PLAYER_DISTANCE,
+        player_health: 72,
+        player_score: 48250,
+        player_car_type: 0,
+
+        player2_active: game_mode == game_modes::SPLIT_SCREEN,
+        player2_position: 2,
+        player2_speed: 131.0,
+        player2_distance: PLAYER_DISTANCE - 6.0,
+        player2_health: 40,
+        player2_score: 39100,
+        player2_car_type: 2,
+
+        lap_time: 83.25,
+        game_mode,
+        track_type,
+        lane_count: 0,
+        level: 5,
+        career_progress: 62.0,
+
+        boost_active: false,
+        boost_remaining: 0.0,
+        shield_active: false,
+        shield_remaining: 0.0,
+        invincibility_active: false,
+        invincibility_remaining: 0.0,
+        magnet_active: false,
+        magnet_remaining: 0.0,
+        slowmo_active: false,
+        slowmo_remaining: 0.0,
+
+        car_count: 0,
+        ai_positions: std::ptr::null(),
+        ai_distances: std::ptr::null(),
+        ai_types: std::ptr::null(),
+        ai_is_boss: std::ptr::null(),
+
+        obstacle_count: 0,
+        obstacle_positions: std::ptr::null(),
+        obstacle_distances: std::ptr::null(),
+        obstacle_types: std::ptr::null(),
+
+        building_count: 0,
+        building_positions: std::ptr::null(),
+        building_distances: std::ptr::null(),
+        building_heights: std::ptr::null(),
+        building_types: std::ptr::null(),
+
+        weather: weather_type,
+        curve_offset: 1.5,
+        elevation: 0.8,
+        tunnel_darkness: 0.4,
+
+        combo: 7,
+        replay_mode: game_mode == game_modes::REPLAY,
+        replay_progress: 0.4,
+        ghost_position: 0,
+        ghost_distance: PLAYER_DISTANCE + 12.0,
+    }
+}
+
+pub const GAME_MODES: [(i32, &str); 4] = [
+    (game_modes::SINGLE_PLAYER, "single"),
+    (game_modes::SPLIT_SCREEN, "split"),
+    (game_modes::CAREER, "career"),
+    (game_modes::REPLAY, "replay"),
+];
+
+pub const TRACK_TYPES: [(i32, &str); 5] = [
+    (track_types::HIGHWAY, "highway"),
+    (track_types::CITY, "city"),
+    (track_types::MOUNTAIN, "mountain"),
+    (track_types::DESERT, "desert"),
+    (track_types::TUNNEL, "tunnel"),
+];
+
+pub const WEATHER: [(i32, &str); 4] = [
+    (weather::CLEAR, "clear"),
+    (weather::RAIN, "rain"),
+    (weather::FOG, "fog"),
+    (weather::NIGHT, "night"),
+];
+
+/// Render into an off-screen buffer and return the frame as text
+pub fn render_to_text<F: FnOnce(&mut Frame)>(width: u16, height: u16, render: F) -> String {
+    let mut terminal = Terminal::new(TestBackend::new(width, height)).unwrap();
+    terminal.draw(render).unwrap();
+    buffer_to_text(terminal.backend().buffer())
+}
+
+/// Compare `actual` with `tests/snapshots/<name>.txt`
+/// Set `UPDATE_SNAPSHOTS=1` to (re)write the golden file instead
+pub fn assert_snapshot(name: &str, actual: &str) {
+    let path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests")
+        .join("snapshots")
+        .join(format!("{}.txt", name));
+    let actual = format!("{}\n", actual);
+
+    if std::env::var_os("UPDATE_SNAPSHOTS").is_some() {
+        std::fs::write(&path, &actual).unwrap();
+        return;
+    }
+
+    let expected = std::fs::read_to_string(&path).unwrap_or_else(|_| {
+        panic!("missing snapshot {}; run with UPDATE_SNAPSHOTS=1", path.display())
+    });
+
+    if expected != actual {
+        panic!(
+            "snapshot {} does not match\n--- expected\n{}\n--- actual\n{}",
+            name, expected, actual
+        );
+    }
+}
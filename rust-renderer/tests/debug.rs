@@ -0,0 +1,78 @@
+//! Debug overlay - toggling, frame meter and the cells ratatui's diff writes
+
+mod common;
+
+use common::*;
+use ratatui::{style::Style, widgets::Paragraph};
+use rust_renderer::core::types::{game_modes, track_types, weather};
+use rust_renderer::io::terminal::RenderTarget;
+use rust_renderer::rendering::debug::{self, FrameMeter};
+use rust_renderer::{ratatui_debug_overlay, ratatui_set_debug_overlay, Renderer, RendererConfig};
+
+#[test]
+fn overlay_is_toggled_for_every_renderer() {
+    let _serial = serial();
+    assert!(!ratatui_debug_overlay(), "off by default");
+
+    let objects = Objects::crowded();
+    let mut state = base_state(game_modes::SINGLE_PLAYER, track_types::CITY, weather::CLEAR);
+    objects.attach(&mut state);
+    let renderer = Renderer::new(&RendererConfig { headless: true, width: 100, height: 40, ..Default::default() }).unwrap();
+
+    renderer.render(&state).unwrap();
+    let plain = renderer.snapshot_text().unwrap();
+    assert!(!plain.contains("Debug"));
+
+    ratatui_set_debug_overlay(true);
+    assert!(ratatui_debug_overlay());
+    renderer.render(&state).unwrap();
+    let shown = renderer.snapshot_text().unwrap();
+    for text in ["Debug", "FPS", "Draw", "Cells", "Objects 30 / 50 / 40", "Size    100x40", "Quality HIGH"] {
+        assert!(shown.contains(text), "missing {:?}", text);
+    }
+    // Top right, leaving the HUD's left side alone
+    let left = |frame: &str| frame.lines().next().unwrap().chars().take(60).collect::<String>();
+    assert!(shown.lines().next().unwrap().ends_with('┐'));
+    assert_eq!(left(&shown), left(&plain));
+
+    assert!(!debug::toggle());
+    renderer.render(&state).unwrap();
+    assert!(!renderer.snapshot_text().unwrap().contains("Debug"));
+    assert!(debug::toggle());
+    ratatui_set_debug_overlay(false);
+}
+
+#[test]
+fn meter_averages_the_frame_interval() {
+    let mut meter = FrameMeter::default();
+    meter.record(0.0, 4000);
+    assert_eq!((meter.fps(), meter.cells()), (0.0, 4000), "no rate from the first frame");
+
+    meter.record(0.02, 12);
+    assert!((meter.fps() - 50.0).abs() < 0.01 && (meter.frame_ms() - 20.0).abs() < 0.01);
+    for _ in 0..100 {
+        meter.record(0.01, 3);
+    }
+    assert!((meter.fps() - 100.0).abs() < 0.5);
+    assert_eq!(meter.cells(), 3);
+}
+
+#[test]
+fn only_changed_cells_are_counted() {
+    let _serial = serial();
+    let mut target = RenderTarget::headless(20, 5).unwrap();
+    let draw = |target: &mut RenderTarget, text: &'static str| {
+        target.draw(|f| f.render_widget(Paragraph::new(text).style(Style::default()), f.area())).unwrap()
+    };
+
+    assert_eq!(draw(&mut target, "hello"), 5);
+    assert_eq!(draw(&mut target, "hello"), 0, "an unchanged frame writes nothing");
+    assert_eq!(draw(&mut target, "help"), 2, "'p' and the cleared 'o'");
+
+    let renderer = Renderer::new(&RendererConfig { headless: true, width: 100, height: 40, ..Default::default() }).unwrap();
+    let state = base_state(game_modes::SINGLE_PLAYER, track_types::HIGHWAY, weather::CLEAR);
+    renderer.render(&state).unwrap();
+    let first = renderer.frame_meter().cells();
+    renderer.render(&state).unwrap();
+    assert!(first > 1000 && renderer.frame_meter().cells() < first / 10);
+}
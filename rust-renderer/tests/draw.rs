@@ -0,0 +1,87 @@
+//! Direct buffer drawing - clipping, wide glyphs and agreement with the
+//! `Paragraph` widgets it replaces
+
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    widgets::{Paragraph, Widget},
+};
+use rust_renderer::io::snapshot::buffer_to_text;
+use rust_renderer::rendering::draw;
+
+fn canvas() -> Buffer {
+    Buffer::filled(Rect::new(0, 0, 10, 3), ratatui::buffer::Cell::new("."))
+}
+
+/// Drawing area inside the buffer, leaving a one-cell border
+const CLIP: Rect = Rect { x: 1, y: 1, width: 8, height: 1 };
+
+#[test]
+fn cells_outside_the_clip_are_left_alone() {
+    let mut buf = canvas();
+    let style = Style::default();
+
+    draw::put(&mut buf, CLIP, 0, 1, "a", style);
+    draw::put(&mut buf, CLIP, 9, 1, "b", style);
+    draw::put(&mut buf, CLIP, 4, 0, "c", style);
+    draw::put(&mut buf, CLIP, 4, 2, "d", style);
+    draw::put(&mut buf, CLIP, 8, 1, "🌵", style);
+    draw::put(&mut buf, CLIP, 200, 200, "e", style);
+    assert_eq!(buffer_to_text(&buf), "..........\n..........\n..........");
+
+    draw::put(&mut buf, CLIP, 7, 1, "🌵", style);
+    draw::put(&mut buf, CLIP, 1, 1, "x", style);
+    assert_eq!(buffer_to_text(&buf), "..........\n.x.....🌵.\n..........");
+}
+
+#[test]
+fn text_and_fill_are_cut_at_both_edges() {
+    let mut buf = canvas();
+    let style = Style::default();
+
+    assert_eq!(draw::text(&mut buf, CLIP, 0, 1, "abc", style), 3);
+    assert_eq!(draw::text(&mut buf, CLIP, 6, 1, "x🌵yz", style), 9);
+    assert_eq!(buffer_to_text(&buf), "..........\n.bc...x🌵.\n..........");
+
+    let mut buf = canvas();
+    assert_eq!(draw::fill(&mut buf, CLIP, 5, 1, 10, "=", style), 9);
+    assert_eq!(draw::fill(&mut buf, CLIP, 0, 1, 2, "◯", style), 2);
+    assert_eq!(draw::fill(&mut buf, CLIP, 3, 0, 2, "=", style), 3, "rows outside write nothing");
+    assert_eq!(buffer_to_text(&buf), "..........\n.◯...====.\n..........");
+}
+
+#[test]
+fn cells_match_the_paragraph_they_replace() {
+    let style = Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD);
+
+    // Wide glyphs differ only in the cells they hide, which Paragraph leaves
+    for text in ["▌", "▒▒▒▒", " ◯◯◯ ", "SPD"] {
+        let width = unicode_width::UnicodeWidthStr::width(text) as u16;
+        let mut expected = canvas();
+        let mut actual = canvas();
+        expected.set_style(Rect::new(0, 0, 10, 3), Style::default().fg(Color::Red).bg(Color::Black));
+        actual.set_style(Rect::new(0, 0, 10, 3), Style::default().fg(Color::Red).bg(Color::Black));
+
+        Paragraph::new(text).style(style).render(Rect::new(1, 1, width, 1), &mut expected);
+        draw::text(&mut actual, CLIP, 1, 1, text, style);
+        assert_eq!(actual, expected, "{}", text);
+    }
+}
+
+#[test]
+fn wide_glyphs_clear_the_cells_they_hide() {
+    let style = Style::default().fg(Color::Cyan);
+    let mut buf = canvas();
+    draw::fill(&mut buf, CLIP, 1, 1, 8, "│", Style::default().fg(Color::Blue));
+
+    // The cactus hides the rain under its second column
+    draw::text(&mut buf, CLIP, 2, 1, "🌵ab", style);
+    let hidden = &buf[(3, 1)];
+    assert_eq!((hidden.symbol(), hidden.fg), (" ", Color::Cyan));
+    assert_eq!(buffer_to_text(&buf), "..........\n.│🌵ab│││.\n..........");
+
+    // Drawing over its first column uncovers a blank, not stale rain
+    draw::put(&mut buf, CLIP, 2, 1, "x", style);
+    assert_eq!(buffer_to_text(&buf), "..........\n.│x ab│││.\n..........");
+}
@@ -0,0 +1,142 @@
+//! Tagged event stream - key, resize, focus, mouse and paste events
+
+use std::ffi::c_char;
+use std::time::Instant;
+use crossterm::event::{KeyCode, KeyEvent, KeyEventKind, KeyModifiers, MouseButton, MouseEvent as TermMouseEvent, MouseEventKind};
+use ratatui::layout::Rect;
+use rust_renderer::core::types::{event_kinds, hit_targets, key_codes, key_modifiers, mouse_kinds};
+use rust_renderer::io::events::{EventQueue, Queued, QUEUE_CAPACITY};
+use rust_renderer::io::input::{KeyTracker, FIRST_REPEAT_TIMEOUT};
+use rust_renderer::rendering::hit::HitMap;
+use rust_renderer::*;
+
+fn press(code: KeyCode, modifiers: KeyModifiers) -> KeyEvent {
+    KeyEvent::new_with_kind(code, modifiers, KeyEventKind::Press)
+}
+
+#[test]
+fn key_events_carry_code_char_and_modifiers() {
+    let mut queue = EventQueue::new();
+    let hits = HitMap::default();
+    queue.push(Queued::KeyDown { key: press(KeyCode::Char('é'), KeyModifiers::ALT), repeat: false });
+    queue.push(Queued::KeyDown { key: press(KeyCode::F(5), KeyModifiers::NONE), repeat: true });
+    queue.push(Queued::KeyDown { key: press(KeyCode::BackTab, KeyModifiers::NONE), repeat: false });
+    queue.push(Queued::KeyUp(press(KeyCode::Left, KeyModifiers::CONTROL | KeyModifiers::SHIFT)));
+
+    let e = queue.next(&hits).unwrap();
+    assert_eq!((e.kind, e.key_code, e.key_char), (event_kinds::KEY_DOWN, key_codes::CHAR, 'é' as u32));
+    assert_eq!((e.modifiers, e.repeat), (key_modifiers::ALT, false));
+
+    let e = queue.next(&hits).unwrap();
+    assert_eq!((e.key_code, e.key_char, e.repeat), (key_codes::FUNCTION, 5, true));
+
+    // Shift+Tab arrives as BackTab
+    let e = queue.next(&hits).unwrap();
+    assert_eq!((e.key_code, e.modifiers), (key_codes::TAB, key_modifiers::SHIFT));
+
+    let e = queue.next(&hits).unwrap();
+    assert_eq!((e.kind, e.key_code), (event_kinds::KEY_UP, key_codes::LEFT));
+    assert_eq!(e.modifiers, key_modifiers::CONTROL | key_modifiers::SHIFT);
+    assert!(queue.next(&hits).is_none());
+}
+
+#[test]
+fn resize_focus_and_paste_events() {
+    let mut queue = EventQueue::new();
+    let hits = HitMap::default();
+    queue.push(Queued::Resize(120, 40));
+    queue.push(Queued::Focus(false));
+    queue.push(Queued::Focus(true));
+    queue.push(Queued::Paste("naïve".to_string()));
+
+    let e = queue.next(&hits).unwrap();
+    assert_eq!((e.kind, e.width, e.height), (event_kinds::RESIZE, 120, 40));
+    assert_eq!(queue.next(&hits).unwrap().kind, event_kinds::FOCUS_LOST);
+    assert_eq!(queue.next(&hits).unwrap().kind, event_kinds::FOCUS_GAINED);
+
+    // The paste text is only handed out once its event is taken
+    assert_eq!(queue.text(), "");
+    let e = queue.next(&hits).unwrap();
+    assert_eq!((e.kind, e.text_len), (event_kinds::PASTE, "naïve".len() as u32));
+    assert_eq!(queue.text(), "naïve");
+}
+
+#[test]
+fn mouse_events_are_hit_tested() {
+    let mut queue = EventQueue::new();
+    let hits = HitMap::for_menu(Rect::new(0, 0, 40, 14), 3);
+    let at = |kind, row| TermMouseEvent { kind, column: 5, row, modifiers: KeyModifiers::NONE };
+
+    // Movement without a button is not reported
+    queue.push(Queued::Mouse(at(MouseEventKind::Moved, 0)));
+    queue.push(Queued::Mouse(at(MouseEventKind::Down(MouseButton::Left), 0)));
+    let e = queue.next(&hits).unwrap();
+    assert_eq!((e.kind, e.mouse.kind, e.mouse.target), (event_kinds::MOUSE, mouse_kinds::DOWN, hit_targets::NONE));
+    assert!(queue.next(&hits).is_none());
+
+    let row = (0..14).find(|&row| hits.hit(5, row).is_some()).unwrap();
+    queue.push(Queued::Mouse(at(MouseEventKind::Down(MouseButton::Left), row)));
+    let e = queue.next(&hits).unwrap();
+    assert_eq!((e.mouse.target, e.mouse.item), (hit_targets::MENU_ITEM, 0));
+}
+
+#[test]
+fn full_queue_drops_oldest_events() {
+    let mut queue = EventQueue::new();
+    let hits = HitMap::default();
+    for width in 0..QUEUE_CAPACITY as u16 + 10 {
+        queue.push(Queued::Resize(width, 1));
+    }
+
+    assert_eq!(queue.next(&hits).unwrap().width, 10);
+    queue.clear();
+    assert!(queue.next(&hits).is_none());
+}
+
+#[test]
+fn key_tracker_flags_repeats_and_expired_keys() {
+    let mut tracker = KeyTracker::new();
+    let mut input = InputState::default();
+    let t0 = Instant::now();
+    let w = press(KeyCode::Char('w'), KeyModifiers::NONE);
+    let x = press(KeyCode::Char('x'), KeyModifiers::NONE);
+
+    // Without release events, presses of a held key are its auto-repeat
+    assert!(!tracker.handle(w, t0, &mut input));
+    assert!(tracker.handle(w, t0 + FIRST_REPEAT_TIMEOUT / 2, &mut input));
+    assert!(!tracker.handle(x, t0, &mut input));
+    assert!(tracker.take_expired().is_empty());
+
+    // Once the repeats stop the key is reported as released, exactly once
+    tracker.apply(t0 + FIRST_REPEAT_TIMEOUT * 2, &mut input);
+    let expired = tracker.take_expired();
+    assert_eq!(expired.len(), 1);
+    assert_eq!(expired[0].code, KeyCode::Char('w'));
+    assert!(tracker.take_expired().is_empty());
+
+    // Exact release events never expire keys
+    tracker.set_release_events(true);
+    assert!(!tracker.handle(w, t0, &mut input));
+    tracker.apply(t0 + FIRST_REPEAT_TIMEOUT * 2, &mut input);
+    assert!(tracker.take_expired().is_empty());
+}
+
+#[test]
+fn headless_renderers_have_no_events() {
+    let config = RendererConfig { headless: true, width: 40, height: 14, ..Default::default() };
+    let renderer = unsafe { ratatui_create(&config) };
+    assert!(!renderer.is_null());
+
+    let mut event = Event { width: 7, ..Default::default() };
+    assert!(!unsafe { ratatui_renderer_next_event(renderer, &mut event) });
+    assert_eq!(event.width, 7);
+    assert!(!unsafe { ratatui_renderer_next_event(renderer, std::ptr::null_mut()) });
+    assert_eq!(ratatui_last_error_code(), ErrorCode::NullPointer);
+
+    // No paste has been taken yet
+    let mut buf = [1 as c_char; 4];
+    assert_eq!(unsafe { ratatui_event_text(buf.as_mut_ptr(), buf.len()) }, 0);
+    assert_eq!(buf[0], 0);
+
+    unsafe { ratatui_destroy(renderer) };
+}
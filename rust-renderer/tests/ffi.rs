@@ -0,0 +1,310 @@
+//! FFI boundary tests - headless lifecycle, thread safety and panic containment
+
+mod common;
+
+use common::*;
+use rust_renderer::core::types::{game_modes, track_types, weather};
+use rust_renderer::io::guard::{ffi_guard, handle_guard};
+use rust_renderer::*;
+
+fn snapshot_text() -> String {
+    let len = unsafe { ratatui_snapshot_text(std::ptr::null_mut(), 0) };
+    assert!(len >= 0, "no headless renderer active");
+
+    let mut buf = vec![0u8; len as usize + 1];
+    unsafe { ratatui_snapshot_text(buf.as_mut_ptr() as *mut _, buf.len()) };
+    buf.pop();
+    String::from_utf8(buf).unwrap()
+}
+
+#[test]
+fn headless_render_round_trip() {
+    let _serial = serial();
+    let objects = Objects::standard();
+    let mut state = base_state(game_modes::SINGLE_PLAYER, track_types::HIGHWAY, weather::CLEAR);
+    objects.attach(&mut state);
+
+    assert!(ratatui_init_headless(100, 40));
+    assert!(unsafe { ratatui_render(&state) });
+
+    let expected = render_to_text(100, 40, |f| render_game(f, &state));
+    assert_eq!(snapshot_text(), expected);
+
+    let mut styles = vec![CellStyle::default(); 100 * 40];
+    let count = unsafe { ratatui_snapshot_styles(styles.as_mut_ptr(), styles.len()) };
+    assert_eq!(count, 100 * 40);
+    assert!(styles.iter().any(|s| s.fg != 0));
+
+    ratatui_cleanup();
+    assert_eq!(unsafe { ratatui_snapshot_text(std::ptr::null_mut(), 0) }, -1);
+    assert!(!unsafe { ratatui_render(&state) });
+}
+
+#[test]
+fn null_pointers_are_rejected() {
+    let _serial = serial();
+    assert!(ratatui_init_headless(40, 20));
+
+    assert!(!unsafe { ratatui_render(std::ptr::null()) });
+    assert!(!unsafe { ratatui_poll_input(std::ptr::null_mut()) });
+    assert!(!unsafe { ratatui_render_menu(std::ptr::null(), std::ptr::null(), 0, 0) });
+
+    ratatui_cleanup();
+}
+
+#[test]
+fn renders_from_many_threads() {
+    let _serial = serial();
+    assert!(ratatui_init_headless(80, 30));
+
+    std::thread::scope(|scope| {
+        for track_type in 0..5 {
+            scope.spawn(move || {
+                let objects = Objects::standard();
+                let mut state = base_state(game_modes::CAREER, track_type, weather::FOG);
+                objects.attach(&mut state);
+                for _ in 0..10 {
+                    assert!(unsafe { ratatui_render(&state) });
+                }
+            });
+        }
+    });
+
+    assert!(!snapshot_text().is_empty());
+    ratatui_cleanup();
+}
+
+#[test]
+fn panics_are_contained_and_release_the_terminal() {
+    let _serial = serial();
+    assert!(ratatui_init_headless(40, 20));
+
+    let result = ffi_guard(false, || panic!("boom"));
+    assert!(!result);
+    assert_eq!(ratatui_last_error_code(), ErrorCode::Panic);
+    assert!(last_error().contains("boom"));
+
+    // The guard tore the renderer down, so it has to be initialized again
+    assert_eq!(unsafe { ratatui_snapshot_text(std::ptr::null_mut(), 0) }, -1);
+    assert!(ratatui_init_headless(40, 20));
+    ratatui_cleanup();
+}
+
+#[test]
+fn a_panicking_handle_leaves_other_renderers_running() {
+    let _serial = serial();
+    let state = base_state(game_modes::SINGLE_PLAYER, track_types::HIGHWAY, weather::CLEAR);
+    let config = RendererConfig { headless: true, width: 40, height: 20, ..Default::default() };
+    let recorder = unsafe { ratatui_create(&config) };
+    let other = unsafe { ratatui_create(&config) };
+    assert!(ratatui_init_headless(40, 20));
+
+    let result = handle_guard(unsafe { recorder.as_ref() }, false, |_| panic!("boom"));
+    assert!(!result);
+    assert_eq!(ratatui_last_error_code(), ErrorCode::Panic);
+    assert!(last_error().contains("boom"));
+
+    // Only the handle that panicked is torn down
+    assert!(unsafe { ratatui_render(&state) });
+    assert_eq!(snapshot_text(), render_to_text(40, 20, |f| render_game(f, &state)));
+    assert!(unsafe { ratatui_renderer_render(other, &state) });
+    assert_eq!(handle_snapshot_text(other), render_to_text(40, 20, |f| render_game(f, &state)));
+
+    assert!(!unsafe { ratatui_renderer_render(recorder, &state) });
+    assert_eq!(ratatui_last_error_code(), ErrorCode::Panic);
+    assert!(last_error().contains("must be destroyed"));
+
+    unsafe {
+        ratatui_destroy(recorder);
+        ratatui_destroy(other);
+    }
+    ratatui_cleanup();
+}
+
+fn last_error() -> String {
+    let len = unsafe { ratatui_last_error(std::ptr::null_mut(), 0) };
+    let mut buf = vec![0u8; len as usize + 1];
+    unsafe { ratatui_last_error(buf.as_mut_ptr() as *mut _, buf.len()) };
+    buf.pop();
+    String::from_utf8(buf).unwrap()
+}
+
+fn handle_snapshot_text(renderer: *const Renderer) -> String {
+    let len = unsafe { ratatui_renderer_snapshot_text(renderer, std::ptr::null_mut(), 0) };
+    assert!(len >= 0, "renderer is not headless");
+
+    let mut buf = vec![0u8; len as usize + 1];
+    unsafe { ratatui_renderer_snapshot_text(renderer, buf.as_mut_ptr() as *mut _, buf.len()) };
+    buf.pop();
+    String::from_utf8(buf).unwrap()
+}
+
+#[test]
+fn renderer_handles_are_independent() {
+    let _serial = serial();
+    let objects = Objects::standard();
+
+    let mut city = base_state(game_modes::SINGLE_PLAYER, track_types::CITY, weather::CLEAR);
+    objects.attach(&mut city);
+    let mut tunnel = base_state(game_modes::REPLAY, track_types::TUNNEL, weather::NIGHT);
+    objects.attach(&mut tunnel);
+
+    let small = RendererConfig { headless: true, width: 60, height: 30, ..Default::default() };
+    let large = RendererConfig { headless: true, width: 120, height: 40, ..Default::default() };
+    let a = unsafe { ratatui_create(&small) };
+    let b = unsafe { ratatui_create(&large) };
+    assert!(!a.is_null() && !b.is_null());
+
+    // The global renderer is unaffected by handles and vice versa
+    assert!(ratatui_init_headless(80, 30));
+    assert!(unsafe { ratatui_render(&city) });
+
+    assert!(unsafe { ratatui_renderer_render(a, &city) });
+    assert!(unsafe { ratatui_renderer_render(b, &tunnel) });
+
+    assert_eq!(handle_snapshot_text(a), render_to_text(60, 30, |f| render_game(f, &city)));
+    assert_eq!(handle_snapshot_text(b), render_to_text(120, 40, |f| render_game(f, &tunnel)));
+    assert_eq!(snapshot_text(), render_to_text(80, 30, |f| render_game(f, &city)));
+
+    let mut styles = vec![CellStyle::default(); 60 * 30];
+    assert_eq!(unsafe { ratatui_renderer_snapshot_styles(a, styles.as_mut_ptr(), styles.len()) }, 60 * 30);
+
+    // Off-screen renderers never receive keyboard input
+    let mut input = InputState { quit: true, ..Default::default() };
+    assert!(unsafe { ratatui_renderer_poll_input(a, &mut input) });
+    assert!(!input.quit);
+
+    unsafe {
+        ratatui_destroy(a);
+        ratatui_destroy(b);
+    }
+    ratatui_cleanup();
+}
+
+#[test]
+fn invalid_handles_are_rejected() {
+    let _serial = serial();
+    let state = base_state(game_modes::SINGLE_PLAYER, track_types::HIGHWAY, weather::CLEAR);
+
+    let zero = RendererConfig { headless: true, width: 0, height: 10, ..Default::default() };
+    assert!(unsafe { ratatui_create(&zero) }.is_null());
+
+    assert!(!unsafe { ratatui_renderer_render(std::ptr::null(), &state) });
+    assert_eq!(unsafe { ratatui_renderer_snapshot_text(std::ptr::null(), std::ptr::null_mut(), 0) }, -1);
+    unsafe { ratatui_destroy(std::ptr::null_mut()) };
+}
+
+#[test]
+fn layout_validation_gates_initialization() {
+    use rust_renderer::core::layout::{ABI_VERSION, GAME_STATE_FIELDS, INPUT_STATE_FIELDS};
+
+    let _serial = serial();
+    assert_eq!(ratatui_abi_version(), ABI_VERSION);
+
+    let game: Vec<usize> = GAME_STATE_FIELDS.iter().map(|&(_, o)| o).collect();
+    let input: Vec<usize> = INPUT_STATE_FIELDS.iter().map(|&(_, o)| o).collect();
+    assert!(game.windows(2).all(|w| w[0] < w[1]), "GameState table out of declaration order");
+    assert!(input.windows(2).all(|w| w[0] < w[1]), "InputState table out of declaration order");
+
+    let game_size = std::mem::size_of::<GameState>();
+    let input_size = std::mem::size_of::<InputState>();
+    let validate = |game_size: usize, game: &[usize], input: &[usize]| unsafe {
+        ratatui_validate_layout(game_size, game.as_ptr(), game.len(), input_size, input.as_ptr(), input.len())
+    };
+
+    assert!(validate(game_size, &game, &input));
+    assert!(ratatui_init_headless(40, 20));
+    ratatui_cleanup();
+
+    // A host struct with a drifted field must stop the renderer from starting
+    let mut drifted = game.clone();
+    drifted[10] += 4;
+    assert!(!validate(game_size, &drifted, &input));
+    assert_eq!(ratatui_last_error_code(), ErrorCode::LayoutMismatch);
+    assert!(last_error().contains("GameState.player2_health"), "{}", last_error());
+    assert!(!validate(game_size + 8, &game, &input));
+    assert!(!validate(game_size, &game[..49], &input));
+    assert!(!ratatui_init_headless(40, 20));
+    assert_eq!(ratatui_last_error_code(), ErrorCode::LayoutMismatch);
+    let config = RendererConfig { headless: true, width: 40, height: 20, ..Default::default() };
+    assert!(unsafe { ratatui_create(&config) }.is_null());
+
+    assert!(validate(game_size, &game, &input));
+    assert!(ratatui_init_headless(40, 20));
+    ratatui_cleanup();
+}
+
+#[test]
+fn failures_report_a_code_and_message() {
+    let _serial = serial();
+    let state = base_state(game_modes::SINGLE_PLAYER, track_types::HIGHWAY, weather::CLEAR);
+
+    ratatui_cleanup();
+    assert!(!unsafe { ratatui_render(&state) });
+    assert_eq!(ratatui_last_error_code(), ErrorCode::NotInitialized);
+    assert!(last_error().contains("not initialized"));
+
+    assert!(!unsafe { ratatui_render(std::ptr::null()) });
+    assert_eq!(ratatui_last_error_code(), ErrorCode::NullPointer);
+    assert_eq!(last_error(), "`state` must not be null");
+
+    assert!(!ratatui_init_headless(0, 10));
+    assert_eq!(ratatui_last_error_code(), ErrorCode::InvalidArgument);
+    assert!(last_error().contains("0x10"));
+
+    assert!(ratatui_init_headless(40, 20));
+    let title = c"MENU";
+    let invalid = [b'o' as std::ffi::c_char, 0xffu8 as std::ffi::c_char, 0];
+    let options = [c"Start".as_ptr(), invalid.as_ptr()];
+    assert!(!unsafe { ratatui_render_menu(title.as_ptr(), options.as_ptr(), 2, 0) });
+    assert_eq!(ratatui_last_error_code(), ErrorCode::InvalidUtf8);
+    assert!(last_error().starts_with("menu option 1 is not valid UTF-8"));
+
+    // Errors are kept until the next failure and are per thread
+    assert!(unsafe { ratatui_render(&state) });
+    assert_eq!(ratatui_last_error_code(), ErrorCode::InvalidUtf8);
+    std::thread::spawn(|| assert_eq!(ratatui_last_error_code(), ErrorCode::Ok)).join().unwrap();
+
+    // A short buffer is truncated but still NUL-terminated
+    let full = unsafe { ratatui_last_error(std::ptr::null_mut(), 0) };
+    let mut short = [0x7fu8; 5];
+    assert_eq!(unsafe { ratatui_last_error(short.as_mut_ptr() as *mut _, short.len()) }, full);
+    assert_eq!(&short, b"menu\0");
+
+    ratatui_cleanup();
+}
+
+#[test]
+fn bindings_are_set_and_reported() {
+    let _serial = serial();
+    let report = || {
+        let len = unsafe { ratatui_get_bindings(std::ptr::null_mut(), 0) };
+        let mut buf = vec![0u8; len as usize + 1];
+        unsafe { ratatui_get_bindings(buf.as_mut_ptr() as *mut _, buf.len()) };
+        buf.pop();
+        String::from_utf8(buf).unwrap()
+    };
+    let defaults = report();
+    assert!(defaults.contains("\"p1_left\": [\"Left\",\"a\"]"));
+
+    assert!(unsafe { ratatui_set_bindings(c"p1_boost = [\"b\"]".as_ptr()) });
+    assert!(report().contains("\"p1_boost\": [\"b\"]"));
+
+    // The HUD hints follow the new keys
+    assert!(ratatui_init_headless(100, 40));
+    let state = base_state(game_modes::SINGLE_PLAYER, track_types::HIGHWAY, weather::CLEAR);
+    assert!(unsafe { ratatui_render(&state) });
+    assert!(snapshot_text().contains("B Boost"));
+    ratatui_cleanup();
+
+    // A rejected table leaves the active one in place
+    assert!(!unsafe { ratatui_set_bindings(c"p2_boost = [\"a\"]".as_ptr()) });
+    assert_eq!(ratatui_last_error_code(), ErrorCode::BindingConflict);
+    assert!(report().contains("\"p1_boost\": [\"b\"]"));
+
+    assert!(!unsafe { ratatui_load_bindings(c"/nonexistent/keys.toml".as_ptr()) });
+    assert_eq!(ratatui_last_error_code(), ErrorCode::Io);
+
+    assert!(unsafe { ratatui_set_bindings(c"{}".as_ptr()) });
+    assert_eq!(report(), defaults);
+}
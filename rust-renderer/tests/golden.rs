@@ -0,0 +1,152 @@
+//! Golden frame snapshots for every game mode, track, weather and powerup
+
+mod common;
+
+use common::*;
+use ratatui::layout::Rect;
+use rust_renderer::core::types::{game_modes, track_types, weather};
+use rust_renderer::rendering::{hud, size, track, FrameTime, Scene};
+
+const WIDTH: u16 = 100;
+const HEIGHT: u16 = 40;
+
+#[test]
+fn every_game_mode_on_every_track() {
+    let objects = Objects::standard();
+
+    for (mode, mode_name) in GAME_MODES {
+        for (track_type, track_name) in TRACK_TYPES {
+            let mut state = base_state(mode, track_type, weather::CLEAR);
+            objects.attach(&mut state);
+
+            let text = render_to_text(WIDTH, HEIGHT, |f| rust_renderer::render_game(f, &state));
+            assert_snapshot(&format!("mode_{}__{}", mode_name, track_name), &text);
+        }
+    }
+}
+
+#[test]
+fn every_weather_on_every_track() {
+    let objects = Objects::standard();
+
+    for (track_type, track_name) in TRACK_TYPES {
+        for (weather_type, weather_name) in WEATHER {
+            let mut state = base_state(game_modes::SINGLE_PLAYER, track_type, weather_type);
+            objects.attach(&mut state);
+
+            let text = render_to_text(WIDTH, 30, |f| {
+                let area = f.area();
+                track::render_track(f, area, &state, state.player_position, state.player_distance, true, &Scene::default());
+            });
+            assert_snapshot(&format!("weather_{}__{}", track_name, weather_name), &text);
+        }
+    }
+}
+
+#[test]
+fn every_powerup() {
+    let objects = Objects::standard();
+    let powerups = ["boost", "shield", "invincibility", "magnet", "slowmo"];
+
+    for name in powerups {
+        let mut state = base_state(game_modes::SINGLE_PLAYER, track_types::HIGHWAY, weather::CLEAR);
+        objects.attach(&mut state);
+        match name {
+            "boost" => (state.boost_active, state.boost_remaining) = (true, 80.0),
+            "shield" => (state.shield_active, state.shield_remaining) = (true, 60.0),
+            "invincibility" => (state.invincibility_active, state.invincibility_remaining) = (true, 40.0),
+            "magnet" => (state.magnet_active, state.magnet_remaining) = (true, 20.0),
+            _ => (state.slowmo_active, state.slowmo_remaining) = (true, 50.0),
+        }
+
+        let text = render_to_text(WIDTH, HEIGHT, |f| rust_renderer::render_game(f, &state));
+        assert_snapshot(&format!("powerup_{}", name), &text);
+    }
+}
+
+#[test]
+fn hud_panels() {
+    let state = base_state(game_modes::SPLIT_SCREEN, track_types::HIGHWAY, weather::CLEAR);
+
+    let text = render_to_text(WIDTH, 6, |f| hud::render_enhanced_hud(f, f.area(), &state, FrameTime::default()));
+    assert_snapshot("hud_enhanced", &text);
+
+    let text = render_to_text(50, 4, |f| hud::render_player_hud(f, f.area(), &state, 2));
+    assert_snapshot("hud_player2", &text);
+
+    let text = render_to_text(60, 3, |f| hud::render_career_info(f, f.area(), &state));
+    assert_snapshot("hud_career_info", &text);
+
+    let options = ["Quick Race".to_string(), "Career".to_string(), "Quit".to_string()];
+    let text = render_to_text(40, 14, |f| hud::render_menu(f, "TERMINAL RACER", &options, 1));
+    assert_snapshot("hud_menu", &text);
+}
+
+#[test]
+fn empty_scene_renders() {
+    let objects = Objects::empty();
+    let mut state = base_state(game_modes::SINGLE_PLAYER, track_types::CITY, weather::CLEAR);
+    objects.attach(&mut state);
+
+    let text = render_to_text(WIDTH, HEIGHT, |f| rust_renderer::render_game(f, &state));
+    assert_snapshot("empty_scene", &text);
+}
+
+#[test]
+fn narrow_track_areas_do_not_panic() {
+    let objects = Objects::standard();
+
+    for (track_type, _) in TRACK_TYPES {
+        let mut state = base_state(game_modes::SINGLE_PLAYER, track_type, weather::RAIN);
+        objects.attach(&mut state);
+        state.replay_mode = true;
+
+        for width in 10..24 {
+            render_to_text(width, 24, |f| {
+                let area = Rect::new(0, 0, width, 24);
+                track::render_track(f, area, &state, state.player_position, state.player_distance, true, &Scene::default());
+            });
+        }
+    }
+}
+
+#[test]
+fn short_track_areas_do_not_panic() {
+    let objects = Objects::standard();
+
+    for (track_type, _) in TRACK_TYPES {
+        let mut state = base_state(game_modes::SINGLE_PLAYER, track_type, weather::RAIN);
+        objects.attach(&mut state);
+        state.replay_mode = true;
+
+        for height in 0..12 {
+            render_to_text(40, 12, |f| {
+                let area = Rect::new(0, 0, 40, height);
+                track::render_track(f, area, &state, state.player_position, state.player_distance, true, &Scene::default());
+            });
+        }
+    }
+}
+
+#[test]
+fn small_terminals_get_a_resize_prompt() {
+    let objects = Objects::standard();
+
+    for (mode, _) in GAME_MODES {
+        let mut state = base_state(mode, track_types::CITY, weather::CLEAR);
+        objects.attach(&mut state);
+        let (min_width, min_height) = size::min_size(&state);
+
+        // Every size up to the minimum, including a terminal shrunk to nothing
+        for (width, height) in [(1, 1), (min_width - 1, min_height), (min_width, min_height - 1), (20, 5)] {
+            let text = render_to_text(width, height, |f| rust_renderer::render_game(f, &state));
+            assert!(!text.contains("═══"), "{}x{} still draws the track", width, height);
+        }
+        let text = render_to_text(min_width, min_height, |f| rust_renderer::render_game(f, &state));
+        assert!(text.contains("═══") && !text.contains("enlarge"));
+    }
+
+    let state = base_state(game_modes::SPLIT_SCREEN, track_types::CITY, weather::CLEAR);
+    let text = render_to_text(60, 12, |f| rust_renderer::render_game(f, &state));
+    assert_snapshot("too_small_split", &text);
+}
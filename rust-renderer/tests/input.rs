@@ -0,0 +1,190 @@
+//! Held-key tracking - release events, the auto-repeat fallback, menu input
+//! and typed text
+
+use std::time::{Duration, Instant};
+use crossterm::event::{KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use rust_renderer::io::input::{KeyTracker, DEBUG_OVERLAY_KEY, FIRST_REPEAT_TIMEOUT, REPEAT_TIMEOUT};
+use rust_renderer::core::types::INPUT_TEXT_CAPACITY;
+use rust_renderer::InputState;
+
+fn key(c: char, kind: KeyEventKind) -> KeyEvent {
+    KeyEvent::new_with_kind(KeyCode::Char(c), KeyModifiers::NONE, kind)
+}
+
+/// Feed events at `now` and return the resulting input state
+fn poll(tracker: &mut KeyTracker, now: Instant, events: &[KeyEvent]) -> InputState {
+    let mut input = InputState::default();
+    for &event in events {
+        tracker.handle(event, now, &mut input);
+    }
+    tracker.apply(now, &mut input);
+    input
+}
+
+fn ms(n: u64) -> Duration {
+    Duration::from_millis(n)
+}
+
+#[test]
+fn keys_stay_held_until_released() {
+    let mut tracker = KeyTracker::new();
+    tracker.set_release_events(true);
+    let t0 = Instant::now();
+
+    // Both players steer and accelerate at once
+    let input = poll(&mut tracker, t0, &[
+        key('w', KeyEventKind::Press),
+        key('a', KeyEventKind::Press),
+        key('i', KeyEventKind::Press),
+        key('l', KeyEventKind::Press),
+    ]);
+    assert!(input.p1_accel && input.p1_left && input.p2_accel && input.p2_right);
+
+    // No events for a long time: still held
+    let input = poll(&mut tracker, t0 + ms(2000), &[]);
+    assert!(input.p1_accel && input.p1_left && input.p2_accel && input.p2_right);
+
+    let input = poll(&mut tracker, t0 + ms(2016), &[key('a', KeyEventKind::Release)]);
+    assert!(input.p1_accel && !input.p1_left);
+
+    // Released with Shift down still releases the lowercase press
+    let input = poll(&mut tracker, t0 + ms(2032), &[key('W', KeyEventKind::Release)]);
+    assert!(!input.p1_accel && input.p2_accel);
+}
+
+#[test]
+fn taps_within_one_poll_register() {
+    let mut tracker = KeyTracker::new();
+    tracker.set_release_events(true);
+    let t0 = Instant::now();
+
+    let input = poll(&mut tracker, t0, &[key('d', KeyEventKind::Press), key('d', KeyEventKind::Release)]);
+    assert!(input.p1_right);
+    assert!(!poll(&mut tracker, t0 + ms(16), &[]).p1_right);
+}
+
+#[test]
+fn system_keys_fire_once_per_press() {
+    let mut tracker = KeyTracker::new();
+    tracker.set_release_events(true);
+    let t0 = Instant::now();
+
+    assert!(poll(&mut tracker, t0, &[key('p', KeyEventKind::Press)]).pause);
+    assert!(!poll(&mut tracker, t0 + ms(16), &[key('p', KeyEventKind::Repeat)]).pause);
+    assert!(!poll(&mut tracker, t0 + ms(32), &[key('p', KeyEventKind::Release)]).pause);
+    assert!(poll(&mut tracker, t0 + ms(48), &[key('p', KeyEventKind::Press)]).pause);
+}
+
+#[test]
+fn without_release_events_auto_repeat_keeps_keys_held() {
+    let mut tracker = KeyTracker::new();
+    let t0 = Instant::now();
+    let press = |c| key(c, KeyEventKind::Press);
+
+    // Held through the initial auto-repeat delay
+    assert!(poll(&mut tracker, t0, &[press('w')]).p1_accel);
+    assert!(poll(&mut tracker, t0 + FIRST_REPEAT_TIMEOUT - ms(1), &[]).p1_accel);
+
+    // Repeats keep it alive; steering pressed meanwhile does not drop it
+    let mut now = t0 + FIRST_REPEAT_TIMEOUT - ms(1);
+    for _ in 0..10 {
+        now += ms(33);
+        let input = poll(&mut tracker, now, &[press('w'), press('j')]);
+        assert!(input.p1_accel && input.p2_left);
+    }
+
+    // Released once the repeats stop
+    assert!(poll(&mut tracker, now + REPEAT_TIMEOUT - ms(1), &[]).p1_accel);
+    assert!(!poll(&mut tracker, now + REPEAT_TIMEOUT, &[]).p1_accel);
+
+    // A single tap expires after the first-repeat window
+    assert!(poll(&mut tracker, now, &[press('s')]).p1_brake);
+    assert!(!poll(&mut tracker, now + FIRST_REPEAT_TIMEOUT, &[]).p1_brake);
+}
+
+#[test]
+fn without_release_events_repeated_system_keys_are_ignored() {
+    let mut tracker = KeyTracker::new();
+    let t0 = Instant::now();
+    let press = key('q', KeyEventKind::Press);
+
+    assert!(poll(&mut tracker, t0, &[press]).quit);
+    assert!(!poll(&mut tracker, t0 + ms(500), &[press]).quit);
+    assert!(!poll(&mut tracker, t0 + ms(533), &[press]).quit);
+    assert!(poll(&mut tracker, t0 + ms(533) + REPEAT_TIMEOUT, &[press]).quit);
+}
+
+#[test]
+fn overlay_key_toggles_on_each_tap() {
+    let mut tracker = KeyTracker::new();
+    let t0 = Instant::now();
+    let f3 = |kind| KeyEvent::new_with_kind(DEBUG_OVERLAY_KEY, KeyModifiers::NONE, kind);
+    let press = f3(KeyEventKind::Press);
+
+    // Two quick taps, well inside the first-repeat window, both toggle
+    assert!(tracker.toggles_overlay(&press, t0));
+    assert!(tracker.toggles_overlay(&press, t0 + ms(300)));
+
+    // Held: auto-repeat arriving every few frames does not
+    let mut now = t0 + ms(800);
+    assert!(tracker.toggles_overlay(&press, now));
+    for _ in 0..10 {
+        now += ms(33);
+        assert!(!tracker.toggles_overlay(&press, now));
+    }
+    assert!(tracker.toggles_overlay(&press, now + REPEAT_TIMEOUT));
+
+    // With release events only real repeats are ignored
+    tracker.set_release_events(true);
+    assert!(tracker.toggles_overlay(&press, now));
+    assert!(tracker.toggles_overlay(&press, now + ms(10)));
+    assert!(!tracker.toggles_overlay(&f3(KeyEventKind::Repeat), now + ms(20)));
+    assert!(!tracker.toggles_overlay(&f3(KeyEventKind::Release), now + ms(30)));
+    assert!(!tracker.toggles_overlay(&key('d', KeyEventKind::Press), now + ms(40)));
+}
+
+#[test]
+fn menu_navigation_fires_on_press_and_repeat() {
+    let mut tracker = KeyTracker::new();
+    tracker.set_release_events(true);
+    let t0 = Instant::now();
+    let special = |code, kind| KeyEvent::new_with_kind(code, KeyModifiers::NONE, kind);
+
+    // Up steers player 1 and moves the menu selection at the same time
+    let input = poll(&mut tracker, t0, &[special(KeyCode::Up, KeyEventKind::Press)]);
+    assert!(input.p1_accel && input.menu_up);
+
+    // Held: the gameplay action stays, navigation only follows repeats
+    let input = poll(&mut tracker, t0 + ms(16), &[]);
+    assert!(input.p1_accel && !input.menu_up);
+    assert!(poll(&mut tracker, t0 + ms(32), &[special(KeyCode::Up, KeyEventKind::Repeat)]).menu_up);
+
+    // Confirm and back fire once per press
+    assert!(poll(&mut tracker, t0 + ms(48), &[special(KeyCode::Enter, KeyEventKind::Press)]).menu_confirm);
+    assert!(!poll(&mut tracker, t0 + ms(64), &[special(KeyCode::Enter, KeyEventKind::Repeat)]).menu_confirm);
+    let input = poll(&mut tracker, t0 + ms(80), &[special(KeyCode::Esc, KeyEventKind::Press)]);
+    assert!(input.menu_back && input.quit);
+}
+
+#[test]
+fn typed_characters_are_collected() {
+    let mut tracker = KeyTracker::new();
+    let t0 = Instant::now();
+    let ctrl_c = KeyEvent::new_with_kind(KeyCode::Char('c'), KeyModifiers::CONTROL, KeyEventKind::Press);
+
+    let input = poll(&mut tracker, t0, &[
+        key('A', KeyEventKind::Press),
+        key('c', KeyEventKind::Release),
+        ctrl_c,
+        key('é', KeyEventKind::Press),
+        key(' ', KeyEventKind::Press),
+    ]);
+    assert_eq!(input.typed(), "Aé ");
+    assert!(input.p1_left && input.p1_boost);
+
+    // Anything past the buffer is dropped
+    let presses: Vec<KeyEvent> = "abcdefghijklmnopqrstuvwxyz".chars().map(|c| key(c, KeyEventKind::Press)).collect();
+    let input = poll(&mut tracker, t0 + ms(16), &presses);
+    assert_eq!(input.typed(), "abcdefghijklmnop");
+    assert_eq!(input.text_len as usize, INPUT_TEXT_CAPACITY);
+}
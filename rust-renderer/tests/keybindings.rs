@@ -0,0 +1,147 @@
+//! Key binding tables - parsing, conflicts and the keys they report
+
+use crossterm::event::{KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use rust_renderer::io::bindings::{Action, Bindings, KeyBinding};
+use rust_renderer::io::input::KeyTracker;
+use rust_renderer::{ErrorCode, InputState};
+use std::sync::Arc;
+use std::time::Instant;
+
+fn press(code: KeyCode, modifiers: KeyModifiers) -> KeyEvent {
+    KeyEvent::new_with_kind(code, modifiers, KeyEventKind::Press)
+}
+
+fn char_key(c: char) -> KeyEvent {
+    press(KeyCode::Char(c), KeyModifiers::NONE)
+}
+
+#[test]
+fn defaults_match_the_original_layout() {
+    let bindings = Bindings::default();
+    assert_eq!(bindings.action_for(&char_key('w')), Some(Action::P1Accel));
+    assert_eq!(bindings.action_for(&char_key('W')), Some(Action::P1Accel));
+    assert_eq!(bindings.action_for(&press(KeyCode::Left, KeyModifiers::NONE)), Some(Action::P1Left));
+    assert_eq!(bindings.action_for(&char_key(' ')), Some(Action::P1Boost));
+    assert_eq!(bindings.action_for(&char_key('u')), Some(Action::P2Boost));
+    assert_eq!(bindings.action_for(&press(KeyCode::Esc, KeyModifiers::NONE)), Some(Action::Quit));
+    assert_eq!(bindings.action_for(&char_key('x')), None);
+
+    // Ctrl and Alt make a different key; Shift on a letter does not
+    assert_eq!(bindings.action_for(&press(KeyCode::Char('w'), KeyModifiers::CONTROL)), None);
+    assert_eq!(bindings.action_for(&press(KeyCode::Char('W'), KeyModifiers::SHIFT)), Some(Action::P1Accel));
+
+    // Gameplay and menus are separate channels sharing keys
+    assert_eq!(bindings.actions_for(&press(KeyCode::Up, KeyModifiers::NONE)), [Action::P1Accel, Action::MenuUp]);
+    assert_eq!(bindings.actions_for(&press(KeyCode::Enter, KeyModifiers::NONE)), [Action::MenuConfirm]);
+
+    assert!(bindings.check_conflicts().is_ok());
+    assert_eq!(bindings.driving_hints(1), "← → Move | ↑ Accel | ↓ Brake | SPACE Boost");
+    assert_eq!(bindings.driving_hints(2), "J L Move | I Accel | K Brake | U Boost");
+}
+
+#[test]
+fn toml_and_json_override_listed_inputs() {
+    let toml = r#"
+        p1_boost = ["Shift+Up", "b"]
+        p2_left = ["Ctrl+Left"]
+        menu = []
+    "#;
+    let json = r#"{ "p1_boost": ["Shift+Up", "b"], "p2_left": ["Ctrl+Left"], "menu": [] }"#;
+
+    for bindings in [Bindings::parse(toml).unwrap(), Bindings::parse(json).unwrap()] {
+        assert_eq!(bindings.action_for(&char_key('b')), Some(Action::P1Boost));
+        assert_eq!(bindings.action_for(&char_key(' ')), None);
+        assert_eq!(bindings.action_for(&press(KeyCode::Up, KeyModifiers::SHIFT)), Some(Action::P1Boost));
+        assert_eq!(bindings.action_for(&press(KeyCode::Up, KeyModifiers::NONE)), Some(Action::P1Accel));
+        assert_eq!(bindings.action_for(&press(KeyCode::Left, KeyModifiers::CONTROL)), Some(Action::P2Left));
+        assert_eq!(bindings.action_for(&char_key('j')), None);
+        assert_eq!(bindings.action_for(&char_key('m')), None);
+
+        // Untouched inputs keep their defaults
+        assert_eq!(bindings.action_for(&char_key('i')), Some(Action::P2Accel));
+        assert_eq!(bindings.label(Action::P1Boost), "Shift+↑");
+        assert_eq!(bindings.label(Action::Menu), "-");
+    }
+}
+
+#[test]
+fn conflicts_and_bad_tables_are_rejected() {
+    let conflict = Bindings::parse("p2_left = [\"a\"]").unwrap_err();
+    assert_eq!(conflict.code, ErrorCode::BindingConflict);
+    assert_eq!(conflict.message, "key `a` is bound to both p1_left and p2_left");
+
+    // Only keys shared within one channel conflict
+    assert!(Bindings::parse("menu_confirm = [\"Space\"]").is_ok());
+    let conflict = Bindings::parse("menu_confirm = [\"Up\"]").unwrap_err();
+    assert_eq!(conflict.message, "key `Up` is bound to both menu_up and menu_confirm");
+
+    // Letters conflict regardless of case
+    let conflict = Bindings::parse("pause = [\"Q\"]").unwrap_err();
+    assert_eq!(conflict.code, ErrorCode::BindingConflict);
+
+    // Moving a key away first is fine
+    assert!(Bindings::parse("p1_left = [\"Left\"]\np2_left = [\"a\"]").is_ok());
+
+    for bad in ["p3_left = [\"x\"]", "p1_left = [\"Hyper+x\"]", "p1_left = [\"Lefty\"]", "p1_left = \"a\"", "{ \"p1_left\": "] {
+        let err = Bindings::parse(bad).unwrap_err();
+        assert_eq!(err.code, ErrorCode::InvalidBindings, "{}", bad);
+    }
+}
+
+#[test]
+fn key_specs_round_trip() {
+    for spec in ["a", "Space", "Left", "Esc", "Enter", "F5", "PageUp", "Ctrl+Alt+Delete", "Shift+Tab", "+", "Ctrl++"] {
+        let key = KeyBinding::parse(spec).unwrap();
+        assert_eq!(KeyBinding::parse(&key.spec()).unwrap(), key, "{}", spec);
+    }
+    assert_eq!(KeyBinding::parse("ctrl+esc").unwrap().spec(), "Ctrl+Esc");
+}
+
+#[test]
+fn plus_is_only_a_key_when_spelled_out() {
+    assert_eq!(KeyBinding::parse("+").unwrap(), KeyBinding::new(KeyCode::Char('+'), KeyModifiers::NONE));
+    assert_eq!(KeyBinding::parse("Ctrl++").unwrap(), KeyBinding::new(KeyCode::Char('+'), KeyModifiers::CONTROL));
+
+    // A modifier with its key left out is a typo, not a binding for `+`
+    for spec in ["Ctrl+", "Shift+Alt+", "++", ""] {
+        let err = KeyBinding::parse(spec).unwrap_err();
+        assert_eq!(err.code, ErrorCode::InvalidBindings, "{}", spec);
+    }
+}
+
+#[test]
+fn json_report_lists_every_input_in_order() {
+    let bindings = Bindings::parse("p1_boost = [\"Enter\"]").unwrap();
+    let json = bindings.to_json();
+
+    let positions: Vec<usize> = Action::ALL
+        .iter()
+        .map(|a| json.find(&format!("\"{}\"", a.name())).unwrap())
+        .collect();
+    assert!(positions.windows(2).all(|w| w[0] < w[1]));
+    assert!(json.contains("\"p1_boost\": [\"Enter\"]"));
+
+    // The report parses back to the same table
+    assert_eq!(Bindings::parse(&json).unwrap(), bindings);
+}
+
+#[test]
+fn tracker_follows_rebinding() {
+    let mut tracker = KeyTracker::new();
+    tracker.set_release_events(true);
+    let now = Instant::now();
+
+    let mut input = InputState::default();
+    tracker.handle(char_key('w'), now, &mut input);
+    assert!(input.p1_accel);
+
+    tracker.set_bindings(Arc::new(Bindings::parse("p1_accel = [\"e\"]").unwrap()));
+    let mut input = InputState::default();
+    tracker.apply(now, &mut input);
+    assert!(!input.p1_accel, "held keys are forgotten on rebinding");
+
+    tracker.handle(char_key('w'), now, &mut input);
+    assert!(!input.p1_accel);
+    tracker.handle(char_key('e'), now, &mut input);
+    assert!(input.p1_accel);
+}
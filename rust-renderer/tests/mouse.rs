@@ -0,0 +1,115 @@
+//! Mouse hit-testing - menu options, the replay timeline and drag tracking
+
+mod common;
+
+use common::*;
+use crossterm::event::{KeyModifiers, MouseButton, MouseEvent as TermMouseEvent, MouseEventKind};
+use ratatui::layout::Rect;
+use rust_renderer::core::types::{game_modes, hit_targets, mouse_buttons, mouse_kinds, track_types, weather};
+use rust_renderer::io::mouse::{MouseTracker, QUEUE_CAPACITY};
+use rust_renderer::rendering::hit::{HitMap, HitTarget};
+use rust_renderer::rendering::hud;
+use rust_renderer::*;
+
+fn mouse(kind: MouseEventKind, column: u16, row: u16) -> TermMouseEvent {
+    TermMouseEvent { kind, column, row, modifiers: KeyModifiers::NONE }
+}
+
+/// Row of the first line containing `needle`
+fn row_of(text: &str, needle: &str) -> u16 {
+    text.lines().position(|l| l.contains(needle)).unwrap_or_else(|| panic!("{} not drawn", needle)) as u16
+}
+
+#[test]
+fn menu_options_are_hit_where_they_are_drawn() {
+    let options = ["Quick Race".to_string(), "Career".to_string(), "Quit".to_string()];
+    let text = render_to_text(40, 14, |f| hud::render_menu(f, "TERMINAL RACER", &options, 1));
+    let hits = HitMap::for_menu(Rect::new(0, 0, 40, 14), options.len());
+
+    for (i, option) in options.iter().enumerate() {
+        let row = row_of(&text, option);
+        assert_eq!(hits.hit(5, row), Some(HitTarget::MenuItem(i)), "{}", option);
+    }
+
+    // Borders, the title and rows past the last option hit nothing
+    assert_eq!(hits.hit(0, row_of(&text, "Career")), None);
+    assert_eq!(hits.hit(5, row_of(&text, "Select Option")), None);
+    assert_eq!(hits.hit(5, row_of(&text, "Quit") + 1), None);
+    assert_eq!(hits.hit(5, row_of(&text, "TERMINAL RACER")), None);
+}
+
+#[test]
+fn replay_timeline_maps_columns_to_positions() {
+    let state = base_state(game_modes::REPLAY, track_types::HIGHWAY, weather::CLEAR);
+    let text = render_to_text(100, 40, |f| render_game(f, &state));
+    let hits = HitMap::for_game(Rect::new(0, 0, 100, 40), &state);
+    let bar = row_of(&text, "40%");
+
+    assert_eq!(hits.hit(0, bar), Some(HitTarget::ReplayTimeline(0.0)));
+    assert_eq!(hits.hit(99, bar), Some(HitTarget::ReplayTimeline(1.0)));
+    assert!(matches!(hits.hit(33, bar), Some(HitTarget::ReplayTimeline(p)) if (p - 1.0 / 3.0).abs() < 1e-6));
+    assert_eq!(hits.hit(50, bar - 1), None);
+
+    // Other modes have nothing to click
+    let single = base_state(game_modes::SINGLE_PLAYER, track_types::HIGHWAY, weather::CLEAR);
+    assert_eq!(HitMap::for_game(Rect::new(0, 0, 100, 40), &single), HitMap::default());
+}
+
+#[test]
+fn timeline_drags_keep_seeking_until_release() {
+    let state = base_state(game_modes::REPLAY, track_types::HIGHWAY, weather::CLEAR);
+    let hits = HitMap::for_game(Rect::new(0, 0, 100, 40), &state);
+    let bar = 39;
+    let mut tracker = MouseTracker::new();
+
+    tracker.push(mouse(MouseEventKind::Down(MouseButton::Left), 49, bar));
+    tracker.push(mouse(MouseEventKind::Moved, 60, bar));
+    tracker.push(mouse(MouseEventKind::Drag(MouseButton::Left), 120, 10));
+    tracker.push(mouse(MouseEventKind::Up(MouseButton::Left), 0, 0));
+    tracker.push(mouse(MouseEventKind::Drag(MouseButton::Left), 0, 10));
+
+    let down = tracker.next(&hits).unwrap();
+    assert_eq!((down.kind, down.button, down.target), (mouse_kinds::DOWN, mouse_buttons::LEFT, hit_targets::REPLAY_TIMELINE));
+    assert!((down.position - 49.0 / 99.0).abs() < 1e-6);
+
+    // Moves are dropped; the drag off the bar clamps to the end
+    let drag = tracker.next(&hits).unwrap();
+    assert_eq!((drag.kind, drag.target, drag.position), (mouse_kinds::DRAG, hit_targets::REPLAY_TIMELINE, 1.0));
+
+    let up = tracker.next(&hits).unwrap();
+    assert_eq!((up.kind, up.target, up.position), (mouse_kinds::UP, hit_targets::REPLAY_TIMELINE, 0.0));
+
+    let stray = tracker.next(&hits).unwrap();
+    assert_eq!((stray.kind, stray.target, stray.item), (mouse_kinds::DRAG, hit_targets::NONE, -1));
+    assert!(tracker.next(&hits).is_none());
+}
+
+#[test]
+fn queue_keeps_the_newest_events() {
+    let hits = HitMap::for_menu(Rect::new(0, 0, 40, 14), 3);
+    let mut tracker = MouseTracker::new();
+    for row in 0..QUEUE_CAPACITY as u16 + 5 {
+        tracker.push(mouse(MouseEventKind::ScrollDown, 0, row));
+    }
+
+    let first = tracker.next(&hits).unwrap();
+    assert_eq!((first.kind, first.button, first.row), (mouse_kinds::SCROLL_DOWN, mouse_buttons::NONE, 5));
+    tracker.clear();
+    assert!(tracker.next(&hits).is_none());
+}
+
+#[test]
+fn headless_renderers_have_no_mouse() {
+    let config = RendererConfig { headless: true, width: 40, height: 14, ..Default::default() };
+    let renderer = unsafe { ratatui_create(&config) };
+    assert!(!renderer.is_null());
+
+    assert!(unsafe { ratatui_renderer_set_mouse_capture(renderer, true) });
+    let mut event = MouseEvent { item: 7, ..Default::default() };
+    assert!(!unsafe { ratatui_renderer_poll_mouse(renderer, &mut event) });
+    assert_eq!(event.item, 7);
+    assert!(!unsafe { ratatui_renderer_poll_mouse(renderer, std::ptr::null_mut()) });
+    assert_eq!(ratatui_last_error_code(), ErrorCode::NullPointer);
+
+    unsafe { ratatui_destroy(renderer) };
+}
@@ -0,0 +1,150 @@
+//! Frame pacing - target rate, measured frame cost and adaptive quality
+//! dropping rain, particles and building windows
+
+mod common;
+
+use std::time::{Duration, Instant};
+use common::*;
+use rust_renderer::core::types::{game_modes, quality_levels, track_types, weather, GameState};
+use rust_renderer::rendering::{Effects, FramePacer, Scene};
+use rust_renderer::{
+    ratatui_renderer_frame_stats, ratatui_renderer_set_quality, ratatui_renderer_set_target_fps,
+    ratatui_renderer_wait_frame, render_game_with, FrameStats, Renderer, RendererConfig,
+};
+
+const MS: Duration = Duration::from_millis(1);
+
+fn at_quality(state: &GameState, level: i32) -> String {
+    let mut scene = Scene::default();
+    scene.pacer_mut().set_quality(level, false).unwrap();
+    for _ in 0..6 {
+        scene.step(state, 1.0 / 30.0);
+    }
+    render_to_text(100, 40, |f| render_game_with(f, state, &scene))
+}
+
+#[test]
+fn each_level_drops_one_more_effect() {
+    let all = Effects { rain: true, particles: true, windows: true };
+    assert_eq!(Effects::at(quality_levels::HIGH), all);
+    assert_eq!(Effects::at(quality_levels::MEDIUM), Effects { rain: false, ..all });
+    assert_eq!(Effects::at(quality_levels::LOW), Effects { rain: false, particles: false, windows: true });
+    assert_eq!(Effects::at(quality_levels::MINIMAL), Effects { rain: false, particles: false, windows: false });
+
+    let mut pacer = FramePacer::default();
+    assert!(pacer.set_quality(quality_levels::MINIMAL + 1, true).is_err());
+    assert!(pacer.set_quality(-1, false).is_err());
+    assert_eq!((pacer.quality(), pacer.adaptive()), (quality_levels::HIGH, false), "left unchanged");
+}
+
+#[test]
+fn lower_quality_leaves_effects_out_of_the_frame() {
+    let objects = Objects::standard();
+    let count = |text: String, glyph: char| text.matches(glyph).count();
+
+    let mut rain = base_state(game_modes::SINGLE_PLAYER, track_types::HIGHWAY, weather::RAIN);
+    rain.boost_active = true;
+    objects.attach(&mut rain);
+    let mut clear = base_state(game_modes::SINGLE_PLAYER, track_types::HIGHWAY, weather::CLEAR);
+    clear.boost_active = true;
+    objects.attach(&mut clear);
+    let dry = count(at_quality(&clear, quality_levels::HIGH), '·');
+    assert!(count(at_quality(&rain, quality_levels::HIGH), '·') > dry);
+    assert_eq!(count(at_quality(&rain, quality_levels::MEDIUM), '·'), dry, "no rain");
+
+    assert!(at_quality(&rain, quality_levels::MEDIUM).contains('✦'));
+    assert!(!at_quality(&rain, quality_levels::LOW).contains('✦'), "no exhaust");
+
+    let mut city = base_state(game_modes::SINGLE_PLAYER, track_types::CITY, weather::CLEAR);
+    objects.attach(&mut city);
+    assert!(at_quality(&city, quality_levels::LOW).contains('▫'));
+    assert!(!at_quality(&city, quality_levels::MINIMAL).contains('▫'), "no windows");
+}
+
+#[test]
+fn adaptive_quality_follows_frame_cost() {
+    let mut pacer = FramePacer::default();
+    pacer.set_target_fps(60);
+    pacer.set_quality(quality_levels::MEDIUM, true).unwrap();
+
+    // Over budget, one level at a time once each change has settled
+    for _ in 0..30 {
+        pacer.record(25 * MS);
+    }
+    assert_eq!(pacer.quality(), quality_levels::MEDIUM, "settling after the quality was set");
+    pacer.record(25 * MS);
+    assert_eq!(pacer.quality(), quality_levels::LOW);
+    for _ in 0..100 {
+        pacer.record(25 * MS);
+    }
+    assert_eq!(pacer.quality(), quality_levels::MINIMAL);
+
+    // Just under budget keeps the level; well under raises it, but never
+    // past the level the host asked for
+    for _ in 0..100 {
+        pacer.record(14 * MS);
+    }
+    assert_eq!(pacer.quality(), quality_levels::MINIMAL);
+    for _ in 0..200 {
+        pacer.record(2 * MS);
+    }
+    assert_eq!(pacer.quality(), quality_levels::MEDIUM);
+
+    let stats = pacer.stats(7);
+    assert_eq!((stats.frame, stats.target_fps, stats.quality, stats.adaptive), (7, 60, quality_levels::MEDIUM, true));
+    assert!((stats.render_ms - 2.0).abs() < 0.01 && stats.average_ms < 3.0);
+
+    // A fixed quality stays put however slow frames get
+    pacer.set_quality(quality_levels::HIGH, false).unwrap();
+    for _ in 0..100 {
+        pacer.record(50 * MS);
+    }
+    assert_eq!(pacer.quality(), quality_levels::HIGH);
+}
+
+#[test]
+fn frames_are_due_one_period_apart() {
+    let mut pacer = FramePacer::default();
+    let start = Instant::now();
+    assert_eq!(pacer.until_next_frame(start), Duration::ZERO, "unpaced");
+
+    pacer.set_target_fps(50);
+    assert_eq!(pacer.until_next_frame(start), Duration::ZERO, "the first frame is due at once");
+    assert_eq!(pacer.until_next_frame(start + 5 * MS), 15 * MS);
+    assert_eq!(pacer.until_next_frame(start + 25 * MS), 15 * MS);
+
+    // A late host starts over instead of rushing through missed frames
+    assert_eq!(pacer.until_next_frame(start + 100 * MS), Duration::ZERO);
+    assert_eq!(pacer.until_next_frame(start + 101 * MS), 19 * MS);
+}
+
+#[test]
+fn renderers_pace_and_measure_their_frames() {
+    let state = base_state(game_modes::SINGLE_PLAYER, track_types::HIGHWAY, weather::RAIN);
+    let renderer = Renderer::new(&RendererConfig { headless: true, width: 100, height: 40, ..Default::default() }).unwrap();
+    assert_eq!(renderer.frame_stats(), FrameStats::default());
+
+    renderer.render(&state).unwrap();
+    let stats = renderer.frame_stats();
+    assert_eq!((stats.frame, stats.target_fps, stats.quality), (1, 0, quality_levels::HIGH));
+    assert!(stats.render_ms > 0.0 && stats.average_ms == stats.render_ms);
+
+    let start = Instant::now();
+    renderer.wait_frame().unwrap();
+    assert!(start.elapsed() < 10 * MS, "unpaced frames are not held back");
+
+    let handle = &renderer as *const Renderer;
+    assert!(unsafe { ratatui_renderer_set_target_fps(handle, 50) });
+    assert!(!unsafe { ratatui_renderer_set_quality(handle, 9, true) });
+    assert!(unsafe { ratatui_renderer_set_quality(handle, quality_levels::LOW, true) });
+    let start = Instant::now();
+    for _ in 0..3 {
+        assert!(unsafe { ratatui_renderer_wait_frame(handle) });
+    }
+    assert!(start.elapsed() >= 40 * MS, "two full periods after the first frame");
+
+    let mut stats = FrameStats::default();
+    assert!(!unsafe { ratatui_renderer_frame_stats(handle, std::ptr::null_mut()) });
+    assert!(unsafe { ratatui_renderer_frame_stats(handle, &mut stats) });
+    assert_eq!((stats.target_fps, stats.quality, stats.adaptive), (50, quality_levels::LOW, true));
+}
@@ -0,0 +1,129 @@
+//! Particle system - emitters, ageing by frame delta and persistence across
+//! renders
+
+mod common;
+
+use common::*;
+use rust_renderer::core::types::{game_modes, track_types, weather, GameState};
+use rust_renderer::rendering::particles::{ParticleKind, ParticleSystem, MAX_PARTICLES};
+use rust_renderer::rendering::Scene;
+use rust_renderer::{Renderer, RendererConfig};
+
+const FRAME: f32 = 1.0 / 30.0;
+
+fn run(particles: &mut ParticleSystem, state: &GameState, seconds: f32) {
+    for _ in 0..(seconds / FRAME).round() as usize {
+        particles.update(state, FRAME);
+    }
+}
+
+#[test]
+fn boost_leaves_an_exhaust_trail_that_fades() {
+    let mut state = base_state(game_modes::SINGLE_PLAYER, track_types::HIGHWAY, weather::CLEAR);
+    let mut particles = ParticleSystem::default();
+
+    run(&mut particles, &state, 1.0);
+    assert!(particles.is_empty(), "nothing to emit on a clear highway without boost");
+
+    state.boost_active = true;
+    run(&mut particles, &state, 0.5);
+    assert!(particles.count(ParticleKind::Exhaust) > 0);
+    assert_eq!(particles.len(), particles.count(ParticleKind::Exhaust));
+
+    state.boost_active = false;
+    run(&mut particles, &state, 1.0);
+    assert!(particles.is_empty());
+}
+
+#[test]
+fn losing_health_throws_sparks() {
+    let mut state = base_state(game_modes::SPLIT_SCREEN, track_types::HIGHWAY, weather::CLEAR);
+    let mut particles = ParticleSystem::default();
+
+    // The first frame only records health
+    particles.update(&state, FRAME);
+    assert!(particles.is_empty());
+
+    state.player2_health -= 10;
+    particles.update(&state, FRAME);
+    let burst = particles.count(ParticleKind::Spark);
+    assert!(burst > 0);
+
+    state.player_health -= 10;
+    particles.update(&state, FRAME);
+    assert_eq!(particles.count(ParticleKind::Spark), burst * 2);
+
+    state.player_health += 20;
+    run(&mut particles, &state, 1.0);
+    assert!(particles.is_empty(), "repairs throw nothing and sparks burn out");
+}
+
+#[test]
+fn dust_and_spray_follow_track_and_weather() {
+    let mut particles = ParticleSystem::default();
+    run(&mut particles, &base_state(game_modes::SINGLE_PLAYER, track_types::DESERT, weather::CLEAR), 0.2);
+    assert!(particles.count(ParticleKind::Dust) > 0);
+    assert_eq!(particles.count(ParticleKind::Spray), 0);
+
+    let mut particles = ParticleSystem::default();
+    let mut rain = base_state(game_modes::SINGLE_PLAYER, track_types::CITY, weather::RAIN);
+    run(&mut particles, &rain, 0.2);
+    assert!(particles.count(ParticleKind::Spray) > 0);
+    assert_eq!(particles.count(ParticleKind::Dust), 0);
+
+    // Standing still throws nothing up
+    let mut particles = ParticleSystem::default();
+    rain.player_speed = 0.0;
+    run(&mut particles, &rain, 0.2);
+    assert!(particles.is_empty());
+}
+
+#[test]
+fn long_frames_are_capped_and_so_is_the_count() {
+    let mut state = base_state(game_modes::SPLIT_SCREEN, track_types::DESERT, weather::RAIN);
+    state.boost_active = true;
+
+    // A stalled host does not age particles out in one step
+    let mut particles = ParticleSystem::default();
+    particles.update(&state, FRAME);
+    state.player_health -= 1;
+    particles.update(&state, 5.0);
+    assert!(particles.count(ParticleKind::Spark) > 0);
+
+    for _ in 0..200 {
+        particles.update(&state, 0.001);
+        state.player_health -= 1;
+    }
+    assert_eq!(particles.len(), MAX_PARTICLES);
+}
+
+#[test]
+fn particles_are_drawn_over_the_track() {
+    let mut state = base_state(game_modes::SINGLE_PLAYER, track_types::HIGHWAY, weather::CLEAR);
+    state.boost_active = true;
+    let mut scene = Scene::default();
+    for _ in 0..6 {
+        scene.step(&state, FRAME);
+    }
+    assert!(!scene.particles().is_empty());
+
+    let with = render_to_text(100, 40, |f| rust_renderer::render_game_with(f, &state, &scene));
+    let without = render_to_text(100, 40, |f| rust_renderer::render_game(f, &state));
+    assert!(with.contains('✦'));
+    assert!(!without.contains('✦'));
+}
+
+#[test]
+fn renderers_keep_particles_between_frames() {
+    let mut state = base_state(game_modes::SINGLE_PLAYER, track_types::HIGHWAY, weather::CLEAR);
+    state.boost_active = true;
+    let renderer = Renderer::new(&RendererConfig { headless: true, width: 100, height: 40, ..Default::default() }).unwrap();
+
+    // The first frame starts the clock, so later frames have a delta to emit over
+    renderer.render(&state).unwrap();
+    assert!(!renderer.snapshot_text().unwrap().contains('✦'));
+
+    std::thread::sleep(std::time::Duration::from_millis(60));
+    renderer.render(&state).unwrap();
+    assert!(renderer.snapshot_text().unwrap().contains('✦'));
+}
@@ -0,0 +1,159 @@
+//! Pseudo-3D road projection - perspective, curve bending, elevation and
+//! sprite scaling
+
+mod common;
+
+use common::*;
+use ratatui::layout::Rect;
+use rust_renderer::core::types::{game_modes, track_types, weather};
+use rust_renderer::rendering::projection::{Projection, SpriteSize, DRAW_DISTANCE, MAX_LANES, REAR_DISTANCE};
+
+const AREA: Rect = Rect::new(1, 7, 98, 30);
+
+fn straight() -> Projection {
+    let mut state = base_state(game_modes::SINGLE_PLAYER, track_types::HIGHWAY, weather::CLEAR);
+    state.curve_offset = 0.0;
+    state.elevation = 0.0;
+    Projection::new(AREA, &state, 3.0)
+}
+
+#[test]
+fn road_narrows_towards_the_horizon() {
+    let projection = straight();
+    let rows = projection.horizon()..AREA.bottom();
+    let widths: Vec<f32> = rows.clone().map(|y| projection.road_width(projection.row_scale(y).unwrap())).collect();
+
+    assert!(widths.windows(2).all(|w| w[0] < w[1]), "{:?}", widths);
+    assert!(widths[widths.len() - 1] > AREA.width as f32 * 0.9);
+    assert!(widths[0] < AREA.width as f32 * 0.1);
+
+    // Nothing above the horizon is road
+    assert_eq!(projection.row_scale(projection.horizon() - 1), None);
+    assert_eq!(projection.distance_at(AREA.bottom()), None);
+}
+
+#[test]
+fn lane_markers_converge() {
+    let projection = straight();
+    let gap = |scale| projection.lane_boundary(2, scale) - projection.lane_boundary(1, scale);
+
+    assert!(gap(0.2) < gap(0.5) && gap(0.5) < gap(1.0));
+    // Without a curve every lane boundary points at the same vanishing point
+    let center = projection.center(1.0);
+    assert!((projection.center(0.01) - center).abs() < 1e-3);
+    assert!((projection.lane_center(1, 0.3) - center).abs() < 1e-3);
+}
+
+#[test]
+fn lane_count_follows_the_track() {
+    let lanes = |track| {
+        let state = base_state(game_modes::SINGLE_PLAYER, track, weather::CLEAR);
+        let projection = Projection::new(AREA, &state, 2.0);
+        (projection.lanes(), projection.marker())
+    };
+
+    assert_eq!(lanes(track_types::CITY), (4, "║"));
+    assert_eq!(lanes(track_types::DESERT), (2, "┆"));
+    assert_eq!(lanes(track_types::MOUNTAIN), (3, "┃"));
+    assert_eq!(lanes(track_types::HIGHWAY), (3, "┆"));
+}
+
+#[test]
+fn lane_count_override_widens_the_road() {
+    let mut state = base_state(game_modes::SINGLE_PLAYER, track_types::HIGHWAY, weather::CLEAR);
+    state.lane_count = 2;
+    let narrow = Projection::new(AREA, &state, 3.0);
+    state.lane_count = 4;
+    let wide = Projection::new(AREA, &state, 3.0);
+    state.lane_count = 50;
+    let capped = Projection::new(AREA, &state, 3.0);
+
+    assert_eq!((narrow.lanes(), wide.lanes(), capped.lanes()), (2, 4, MAX_LANES));
+    assert!(narrow.road_width(1.0) < straight().road_width(1.0));
+    assert!(capped.road_width(1.0) <= AREA.width as f32);
+
+    // Every lane centre lies on the road, in order, and strays are clamped
+    let (left, right) = wide.road_edges(0.5);
+    let centers: Vec<f32> = (0..4).map(|lane| wide.lane_center(lane, 0.5)).collect();
+    assert!(centers.windows(2).all(|c| c[0] < c[1]));
+    assert!(centers.iter().all(|&x| left < x && x < right));
+    assert_eq!(wide.lane_center(7, 0.5), centers[3]);
+}
+
+#[test]
+fn huge_lane_counts_are_capped() {
+    let objects = Objects::standard();
+    for lane_count in [65536, i32::MAX] {
+        let mut state = base_state(game_modes::SINGLE_PLAYER, track_types::HIGHWAY, weather::CLEAR);
+        objects.attach(&mut state);
+        state.lane_count = lane_count;
+        assert_eq!(Projection::new(AREA, &state, 3.0).lanes(), MAX_LANES, "{lane_count}");
+        render_to_text(100, 40, |f| rust_renderer::render_game(f, &state));
+    }
+}
+
+#[test]
+fn curve_bends_progressively_with_depth() {
+    let mut state = base_state(game_modes::SINGLE_PLAYER, track_types::HIGHWAY, weather::CLEAR);
+    state.curve_offset = 2.0;
+    let right = Projection::new(AREA, &state, 3.0);
+    state.curve_offset = -2.0;
+    let left = Projection::new(AREA, &state, 3.0);
+    let base = straight();
+
+    // The road at the player stays put while the far end swings
+    assert!((right.center(1.0) - base.center(1.0)).abs() < 1e-3);
+    let shift = |scale: f32| right.center(scale) - base.center(scale);
+    assert!(0.0 < shift(0.8) && shift(0.8) < shift(0.5) && shift(0.5) < shift(0.1));
+    assert!((left.center(0.1) - base.center(0.1) + shift(0.1)).abs() < 1e-3);
+}
+
+#[test]
+fn elevation_shifts_the_horizon() {
+    let mut state = base_state(game_modes::SINGLE_PLAYER, track_types::MOUNTAIN, weather::CLEAR);
+    state.elevation = 1.0;
+    let uphill = Projection::new(AREA, &state, 4.0).horizon();
+    state.elevation = -1.0;
+    let downhill = Projection::new(AREA, &state, 4.0).horizon();
+
+    assert!(uphill < straight().horizon() && straight().horizon() < downhill);
+    assert!(uphill >= AREA.y && downhill < AREA.bottom());
+}
+
+#[test]
+fn objects_shrink_with_distance() {
+    let projection = straight();
+
+    assert_eq!(projection.project(-REAR_DISTANCE - 0.1), None);
+    assert_eq!(projection.project(DRAW_DISTANCE), None);
+    assert_eq!(projection.project(-REAR_DISTANCE).unwrap().y, AREA.bottom() - 1);
+
+    let distances = [0.0, 5.0, 15.0, 30.0, 55.0];
+    let projected: Vec<_> = distances.iter().map(|&d| projection.project(d).unwrap()).collect();
+    assert!(projected.windows(2).all(|p| p[0].y >= p[1].y && p[0].scale > p[1].scale));
+    assert!(projected.iter().all(|p| p.y >= projection.horizon()));
+
+    let sizes: Vec<_> = projected.iter().map(|p| SpriteSize::from_scale(p.scale)).collect();
+    assert_eq!(sizes[0], SpriteSize::Large);
+    assert_eq!(sizes[2], SpriteSize::Medium);
+    assert_eq!(sizes[4], SpriteSize::Small);
+
+    // Projection and row lookup agree
+    for p in projected {
+        let scale = projection.row_scale(p.y).unwrap();
+        assert!((scale - p.scale).abs() <= 1.0 / (AREA.bottom() - projection.horizon()) as f32);
+    }
+}
+
+#[test]
+fn distant_cars_use_small_sprites() {
+    let mut objects = Objects::with_counts(2, 0, 0);
+    objects.ai_distances = vec![PLAYER_DISTANCE + 4.0, PLAYER_DISTANCE + 50.0];
+    objects.ai_types = vec![3, 3];
+    let mut state = base_state(game_modes::SINGLE_PLAYER, track_types::HIGHWAY, weather::CLEAR);
+    objects.attach(&mut state);
+
+    let text = render_to_text(100, 40, |f| rust_renderer::render_game(f, &state));
+    assert!(text.contains(" TRUCK "), "near truck is drawn full size");
+    assert_eq!(text.matches("TRUCK").count(), 1, "far truck has no label");
+}
@@ -0,0 +1,123 @@
+//! Asset pack hot reload - polling for changes, swapping between frames and
+//! the error overlay
+
+mod common;
+
+use std::ffi::CString;
+use std::path::PathBuf;
+use common::*;
+use rust_renderer::core::types::{game_modes, track_types, weather};
+use rust_renderer::rendering::assets;
+use rust_renderer::rendering::reload;
+use rust_renderer::{
+    ratatui_asset_reload_error, ratatui_last_error_code, ratatui_unwatch_asset_pack, ratatui_watch_asset_pack,
+    ErrorCode, Renderer, RendererConfig,
+};
+
+fn pack_file(name: &str) -> PathBuf {
+    std::env::temp_dir().join(format!("reload-{}-{}.toml", name, std::process::id()))
+}
+
+fn boost_title(title: &str) -> String {
+    format!("[hud]\nboost = {{ symbol = \"{}\" }}\n", title)
+}
+
+fn frame(renderer: &Renderer) -> String {
+    let mut state = base_state(game_modes::SINGLE_PLAYER, track_types::HIGHWAY, weather::CLEAR);
+    let objects = Objects::standard();
+    objects.attach(&mut state);
+    renderer.render(&state).unwrap();
+    renderer.snapshot_text().unwrap()
+}
+
+/// Stops watching and restores the default registry when dropped, even if
+/// the test failed
+struct Reset(RestoreAssets);
+
+impl Drop for Reset {
+    fn drop(&mut self) {
+        reload::unwatch();
+    }
+}
+
+#[test]
+fn edits_are_swapped_in_between_frames() {
+    let _serial = serial();
+    let _reset = Reset(RestoreAssets);
+    let path = pack_file("edits");
+    std::fs::write(&path, boost_title("TURBO")).unwrap();
+    let renderer = Renderer::new(&RendererConfig { headless: true, width: 100, height: 40, ..Default::default() }).unwrap();
+
+    reload::watch(&path).unwrap();
+    assert!(frame(&renderer).contains("TURBO"));
+    assert!(!reload::poll(), "unchanged file is not reloaded");
+
+    std::fs::write(&path, boost_title("NITROUS")).unwrap();
+    let text = frame(&renderer);
+    assert!(text.contains("NITROUS") && !text.contains("TURBO"));
+
+    // A missing file keeps the last assets
+    std::fs::remove_file(&path).unwrap();
+    assert!(!reload::poll());
+    assert_eq!(assets::active().hud().boost.symbol, "NITROUS");
+
+    reload::unwatch();
+    std::fs::write(&path, boost_title("IGNORED")).unwrap();
+    assert!(!reload::poll());
+    assert_eq!(assets::active().hud().boost.symbol, "NITROUS");
+
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn broken_packs_are_reported_in_an_overlay() {
+    let _serial = serial();
+    let _reset = Reset(RestoreAssets);
+    let path = pack_file("broken");
+    std::fs::write(&path, boost_title("TURBO")).unwrap();
+    let renderer = Renderer::new(&RendererConfig { headless: true, width: 100, height: 40, ..Default::default() }).unwrap();
+    reload::watch(&path).unwrap();
+
+    std::fs::write(&path, "[cars.3]\nsmall = \"▄▄▄▄▄▄▄▄▄▄▄▄\"\n").unwrap();
+    let text = frame(&renderer);
+    assert!(text.contains("Asset pack not reloaded"));
+    assert!(text.contains("cars.3.small"));
+    assert!(text.contains("TURBO"), "last good assets stay active");
+    assert!(reload::error().unwrap().contains("cars.3.small"));
+
+    std::fs::write(&path, boost_title("FIXED")).unwrap();
+    let text = frame(&renderer);
+    assert!(text.contains("FIXED") && !text.contains("Asset pack not reloaded"));
+    assert_eq!(reload::error(), None);
+
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn watching_over_ffi() {
+    let _serial = serial();
+    let _reset = Reset(RestoreAssets);
+    let path = pack_file("ffi");
+    std::fs::write(&path, "[hud]\nboost = 3\n").unwrap();
+    let c_path = CString::new(path.to_str().unwrap()).unwrap();
+
+    // The first load fails but the file stays watched
+    assert!(!unsafe { ratatui_watch_asset_pack(c_path.as_ptr()) });
+    assert_eq!(ratatui_last_error_code(), ErrorCode::InvalidAssets);
+    let len = unsafe { ratatui_asset_reload_error(std::ptr::null_mut(), 0) };
+    assert!(len > 0);
+    let mut buf = vec![0u8; len as usize + 1];
+    unsafe { ratatui_asset_reload_error(buf.as_mut_ptr() as *mut _, buf.len()) };
+    assert!(String::from_utf8_lossy(&buf).contains("hud.boost"));
+
+    std::fs::write(&path, boost_title("TURBO")).unwrap();
+    assert!(reload::poll());
+    assert_eq!(unsafe { ratatui_asset_reload_error(std::ptr::null_mut(), 0) }, 0);
+    assert_eq!(assets::active().hud().boost.symbol, "TURBO");
+
+    ratatui_unwatch_asset_pack();
+    assert!(!unsafe { ratatui_watch_asset_pack(std::ptr::null()) });
+    assert_eq!(ratatui_last_error_code(), ErrorCode::NullPointer);
+
+    std::fs::remove_file(&path).unwrap();
+}